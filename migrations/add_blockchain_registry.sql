@@ -0,0 +1,5 @@
+-- 支持的链改由 system_configs.blockchain 统一配置（数组，每项含 code/name/enabled/fee/minAmount/maxAmount/
+-- confirmations/addressFormat/icon/confirmationTime），取代旧的 {链: 收款地址} 映射与 withdrawal_fee 配置，
+-- withdrawal_fee 不再读取，如有自定义费率需迁移到 blockchain 配置中。
+-- 订单收款地址已改由地址池分配，旧格式的 blockchain 配置不再使用，删除后按默认链配置运行
+DELETE FROM `system_configs` WHERE `config_key` = 'blockchain' AND `config_value` LIKE '{%';
@@ -0,0 +1,46 @@
+-- 优惠券：支持百分比/固定金额折扣、全局与单用户使用次数上限、有效期和算力包限制
+CREATE TABLE `coupons` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '优惠券ID，主键',
+  `code` varchar(64) COLLATE utf8mb4_bin NOT NULL COMMENT '优惠码，用户下单时填写',
+  `discount_type` tinyint unsigned NOT NULL COMMENT '折扣类型：1 百分比折扣 / 2 固定金额减免',
+  `discount_value` decimal(20,8) NOT NULL COMMENT '折扣值：百分比折扣为百分数（如 15 表示减 15%），固定减免为 USDT 金额',
+  `max_discount` decimal(20,8) NOT NULL DEFAULT '0.00000000' COMMENT '百分比折扣的最高减免金额，0 表示不限',
+  `min_amount` decimal(20,8) NOT NULL DEFAULT '0.00000000' COMMENT '订单最低金额，低于该金额不可使用',
+  `total_limit` int unsigned NOT NULL DEFAULT '0' COMMENT '全局可使用次数，0 表示不限',
+  `per_user_limit` int unsigned NOT NULL DEFAULT '1' COMMENT '每个用户可使用次数，0 表示不限',
+  `used_count` int unsigned NOT NULL DEFAULT '0' COMMENT '已使用次数（订单取消或过期后回退）',
+  `starts_at` timestamp NOT NULL COMMENT '生效时间',
+  `ends_at` timestamp NOT NULL COMMENT '失效时间',
+  `status` tinyint unsigned NOT NULL DEFAULT '1' COMMENT '状态：1 启用 / 0 停用',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '最后更新时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `idx_code` (`code`),
+  KEY `idx_status_time` (`status`,`starts_at`,`ends_at`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='优惠券表';
+
+-- 优惠券适用的算力包，没有记录表示适用于所有算力包
+CREATE TABLE `coupon_packages` (
+  `coupon_id` bigint unsigned NOT NULL COMMENT '优惠券ID',
+  `power_package_id` bigint unsigned NOT NULL COMMENT '算力包ID',
+  PRIMARY KEY (`coupon_id`,`power_package_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='优惠券适用算力包表';
+
+-- 优惠券使用记录，订单取消或过期时释放
+CREATE TABLE `coupon_usages` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '使用记录ID，主键',
+  `coupon_id` bigint unsigned NOT NULL COMMENT '优惠券ID',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID',
+  `order_id` varchar(64) COLLATE utf8mb4_bin NOT NULL COMMENT '订单号',
+  `discount_amount` decimal(20,8) NOT NULL COMMENT '本次减免金额',
+  `status` tinyint unsigned NOT NULL DEFAULT '1' COMMENT '状态：1 已使用 / 0 已释放',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '使用时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '最后更新时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `idx_order_id` (`order_id`),
+  KEY `idx_coupon_user` (`coupon_id`,`user_id`,`status`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='优惠券使用记录表';
+
+ALTER TABLE `orders`
+  ADD COLUMN `coupon_id` bigint unsigned DEFAULT NULL COMMENT '使用的优惠券ID' AFTER `coin_pay`,
+  ADD COLUMN `discount_amount` decimal(20,8) NOT NULL DEFAULT '0.00000000' COMMENT '优惠券减免金额，asset_pay + coin_pay = amount - discount_amount' AFTER `coupon_id`;
@@ -0,0 +1,20 @@
+-- 收款地址池：每个待支付订单独占一个收款地址，便于按地址归属链上转账
+-- 地址可由运营预先导入（derivation_index 为 NULL），也可由派生服务按序号生成
+CREATE TABLE `deposit_addresses` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '地址ID，主键',
+  `chain` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '链类型，如 TRC20、ERC20',
+  `address` varchar(128) COLLATE utf8mb4_bin NOT NULL COMMENT '收款地址',
+  `derivation_index` int unsigned DEFAULT NULL COMMENT 'HD 派生序号，预导入地址为 NULL',
+  `status` tinyint unsigned NOT NULL DEFAULT '0' COMMENT '状态：0 空闲 / 1 已分配 / 2 停用',
+  `user_id` bigint unsigned DEFAULT NULL COMMENT '最近一次分配的用户ID',
+  `order_id` varchar(64) COLLATE utf8mb4_bin DEFAULT NULL COMMENT '最近一次分配的订单号',
+  `allocated_at` timestamp NULL DEFAULT NULL COMMENT '最近一次分配时间',
+  `released_at` timestamp NULL DEFAULT NULL COMMENT '最近一次释放时间，冷却期内不会再次分配',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '最后更新时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `idx_chain_address` (`chain`,`address`),
+  UNIQUE KEY `idx_chain_derivation_index` (`chain`,`derivation_index`),
+  KEY `idx_chain_status` (`chain`,`status`,`released_at`),
+  KEY `idx_order_id` (`order_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='订单收款地址池';
@@ -0,0 +1,8 @@
+-- 充值确认检查按最近检查时间轮转，长期无法确认的充值超过最大检查次数（APP_CHAIN__DEPOSIT_MAX_CONFIRM_CHECKS）后
+-- 标记为 expired 并需人工核对，避免积压的充值占满每轮确认批次，导致新的充值一直得不到检查
+ALTER TABLE `deposits`
+  MODIFY COLUMN `status` enum('pending','credited','expired') COLLATE utf8mb4_bin NOT NULL DEFAULT 'pending' COMMENT '状态：pending 确认中 / credited 已入账 / expired 超过确认检查次数，需人工核对',
+  ADD COLUMN `check_attempts` int unsigned NOT NULL DEFAULT '0' COMMENT '确认检查次数' AFTER `confirmations`,
+  ADD COLUMN `checked_at` timestamp NULL DEFAULT NULL COMMENT '最近一次确认检查时间，为空表示尚未检查' AFTER `check_attempts`,
+  DROP KEY `idx_status_chain`,
+  ADD KEY `idx_status_chain_checked` (`status`, `chain`, `checked_at`);
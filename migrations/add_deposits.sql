@@ -0,0 +1,27 @@
+-- 用户充值：每个用户在每条链上绑定一个长期充值地址（deposit_addresses.status = 3），
+-- 后台扫描绑定地址的入账转账，达到确认数后以 recharge 交易记入用户 USDT 可用余额
+ALTER TABLE `deposit_addresses`
+  MODIFY COLUMN `status` tinyint unsigned NOT NULL DEFAULT '0' COMMENT '状态：0 空闲 / 1 已分配 / 2 停用 / 3 用户充值地址（长期绑定，不再释放）',
+  ADD KEY `idx_chain_user_status` (`chain`, `user_id`, `status`);
+
+CREATE TABLE `deposits` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '充值记录ID，主键',
+  `deposit_id` varchar(64) COLLATE utf8mb4_bin NOT NULL COMMENT '充值单号，入账后作为交易记录ID',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID',
+  `chain` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '链类型，如 TRC20、ERC20',
+  `address` varchar(128) COLLATE utf8mb4_bin NOT NULL COMMENT '用户充值地址',
+  `tx_hash` varchar(128) COLLATE utf8mb4_bin NOT NULL COMMENT '链上交易哈希',
+  `from_address` varchar(128) COLLATE utf8mb4_bin NOT NULL COMMENT '转出地址',
+  `amount` decimal(20,8) NOT NULL COMMENT '充值金额',
+  `currency` varchar(20) COLLATE utf8mb4_bin NOT NULL DEFAULT 'USDT' COMMENT '充值币种',
+  `confirmations` int unsigned NOT NULL DEFAULT '0' COMMENT '最近一次扫描到的确认数',
+  `status` enum('pending','credited') COLLATE utf8mb4_bin NOT NULL DEFAULT 'pending' COMMENT '状态：pending 确认中 / credited 已入账',
+  `detected_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '首次扫描到的时间',
+  `credited_at` timestamp NULL DEFAULT NULL COMMENT '入账时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '最后更新时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_deposit_id` (`deposit_id`),
+  UNIQUE KEY `uk_chain_tx_address` (`chain`, `tx_hash`, `address`),
+  KEY `idx_status_chain` (`status`, `chain`),
+  KEY `idx_user_id` (`user_id`, `detected_at`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='用户充值记录表';
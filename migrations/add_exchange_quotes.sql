@@ -0,0 +1,19 @@
+-- 币种兑换报价：按 real_time_prices 的实时价格加点差锁定汇率，有效期内凭报价单号兑换一次
+CREATE TABLE `exchange_quotes` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '报价记录ID，主键',
+  `quote_id` varchar(64) COLLATE utf8mb4_bin NOT NULL COMMENT '报价单号，唯一索引',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID',
+  `from_currency` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '转出币种',
+  `to_currency` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '转入币种',
+  `from_amount` decimal(20,8) NOT NULL COMMENT '转出金额（含手续费）',
+  `fee` decimal(20,8) NOT NULL COMMENT '手续费，以转出币种计',
+  `to_amount` decimal(20,8) NOT NULL COMMENT '到账金额',
+  `exchange_rate` decimal(20,8) NOT NULL COMMENT '锁定汇率：1 单位转出币种 = rate 单位转入币种，已含点差',
+  `market_price` decimal(20,8) NOT NULL COMMENT '报价时的 DG/USDT 市场价格',
+  `expires_at` timestamp NOT NULL COMMENT '报价过期时间',
+  `transaction_id` varchar(64) COLLATE utf8mb4_bin DEFAULT NULL COMMENT '兑换成功后的交易记录ID，null表示未使用',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_quote_id` (`quote_id`),
+  KEY `idx_user_id` (`user_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='币种兑换报价表';
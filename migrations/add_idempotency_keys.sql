@@ -0,0 +1,17 @@
+-- 创建幂等键表，客户端重试携带相同 Idempotency-Key 时重放首次请求的响应
+CREATE TABLE `idempotency_keys` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '幂等记录ID，主键',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID，幂等键按用户隔离',
+  `idempotency_key` varchar(128) COLLATE utf8mb4_bin NOT NULL COMMENT '客户端提交的 Idempotency-Key 请求头',
+  `request_method` varchar(10) COLLATE utf8mb4_bin NOT NULL COMMENT '请求方法',
+  `request_path` varchar(255) COLLATE utf8mb4_bin NOT NULL COMMENT '请求路径',
+  `request_hash` char(64) COLLATE utf8mb4_bin NOT NULL COMMENT '请求方法、路径与请求体的 SHA-256，用于识别同键不同请求',
+  `response_status` smallint unsigned DEFAULT NULL COMMENT '首次响应的 HTTP 状态码，null 表示请求处理中',
+  `response_body` mediumtext COLLATE utf8mb4_bin COMMENT '首次响应的序列化 ApiResponse',
+  `expired_at` timestamp NOT NULL COMMENT '幂等窗口结束时间，过期后同一个键可重新使用',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '记录创建时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '记录最后更新时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `idx_user_key` (`user_id`,`idempotency_key`),
+  KEY `idx_expired_at` (`expired_at`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='接口幂等键表，防止客户端重复提交产生重复订单或重复入账';
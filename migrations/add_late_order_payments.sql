@@ -0,0 +1,8 @@
+-- 迟到的订单支付：订单关闭（支付、取消或过期）后释放的收款地址在冷却期内继续扫描，
+-- 未被已支付订单认领的转账记为该地址最近一次下单用户的充值，达到确认数后记入 USDT 可用余额
+ALTER TABLE `deposits`
+  ADD COLUMN `order_id` varchar(64) COLLATE utf8mb4_bin DEFAULT NULL COMMENT '转入订单收款地址的迟到支付对应的订单号，普通充值为 NULL' AFTER `user_id`,
+  ADD KEY `idx_order_id` (`order_id`);
+
+ALTER TABLE `deposit_addresses`
+  ADD KEY `idx_chain_status_released` (`chain`, `status`, `released_at`, `id`);
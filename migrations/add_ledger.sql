@@ -0,0 +1,44 @@
+-- 复式记账：每次余额变动生成一张凭证（ledger_entries）及借贷平衡的分录（ledger_postings），
+-- 科目余额 = 贷方合计 - 借方合计，用户可用/冻结余额可由分录推导并与 user_assets 核对
+CREATE TABLE `ledger_entries` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '凭证ID，主键',
+  `transaction_id` varchar(64) COLLATE utf8mb4_bin NOT NULL COMMENT '关联交易记录 transactions.transaction_id，同一交易可有多张凭证（如提现冻结、出账）',
+  `user_id` bigint unsigned NOT NULL COMMENT '凭证所属用户ID',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '记账时间',
+  PRIMARY KEY (`id`),
+  KEY `idx_transaction_id` (`transaction_id`),
+  KEY `idx_user_id` (`user_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='记账凭证表';
+
+CREATE TABLE `ledger_postings` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '分录ID，主键',
+  `entry_id` bigint unsigned NOT NULL COMMENT '凭证ID，外键关联ledger_entries表',
+  `account` varchar(32) COLLATE utf8mb4_bin NOT NULL COMMENT '科目：available可用/frozen冻结/external用户链上钱包，或系统科目 sales/rewards/fees/exchange/opening',
+  `user_id` bigint unsigned DEFAULT NULL COMMENT '用户科目所属用户ID，系统科目为null',
+  `currency` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '币种，如USDT、DG、POINTS',
+  `direction` enum('debit','credit') COLLATE utf8mb4_bin NOT NULL COMMENT '借贷方向',
+  `amount` decimal(20,8) NOT NULL COMMENT '金额，恒为正数',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '记账时间',
+  PRIMARY KEY (`id`),
+  KEY `idx_entry_id` (`entry_id`),
+  KEY `idx_user_account` (`user_id`, `account`, `currency`),
+  KEY `idx_account_currency` (`account`, `currency`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='记账分录表，每张凭证的借贷按币种平衡';
+
+-- 期初余额：将现有钱包余额从 opening 科目转入，使账本与 user_assets 一致
+INSERT INTO `ledger_entries` (`transaction_id`, `user_id`)
+SELECT CONCAT('OPENING-', `id`), `user_id` FROM `user_assets`
+WHERE `balance` <> 0 OR `frozen_balance` <> 0;
+
+INSERT INTO `ledger_postings` (`entry_id`, `account`, `user_id`, `currency`, `direction`, `amount`)
+SELECT e.`id`, 'available', a.`user_id`, a.`currency`, 'credit', a.`balance`
+FROM `user_assets` a JOIN `ledger_entries` e ON e.`transaction_id` = CONCAT('OPENING-', a.`id`)
+WHERE a.`balance` <> 0
+UNION ALL
+SELECT e.`id`, 'frozen', a.`user_id`, a.`currency`, 'credit', a.`frozen_balance`
+FROM `user_assets` a JOIN `ledger_entries` e ON e.`transaction_id` = CONCAT('OPENING-', a.`id`)
+WHERE a.`frozen_balance` <> 0
+UNION ALL
+SELECT e.`id`, 'opening', NULL, a.`currency`, 'debit', a.`balance` + a.`frozen_balance`
+FROM `user_assets` a JOIN `ledger_entries` e ON e.`transaction_id` = CONCAT('OPENING-', a.`id`)
+WHERE a.`balance` + a.`frozen_balance` <> 0;
@@ -0,0 +1,10 @@
+-- 待支付订单过期时间：下单时按 APP_ORDER__EXPIRE_MINUTES 写入，定时任务据此关闭超时未支付的订单并退回余额支付部分
+ALTER TABLE `orders`
+  ADD COLUMN `expired_at` timestamp NULL DEFAULT NULL COMMENT '订单过期时间，超过此时间仍未支付的订单会被关闭' AFTER `status`;
+
+-- 历史订单按默认过期时间（30 分钟）补齐
+UPDATE `orders` SET `expired_at` = `created_at` + INTERVAL 30 MINUTE WHERE `expired_at` IS NULL;
+
+ALTER TABLE `orders`
+  MODIFY COLUMN `expired_at` timestamp NOT NULL COMMENT '订单过期时间，超过此时间仍未支付的订单会被关闭',
+  ADD KEY `idx_status_expired_at` (`status`, `expired_at`);
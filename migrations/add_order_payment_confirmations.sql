@@ -0,0 +1,4 @@
+-- 订单链上支付确认：记录确认数，交易哈希唯一防止同一笔转账被多个订单认领
+ALTER TABLE `orders`
+  ADD COLUMN `confirmations` int unsigned NOT NULL DEFAULT '0' COMMENT '区块链确认数，达到链配置的确认数后订单才会被标记为已支付' AFTER `transaction_hash`,
+  ADD UNIQUE KEY `idx_transaction_hash` (`transaction_hash`);
@@ -0,0 +1,5 @@
+-- 升级抵扣：旧算力包剩余价值作为升级订单的订单级抵扣记录在订单上，不再作为现金记入用户余额。
+-- 升级订单取消或过期时不退还抵扣，旧算力恢复为生效状态
+ALTER TABLE `orders`
+  ADD COLUMN `upgrade_power_id` bigint unsigned DEFAULT NULL COMMENT '升级订单对应的旧算力ID（user_power.id），普通订单为 NULL' AFTER `discount_amount`,
+  ADD COLUMN `upgrade_credit` decimal(20,8) NOT NULL DEFAULT '0.00000000' COMMENT '旧算力包剩余价值抵扣金额（USDT）' AFTER `upgrade_power_id`;
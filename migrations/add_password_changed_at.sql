@@ -0,0 +1,3 @@
+-- 记录最近一次修改/重置密码的时间，提现审核时作为账户被盗风险提示
+ALTER TABLE `users`
+  ADD COLUMN `password_changed_at` timestamp NULL DEFAULT NULL COMMENT '最近一次修改或重置密码的时间，null表示注册后未修改' AFTER `last_login_at`;
@@ -0,0 +1,3 @@
+-- 算力包有效期，用于升级时按剩余天数折算旧算力包的抵扣金额
+ALTER TABLE `power_packages`
+  ADD COLUMN `duration_days` int unsigned NOT NULL DEFAULT '365' COMMENT '算力有效期天数，升级时按剩余天数折算旧算力包价值' AFTER `amount`;
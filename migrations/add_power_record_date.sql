@@ -0,0 +1,13 @@
+-- 算力收益记录增加显式的收益日期，并以 (user_power_id, record_date) 唯一约束保证同一算力同一交易日只入账一次，
+-- 每日结算写入收益记录时依赖该约束跳过已入账的算力（INSERT IGNORE）
+ALTER TABLE `user_power_record`
+  ADD COLUMN `record_date` date DEFAULT NULL COMMENT '收益所属交易日（北京时间）' AFTER `amount`;
+
+-- 历史记录的 created_at 即写入的交易日
+UPDATE `user_power_record` SET `record_date` = DATE(`created_at`) WHERE `record_date` IS NULL;
+
+-- 添加唯一约束前先确认没有重复入账的记录，有结果时需人工核对处理：
+-- SELECT user_power_id, record_date, COUNT(*) FROM user_power_record GROUP BY user_power_id, record_date HAVING COUNT(*) > 1;
+ALTER TABLE `user_power_record`
+  MODIFY COLUMN `record_date` date NOT NULL COMMENT '收益所属交易日（北京时间）',
+  ADD UNIQUE KEY `uk_power_record_date` (`user_power_id`,`record_date`);
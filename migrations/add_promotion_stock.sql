@@ -0,0 +1,8 @@
+-- 限时优惠套餐关联实际购买的算力包，下单时按优惠价计价并扣减库存
+ALTER TABLE `promotion_packages`
+  ADD COLUMN `power_package_id` bigint unsigned NOT NULL DEFAULT '0' COMMENT '关联的算力包ID，购买时生成该算力包的算力记录' AFTER `id`,
+  ADD KEY `idx_power_package_id` (`power_package_id`);
+
+-- 订单记录使用的限时优惠套餐，订单取消或过期时据此回补库存
+ALTER TABLE `orders`
+  ADD COLUMN `promotion_package_id` bigint unsigned DEFAULT NULL COMMENT '限时优惠套餐ID，NULL 表示原价购买' AFTER `power_package_id`;
@@ -0,0 +1,20 @@
+-- 余额对账：每日按账本分录推导用户各币种可用/冻结余额并与 user_assets 核对，差异写入对账报告
+CREATE TABLE `reconciliation_reports` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '报告ID，主键',
+  `run_date` date NOT NULL COMMENT '对账日期',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID',
+  `account` varchar(32) COLLATE utf8mb4_bin NOT NULL COMMENT '科目：available可用/frozen冻结',
+  `currency` varchar(20) COLLATE utf8mb4_bin NOT NULL COMMENT '币种，如USDT、DG、POINTS',
+  `ledger_balance` decimal(20,8) NOT NULL COMMENT '由账本分录推导的余额',
+  `wallet_balance` decimal(20,8) NOT NULL COMMENT 'user_assets 中的余额',
+  `drift` decimal(20,8) NOT NULL COMMENT '差异 = 钱包余额 - 账本余额',
+  `account_frozen` tinyint(1) NOT NULL DEFAULT '0' COMMENT '是否因差异超过阈值冻结了用户资金',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_run_user_account` (`run_date`, `user_id`, `account`, `currency`),
+  KEY `idx_user_id` (`user_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='余额对账差异报告表';
+
+-- 资金冻结：对账差异超过阈值时冻结，冻结期间不能提现，管理员核查后解除
+ALTER TABLE `users`
+  ADD COLUMN `funds_frozen` tinyint(1) NOT NULL DEFAULT '0' COMMENT '资金是否因对账差异被冻结' AFTER `is_locked`;
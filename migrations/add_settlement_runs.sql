@@ -0,0 +1,19 @@
+-- 每日收益结算批次：每个北京时间交易日一条，按用户 ID 分批入账，
+-- 每个用户入账与断点推进在同一事务中完成，中断后从断点继续，同一交易日不会重复结算
+CREATE TABLE `settlement_runs` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '结算批次ID，主键',
+  `business_date` date NOT NULL COMMENT '结算的交易日（北京时间）',
+  `status` enum('running','completed','failed') COLLATE utf8mb4_bin NOT NULL DEFAULT 'running' COMMENT '状态：running 结算中 / completed 已完成 / failed 失败待重试',
+  `close_price` decimal(20,8) NOT NULL COMMENT '结算使用的 DG 日收盘价（USDT）',
+  `checkpoint_user_id` bigint unsigned NOT NULL DEFAULT '0' COMMENT '断点：已入账的最大用户ID',
+  `users` int unsigned NOT NULL DEFAULT '0' COMMENT '已入账用户数',
+  `records` int unsigned NOT NULL DEFAULT '0' COMMENT '已写入的算力收益记录数',
+  `credited` decimal(20,8) NOT NULL DEFAULT '0.00000000' COMMENT '已入账 DG 总额',
+  `error` varchar(500) COLLATE utf8mb4_bin DEFAULT NULL COMMENT '最近一次失败原因',
+  `started_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '开始时间',
+  `finished_at` timestamp NULL DEFAULT NULL COMMENT '完成时间',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '最后更新时间，结算中每入账一个用户刷新一次',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_business_date` (`business_date`),
+  KEY `idx_status` (`status`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='每日收益结算批次表';
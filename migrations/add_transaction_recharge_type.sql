@@ -0,0 +1,3 @@
+-- 充值到账记账使用 recharge 交易类型
+ALTER TABLE `transactions`
+  MODIFY COLUMN `types` enum('recharge','withdraw','exchange','purchase','cancel_purchase','airdrop','referral','mining_earning','welcome') CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL COMMENT '交易类型：充值/提现/兑换/购买/撤消购买/空投/邀请/挖矿收益/新手福利';
@@ -0,0 +1,3 @@
+-- 升级抵扣单独记录为 upgrade_credit 交易，交易ID与新订单号相同，随订单支付完成或关闭取消
+ALTER TABLE `transactions`
+  MODIFY COLUMN `types` enum('recharge','withdraw','exchange','purchase','cancel_purchase','airdrop','referral','mining_earning','welcome','upgrade_credit') CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL COMMENT '交易类型：充值/提现/兑换/购买/撤消购买/空投/邀请/挖矿收益/新手福利/升级抵扣';
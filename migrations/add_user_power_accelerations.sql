@@ -0,0 +1,24 @@
+-- 算力加速记录：每个算力每个交易日（北京时间）开启加速时追加一条，只增不改。
+-- 每日收益结算与补结算按该表判断算力在交易日内是否开启加速，不再依赖会被后续加速覆盖的 user_power.start_time
+CREATE TABLE `user_power_accelerations` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '加速记录ID，主键',
+  `user_id` bigint unsigned NOT NULL COMMENT '用户ID',
+  `user_power_id` bigint unsigned NOT NULL COMMENT '用户算力ID（user_power.id）',
+  `business_date` date NOT NULL COMMENT '开启加速的交易日（北京时间）',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '首次开启加速的时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_power_date` (`user_power_id`,`business_date`),
+  KEY `idx_date_user` (`business_date`,`user_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='算力加速记录表';
+
+-- 还原历史加速记录，补结算可据此重放上线前的交易日：
+-- 1. 已入账的收益记录说明算力当日开启过加速，首次加速时间未知，按交易日零点（北京时间）记录
+INSERT IGNORE INTO `user_power_accelerations` (`user_id`, `user_power_id`, `business_date`, `created_at`)
+SELECT `user_id`, `user_power_id`, `record_date`, CONVERT_TZ(TIMESTAMP(`record_date`), '+08:00', @@session.time_zone)
+FROM `user_power_record`;
+
+-- 2. user_power.start_time 保存每个算力最近一次加速时间，更早且未入账的加速时间已被覆盖，无法还原
+INSERT IGNORE INTO `user_power_accelerations` (`user_id`, `user_power_id`, `business_date`, `created_at`)
+SELECT `user_id`, `id`, DATE(CONVERT_TZ(`start_time`, @@session.time_zone, '+08:00')), `start_time`
+FROM `user_power`
+WHERE `start_time` IS NOT NULL;
@@ -0,0 +1,3 @@
+-- 后台权限角色，与会员等级 user_level 无关；管理员只能由运维在数据库中授予
+ALTER TABLE `users`
+  ADD COLUMN `role` enum('user','admin') COLLATE utf8mb4_bin NOT NULL DEFAULT 'user' COMMENT '后台权限角色：user 普通用户 / admin 管理员' AFTER `user_level`;
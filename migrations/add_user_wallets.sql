@@ -0,0 +1,13 @@
+-- 资产迁移到分币种钱包 user_assets：balance 为可用余额，frozen_balance 为冻结余额（提现中等），
+-- users 上的 total_assets（USDT）与 dg_amount（DG）并入对应币种账户后下线
+INSERT INTO `user_assets` (`user_id`, `currency`, `balance`)
+SELECT `id`, 'USDT', `total_assets` FROM `users` WHERE `total_assets` <> 0
+ON DUPLICATE KEY UPDATE `balance` = `user_assets`.`balance` + VALUES(`balance`);
+
+INSERT INTO `user_assets` (`user_id`, `currency`, `balance`)
+SELECT `id`, 'DG', `dg_amount` FROM `users` WHERE `dg_amount` <> 0
+ON DUPLICATE KEY UPDATE `balance` = `user_assets`.`balance` + VALUES(`balance`);
+
+ALTER TABLE `users`
+  DROP COLUMN `total_assets`,
+  DROP COLUMN `dg_amount`;
@@ -0,0 +1,6 @@
+-- 提现出账：记录出账重试次数与退避时间，超过重试上限的提现标记为失败
+ALTER TABLE `withdrawal_requests`
+  ADD COLUMN `payout_attempts` int unsigned NOT NULL DEFAULT '0' COMMENT '出账提交尝试次数，达到上限后提现标记为失败并退回' AFTER `confirmations`,
+  ADD COLUMN `next_payout_at` timestamp NULL DEFAULT NULL COMMENT '下次允许提交出账的时间，用于失败后的退避重试' AFTER `payout_attempts`,
+  ADD COLUMN `payout_error` text COLLATE utf8mb4_bin COMMENT '最近一次出账失败原因' AFTER `next_payout_at`,
+  ADD KEY `idx_status_next_payout_at` (`status`, `next_payout_at`);
@@ -0,0 +1,5 @@
+-- 提现出账人工复核：超过重试上限仍无法确认是否已转出（超时、签名服务 5xx）或广播后长时间未上链的提现
+-- 进入 review 状态，冻结资金不退回，由管理员核对链上结果后完成或退回
+ALTER TABLE `withdrawal_requests`
+  MODIFY COLUMN `status` enum('pending','processing','review','completed','failed','cancelled') COLLATE utf8mb4_bin NOT NULL DEFAULT 'pending' COMMENT '提现状态：pending待审核/processing处理中/review待人工复核/completed已完成/failed失败/cancelled已取消',
+  ADD COLUMN `payout_submitted_at` timestamp NULL DEFAULT NULL COMMENT '出账交易广播时间，用于判断交易是否长时间未上链' AFTER `payout_error`;
@@ -13,13 +13,21 @@ use crate::handler::{paid_order, upgrade_order};
 use crate::{
     handler::{
         about_us::get_about_us,
+        admin::{
+            approve_withdrawal, backfill_settlements, batch_approve_withdrawals,
+            get_payout_reviews, get_pending_withdrawals, get_reconciliation_reports,
+            reject_withdrawal,
+            run_reconciliation, unfreeze_user_funds,
+        },
         airdrop::{
             check_daily_airdrop_status, claim_airdrop, get_airdrop_history, get_airdrop_stats,
             get_airdrops, get_popular_airdrops, get_user_airdrop_eligibility,
         },
         asset::{
-            exchange_currency, get_asset_earnings, get_conversion_records, get_recharge_records,
-            get_supported_blockchains, withdraw_asset,
+            cancel_asset_withdrawal, exchange_currency, get_asset_earnings,
+            get_conversion_records, get_deposit_address, get_recharge_records,
+            get_supported_blockchains, get_wallet,
+            get_withdrawal_detail, get_withdrawal_records, quote_exchange, withdraw_asset,
         },
         auth::{
             change_password, check_security_questions, forgot_password_questions,
@@ -28,7 +36,7 @@ use crate::{
         },
         chart::{get_asset_chart_data, get_leaderboard, get_power_chart_data, get_realtime_data},
         chat::{get_chat_messages, mark_chat_message_read, send_message, ws_chat_handler},
-        cron::{get_cron_status, start_cron_scheduler, stop_cron_scheduler},
+        cron::{get_cron_status, start_cron_scheduler, stop_cron_scheduler, trigger_cron_job},
         earnings::get_earnings,
         home::get_statistics,
         invite::{get_invite_code, get_invite_records, get_invite_rewards},
@@ -45,12 +53,17 @@ use crate::{
             claim_reward, get_my_promotions, get_promotion_detail, get_promotion_packages,
             get_promotions, join_promotion,
         },
-        purchase::{cancel_order, create_order, get_order_detail, get_package_detail},
+        purchase::{
+            cancel_order, create_order, get_order_detail, get_orders, get_package_detail,
+            get_upgrade_quote,
+        },
         system_config::{create_config, delete_config, get_config_by_key, update_config},
         user::get_user_info,
         user_benefit::{claim_benefit, get_benefit_center, get_new_user_benefit},
     },
-    middleware::{jwt_auth_middleware, logging_middleware},
+    middleware::{
+        admin_middleware, idempotency_middleware, jwt_auth_middleware, logging_middleware,
+    },
     state::AppState,
 };
 
@@ -78,14 +91,21 @@ pub async fn create_app(app_state: Arc<AppState>) -> Result<Router, crate::error
         .route("/invite/code", get(get_invite_code))
         .route("/invite/records", get(get_invite_records))
         // Asset center module
+        .route("/asset/wallet", get(get_wallet))
+        .route("/asset/deposit-address", get(get_deposit_address))
         .route("/asset/recharge-records", get(get_recharge_records))
         .route("/asset/conversion-records", get(get_conversion_records))
-        .route("/asset/exchange", post(exchange_currency))
+        .route("/asset/exchange/quote", post(quote_exchange))
         .route(
             "/asset/supported-blockchains",
             get(get_supported_blockchains),
         )
-        .route("/asset/withdraw", post(withdraw_asset))
+        .route("/asset/withdrawal-records", get(get_withdrawal_records))
+        .route("/asset/withdrawals/:withdrawalId", get(get_withdrawal_detail))
+        .route(
+            "/asset/withdrawals/:withdrawalId/cancel",
+            post(cancel_asset_withdrawal),
+        )
         .route("/asset/earnings", get(get_asset_earnings))
         .route("/asset/chart/:symbol", get(get_asset_chart_data))
         // Message center module
@@ -114,14 +134,7 @@ pub async fn create_app(app_state: Arc<AppState>) -> Result<Router, crate::error
             put(mark_chat_message_read),
         )
         // System configuration
-        .route("/system/config", post(create_config))
-        .route("/system/config", delete(delete_config))
-        .route("/system/config", put(update_config))
         .route("/system/config/:key", get(get_config_by_key))
-        // Scheduled task management
-        .route("/cron/status", get(get_cron_status))
-        .route("/cron/start", post(start_cron_scheduler))
-        .route("/cron/stop", post(stop_cron_scheduler))
         // Apply JWT verification middleware to all protected routes
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
@@ -134,6 +147,8 @@ pub async fn create_app(app_state: Arc<AppState>) -> Result<Router, crate::error
         .merge(order())
         .merge(activity())
         .merge(auth())
+        .merge(idempotent(app_state.clone()))
+        .merge(admin(app_state.clone()))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             jwt_auth_middleware,
@@ -184,6 +199,7 @@ pub async fn create_app(app_state: Arc<AppState>) -> Result<Router, crate::error
                     axum::http::header::ORIGIN,
                     axum::http::header::USER_AGENT,
                     HeaderName::from_static("x-requested-with"),
+                    HeaderName::from_static(crate::middleware::IDEMPOTENCY_KEY_HEADER),
                 ])
                 .allow_credentials(true),
         )
@@ -224,7 +240,6 @@ fn power() -> Router<AppState> {
         .route("/power/packages", get(get_all_power_packages)) //✅
         .route("/power/stats", post(get_power_stats)) //✅
         .route("/power/upgrade/:levelId", post(upgrade_level))
-        .route("/power/withdraw", post(withdraw_power))
         .route("/power/withdrawal", get(get_withdrawal))
         .route(
             "/power/withdrawal/:withdrawalId/cancel",
@@ -236,18 +251,16 @@ fn power() -> Router<AppState> {
 
 fn order() -> Router<AppState> {
     let route = Router::new()
-        .route("/purchase/order", post(create_order)) //✅
+        .route("/purchase/orders", get(get_orders))
+        .route("/purchase/orders/upgrade/quote", get(get_upgrade_quote))
         .route("/purchase/orders/:orderId", get(get_order_detail))
-        .route("/purchase/orders/:orderId/cancel", post(cancel_order)) //✅
-        .route("/purchase/orders/:orderId/paid", post(paid_order)) //✅
-        .route("/purchase/orders/upgrade", post(upgrade_order)); //✅
+        .route("/purchase/orders/:orderId/cancel", post(cancel_order)); //✅
 
     return route;
 }
 
 fn activity() -> Router<AppState> {
     let route = Router::new()
-        .route("/activity/welcome", get(welcome_bonus)) //✅
         .route("/airdrops", get(get_airdrops))
         .route("/airdrops/claim", post(claim_airdrop))
         .route("/airdrops/history", get(get_airdrop_history))
@@ -259,6 +272,64 @@ fn activity() -> Router<AppState> {
     return route;
 }
 
+/// 需要防重复提交的接口，客户端可携带 Idempotency-Key 请求头安全重试
+fn idempotent(app_state: Arc<AppState>) -> Router<AppState> {
+    Router::new()
+        .route("/purchase/order", post(create_order)) //✅
+        .route("/purchase/orders/:orderId/paid", post(paid_order)) //✅
+        .route("/purchase/orders/upgrade", post(upgrade_order)) //✅
+        .route("/activity/welcome", get(welcome_bonus)) //✅
+        .route("/asset/exchange", post(exchange_currency))
+        .route("/asset/withdraw", post(withdraw_asset))
+        .route("/power/withdraw", post(withdraw_power))
+        .route_layer(middleware::from_fn_with_state(
+            app_state,
+            idempotency_middleware,
+        ))
+}
+
+/// 管理后台接口，需要管理员权限
+fn admin(app_state: Arc<AppState>) -> Router<AppState> {
+    Router::new()
+        .route("/admin/withdrawals", get(get_pending_withdrawals))
+        .route("/admin/withdrawals/payout-reviews", get(get_payout_reviews))
+        .route(
+            "/admin/withdrawals/batch-approve",
+            post(batch_approve_withdrawals),
+        )
+        .route(
+            "/admin/withdrawals/:withdrawalId/approve",
+            post(approve_withdrawal),
+        )
+        .route(
+            "/admin/withdrawals/:withdrawalId/reject",
+            post(reject_withdrawal),
+        )
+        .route(
+            "/admin/reconciliation/reports",
+            get(get_reconciliation_reports),
+        )
+        .route("/admin/reconciliation/run", post(run_reconciliation))
+        .route(
+            "/admin/users/:userId/unfreeze-funds",
+            post(unfreeze_user_funds),
+        )
+        .route("/admin/settlements/backfill", post(backfill_settlements))
+        // System configuration
+        .route("/system/config", post(create_config))
+        .route("/system/config", delete(delete_config))
+        .route("/system/config", put(update_config))
+        // Scheduled task management
+        .route("/cron/status", get(get_cron_status))
+        .route("/cron/start", post(start_cron_scheduler))
+        .route("/cron/stop", post(stop_cron_scheduler))
+        .route("/admin/cron/jobs/:name/run", post(trigger_cron_job))
+        .route_layer(middleware::from_fn_with_state(
+            app_state,
+            admin_middleware,
+        ))
+}
+
 fn auth() -> Router<AppState> {
     let routes = Router::new()
         // Authentication management module
@@ -0,0 +1,111 @@
+use super::error::{ChainError, Result};
+use crate::config::ChainConfig;
+use axum::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 收款地址派生器：按序号确定性地生成地址（如 HD 钱包 m/44'/coin'/0'/0/index），
+/// 私钥不进入本服务，只需返回地址
+#[async_trait]
+pub trait AddressProvider: Send + Sync {
+    /// 链类型，与 orders.blockchain_type 一致
+    fn chain(&self) -> &str;
+
+    /// 派生指定序号的收款地址，同一序号必须始终返回同一地址
+    async fn derive_address(&self, index: u32) -> Result<String>;
+}
+
+/// 按链类型注册的地址派生器集合，未注册的链只能使用预导入的地址池
+#[derive(Clone, Default)]
+pub struct AddressProviders {
+    providers: HashMap<String, Arc<dyn AddressProvider>>,
+}
+
+impl AddressProviders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 根据配置创建派生器：mode = fake 时使用模拟派生，配置了派生服务地址时使用远程派生
+    pub fn from_config(config: &ChainConfig) -> Result<Self> {
+        let mut providers = Self::new();
+        if config.mode == "fake" {
+            for chain in [super::CHAIN_TRC20, super::CHAIN_ERC20] {
+                providers.register(Arc::new(super::fake::FakeAddressProvider::new(chain)));
+            }
+            return Ok(providers);
+        }
+
+        if !config.address_derive_url.is_empty() {
+            for chain in [super::CHAIN_TRC20, super::CHAIN_ERC20] {
+                providers.register(Arc::new(RemoteAddressProvider::new(config, chain)?));
+            }
+        }
+        Ok(providers)
+    }
+
+    pub fn register(&mut self, provider: Arc<dyn AddressProvider>) {
+        self.providers.insert(provider.chain().to_string(), provider);
+    }
+
+    pub fn get(&self, chain: &str) -> Option<Arc<dyn AddressProvider>> {
+        self.providers.get(chain).cloned()
+    }
+}
+
+/// 调用外部钱包/签名服务派生地址：POST {"chain", "index"}，返回 {"address"}
+pub struct RemoteAddressProvider {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+    chain: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeriveResponse {
+    address: String,
+}
+
+impl RemoteAddressProvider {
+    pub fn new(config: &ChainConfig, chain: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.address_derive_url.clone(),
+            token: config.address_derive_token.clone(),
+            chain: chain.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl AddressProvider for RemoteAddressProvider {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    async fn derive_address(&self, index: u32) -> Result<String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "chain": self.chain, "index": index }));
+        if !self.token.is_empty() {
+            request = request.bearer_auth(&self.token);
+        }
+        let resp: DeriveResponse = request.send().await?.error_for_status()?.json().await?;
+        if resp.address.is_empty() {
+            return Err(ChainError::ResponseError(format!(
+                "empty {} address derived for index {}",
+                self.chain, index
+            )));
+        }
+
+        Ok(resp.address)
+    }
+}
@@ -0,0 +1,287 @@
+use super::error::{ChainError, Result};
+use super::payout::{PayoutProvider, PayoutRequest, PayoutSigner, PayoutStatus};
+use super::{parse_token_amount, ChainTransfer, ChainWatcher, CHAIN_ERC20};
+use crate::config::ChainConfig;
+use axum::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// ERC20 Transfer(address,address,uint256) 事件签名
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// 基于以太坊 JSON-RPC 的 ERC20 支付监听器
+pub struct Erc20Watcher {
+    client: reqwest::Client,
+    rpc_url: String,
+    contract: String,
+    decimals: u32,
+    required_confirmations: u64,
+    lookback_blocks: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Log {
+    topics: Vec<String>,
+    data: String,
+    block_number: Option<String>,
+    transaction_hash: Option<String>,
+    #[serde(default)]
+    removed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Receipt {
+    block_number: Option<String>,
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Block {
+    timestamp: String,
+}
+
+impl Erc20Watcher {
+    pub fn new(config: &ChainConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            rpc_url: config.erc20_rpc_url.clone(),
+            contract: config.erc20_usdt_contract.to_lowercase(),
+            decimals: config.erc20_token_decimals,
+            required_confirmations: config.erc20_confirmations,
+            lookback_blocks: config.erc20_lookback_blocks,
+        })
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        let resp: RpcResponse<T> = self
+            .client
+            .post(&self.rpc_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(err) = resp.error {
+            return Err(ChainError::ResponseError(format!(
+                "{} failed ({}): {}",
+                method, err.code, err.message
+            )));
+        }
+
+        Ok(resp.result)
+    }
+
+    async fn block_number(&self) -> Result<u64> {
+        let number: Option<String> = self.call("eth_blockNumber", json!([])).await?;
+        let number = number
+            .ok_or_else(|| ChainError::ResponseError("eth_blockNumber returned null".to_string()))?;
+        parse_hex_u64(&number)
+    }
+
+    async fn block_timestamp(&self, block_number: &str) -> Result<Option<OffsetDateTime>> {
+        let block: Option<Block> = self
+            .call("eth_getBlockByNumber", json!([block_number, false]))
+            .await?;
+        let Some(block) = block else {
+            return Ok(None);
+        };
+
+        let seconds = parse_hex_u64(&block.timestamp)?;
+        Ok(OffsetDateTime::from_unix_timestamp(seconds as i64).ok())
+    }
+
+    /// 根据交易回执查询执行结果与确认数
+    async fn transaction_status(&self, tx_hash: &str) -> Result<PayoutStatus> {
+        let receipt: Option<Receipt> = self
+            .call("eth_getTransactionReceipt", json!([tx_hash]))
+            .await?;
+        let Some(receipt) = receipt else {
+            return Ok(PayoutStatus::Pending { confirmations: 0 });
+        };
+        // status 0x0 表示交易执行失败
+        if receipt.status.as_deref() == Some("0x0") {
+            return Ok(PayoutStatus::Failed("execution reverted".to_string()));
+        }
+        let Some(block_number) = receipt.block_number else {
+            return Ok(PayoutStatus::Pending { confirmations: 0 });
+        };
+
+        let block_number = parse_hex_u64(&block_number)?;
+        let latest = self.block_number().await?;
+        Ok(PayoutStatus::Pending {
+            confirmations: latest.saturating_sub(block_number) + 1,
+        })
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for Erc20Watcher {
+    fn chain(&self) -> &str {
+        CHAIN_ERC20
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.required_confirmations
+    }
+
+    async fn incoming_transfers(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+    ) -> Result<Vec<ChainTransfer>> {
+        let latest = self.block_number().await?;
+        let from_block = latest.saturating_sub(self.lookback_blocks);
+        let to_topic = address_topic(address)?;
+        let logs: Option<Vec<Log>> = self
+            .call(
+                "eth_getLogs",
+                json!([{
+                    "fromBlock": format!("0x{:x}", from_block),
+                    "toBlock": "latest",
+                    "address": self.contract,
+                    "topics": [TRANSFER_TOPIC, Value::Null, to_topic],
+                }]),
+            )
+            .await?;
+
+        let mut timestamps: HashMap<String, Option<OffsetDateTime>> = HashMap::new();
+        let mut transfers = Vec::new();
+        for log in logs.unwrap_or_default() {
+            if log.removed || log.topics.len() < 3 {
+                continue;
+            }
+            let (Some(tx_hash), Some(block_number)) = (log.transaction_hash, log.block_number)
+            else {
+                continue;
+            };
+
+            let timestamp = match timestamps.get(&block_number) {
+                Some(timestamp) => *timestamp,
+                None => {
+                    let timestamp = self.block_timestamp(&block_number).await?;
+                    timestamps.insert(block_number.clone(), timestamp);
+                    timestamp
+                }
+            };
+            if matches!(timestamp, Some(ts) if ts < since) {
+                continue;
+            }
+
+            transfers.push(ChainTransfer {
+                tx_hash,
+                from_address: topic_address(&log.topics[1]),
+                to_address: topic_address(&log.topics[2]),
+                amount: parse_token_amount(&log.data, 16, self.decimals)?,
+                timestamp,
+            });
+        }
+
+        Ok(transfers)
+    }
+
+    async fn confirmations(&self, tx_hash: &str) -> Result<u64> {
+        // 执行失败的交易永远不会被确认
+        match self.transaction_status(tx_hash).await? {
+            PayoutStatus::Pending { confirmations } => Ok(confirmations),
+            PayoutStatus::Failed(_) => Ok(0),
+        }
+    }
+}
+
+/// ERC20 USDT 出账：通过签名服务广播，通过 JSON-RPC 查询确认
+pub struct Erc20Payout {
+    signer: PayoutSigner,
+    watcher: Erc20Watcher,
+}
+
+impl Erc20Payout {
+    pub fn new(config: &ChainConfig) -> Result<Self> {
+        Ok(Self {
+            signer: PayoutSigner::new(config)?,
+            watcher: Erc20Watcher::new(config)?,
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutProvider for Erc20Payout {
+    fn chain(&self) -> &str {
+        CHAIN_ERC20
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.watcher.required_confirmations
+    }
+
+    async fn submit(&self, request: &PayoutRequest) -> Result<String> {
+        self.signer
+            .transfer(CHAIN_ERC20, &self.watcher.contract, request)
+            .await
+    }
+
+    async fn status(&self, tx_hash: &str) -> Result<PayoutStatus> {
+        self.watcher.transaction_status(tx_hash).await
+    }
+}
+
+fn parse_hex_u64(value: &str) -> Result<u64> {
+    u64::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map_err(|e| ChainError::ResponseError(format!("invalid hex number {}: {}", value, e)))
+}
+
+/// 地址左补零为 32 字节的事件 topic
+fn address_topic(address: &str) -> Result<String> {
+    let hex = address.trim_start_matches("0x").to_lowercase();
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ChainError::RequestError(format!(
+            "invalid ERC20 address: {}",
+            address
+        )));
+    }
+
+    Ok(format!("0x{:0>64}", hex))
+}
+
+/// 从事件 topic 中取出后 20 字节地址
+fn topic_address(topic: &str) -> String {
+    let hex = topic.trim_start_matches("0x");
+    let start = hex.len().saturating_sub(40);
+    format!("0x{}", &hex[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_topic_round_trip() {
+        let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        let topic = address_topic(address).unwrap();
+        assert_eq!(topic.len(), 66);
+        assert_eq!(topic_address(&topic), address.to_lowercase());
+        assert!(address_topic("0x1234").is_err());
+    }
+}
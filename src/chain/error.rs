@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ChainError {
+    #[error("Chain request error: {0}")]
+    RequestError(String),
+
+    #[error("Chain response error: {0}")]
+    ResponseError(String),
+
+    /// 签名服务明确拒绝了请求（4xx），交易不会被广播
+    #[error("Chain request rejected: {0}")]
+    Rejected(String),
+
+    #[error("Unsupported chain: {0}")]
+    UnsupportedChain(String),
+}
+
+impl From<reqwest::Error> for ChainError {
+    fn from(err: reqwest::Error) -> Self {
+        ChainError::RequestError(err.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ChainError>;
@@ -0,0 +1,462 @@
+use super::address::AddressProvider;
+use super::error::{ChainError, Result};
+use super::payout::{PayoutProvider, PayoutRequest, PayoutStatus};
+use super::{ChainTransfer, ChainWatcher, CHAIN_ERC20};
+use axum::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use time::OffsetDateTime;
+
+/// 进程内模拟链，用于测试和本地开发，转账与确认数由调用方注入
+pub struct FakeChainWatcher {
+    chain: String,
+    required_confirmations: u64,
+    transfers: RwLock<Vec<ChainTransfer>>,
+    confirmations: RwLock<HashMap<String, u64>>,
+}
+
+impl FakeChainWatcher {
+    pub fn new(chain: &str, required_confirmations: u64) -> Self {
+        Self {
+            chain: chain.to_string(),
+            required_confirmations,
+            transfers: RwLock::new(Vec::new()),
+            confirmations: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 注入一笔转账，初始确认数为 0
+    pub fn push_transfer(&self, transfer: ChainTransfer) {
+        self.confirmations
+            .write()
+            .unwrap()
+            .entry(transfer.tx_hash.clone())
+            .or_insert(0);
+        self.transfers.write().unwrap().push(transfer);
+    }
+
+    /// 设置交易的确认数
+    pub fn set_confirmations(&self, tx_hash: &str, confirmations: u64) {
+        self.confirmations
+            .write()
+            .unwrap()
+            .insert(tx_hash.to_string(), confirmations);
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for FakeChainWatcher {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.required_confirmations
+    }
+
+    async fn incoming_transfers(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+    ) -> Result<Vec<ChainTransfer>> {
+        let transfers = self.transfers.read().unwrap();
+        Ok(transfers
+            .iter()
+            .filter(|t| t.to_address == address)
+            .filter(|t| t.timestamp.is_none_or(|ts| ts >= since))
+            .cloned()
+            .collect())
+    }
+
+    async fn confirmations(&self, tx_hash: &str) -> Result<u64> {
+        Ok(self
+            .confirmations
+            .read()
+            .unwrap()
+            .get(tx_hash)
+            .copied()
+            .unwrap_or(0))
+    }
+}
+
+/// 模拟链数据文件中的一笔转账
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTransfer {
+    pub chain: String,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: Decimal,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub timestamp: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub confirmations: u64,
+}
+
+/// 由 JSON 文件驱动的模拟链，每次查询都重新读取文件，
+/// 本地开发和测试时直接编辑文件即可模拟充值到账与确认数增长
+pub struct FileChainWatcher {
+    chain: String,
+    required_confirmations: u64,
+    path: PathBuf,
+}
+
+impl FileChainWatcher {
+    pub fn new(chain: &str, required_confirmations: u64, path: impl Into<PathBuf>) -> Self {
+        Self {
+            chain: chain.to_string(),
+            required_confirmations,
+            path: path.into(),
+        }
+    }
+
+    /// 读取本链的转账，文件不存在时视为没有转账
+    fn load(&self) -> Result<Vec<FeedTransfer>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(ChainError::RequestError(format!(
+                    "failed to read {}: {}",
+                    self.path.display(),
+                    err
+                )))
+            }
+        };
+        let transfers: Vec<FeedTransfer> = serde_json::from_str(&content).map_err(|err| {
+            ChainError::ResponseError(format!("invalid feed {}: {}", self.path.display(), err))
+        })?;
+
+        Ok(transfers
+            .into_iter()
+            .filter(|t| t.chain == self.chain)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for FileChainWatcher {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.required_confirmations
+    }
+
+    async fn incoming_transfers(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+    ) -> Result<Vec<ChainTransfer>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .filter(|t| t.to_address == address)
+            .filter(|t| t.timestamp.is_none_or(|ts| ts >= since))
+            .map(|t| ChainTransfer {
+                tx_hash: t.tx_hash,
+                from_address: t.from_address,
+                to_address: t.to_address,
+                amount: t.amount,
+                timestamp: t.timestamp,
+            })
+            .collect())
+    }
+
+    async fn confirmations(&self, tx_hash: &str) -> Result<u64> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .find(|t| t.tx_hash == tx_hash)
+            .map_or(0, |t| t.confirmations))
+    }
+}
+
+/// 模拟地址派生：由链类型和序号哈希生成格式近似的地址，仅用于测试和本地开发
+pub struct FakeAddressProvider {
+    chain: String,
+}
+
+impl FakeAddressProvider {
+    pub fn new(chain: &str) -> Self {
+        Self {
+            chain: chain.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AddressProvider for FakeAddressProvider {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    async fn derive_address(&self, index: u32) -> Result<String> {
+        let digest = Sha256::digest(format!("{}:{}", self.chain, index).as_bytes());
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        if self.chain == CHAIN_ERC20 {
+            Ok(format!("0x{}", &hex[..40]))
+        } else {
+            Ok(format!("T{}", &hex[..33]))
+        }
+    }
+}
+
+/// 模拟出账记录
+#[derive(Debug, Clone)]
+pub struct FakePayout {
+    pub tx_hash: String,
+    pub request: PayoutRequest,
+    pub confirmations: u64,
+    pub failure: Option<String>,
+}
+
+/// 进程内模拟出账链：按提现单号去重生成交易哈希，出块与失败由调用方控制，
+/// auto_mine 开启时每次查询状态自动出一个块，便于本地开发
+pub struct FakePayoutProvider {
+    chain: String,
+    required_confirmations: u64,
+    auto_mine: bool,
+    failing_submits: RwLock<u32>,
+    payouts: RwLock<HashMap<String, FakePayout>>,
+}
+
+impl FakePayoutProvider {
+    pub fn new(chain: &str, required_confirmations: u64) -> Self {
+        Self {
+            chain: chain.to_string(),
+            required_confirmations,
+            auto_mine: false,
+            failing_submits: RwLock::new(0),
+            payouts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_auto_mine(mut self) -> Self {
+        self.auto_mine = true;
+        self
+    }
+
+    /// 让接下来的 times 次提交返回错误，模拟签名服务或节点不可用
+    pub fn fail_next_submits(&self, times: u32) {
+        *self.failing_submits.write().unwrap() = times;
+    }
+
+    /// 所有未失败的出账增加 blocks 个确认
+    pub fn mine(&self, blocks: u64) {
+        for payout in self.payouts.write().unwrap().values_mut() {
+            if payout.failure.is_none() {
+                payout.confirmations += blocks;
+            }
+        }
+    }
+
+    /// 将出账标记为链上执行失败
+    pub fn revert(&self, tx_hash: &str, reason: &str) {
+        if let Some(payout) = self
+            .payouts
+            .write()
+            .unwrap()
+            .values_mut()
+            .find(|payout| payout.tx_hash == tx_hash)
+        {
+            payout.failure = Some(reason.to_string());
+        }
+    }
+
+    /// 已广播的出账
+    pub fn payouts(&self) -> Vec<FakePayout> {
+        self.payouts.read().unwrap().values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl PayoutProvider for FakePayoutProvider {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.required_confirmations
+    }
+
+    async fn submit(&self, request: &PayoutRequest) -> Result<String> {
+        {
+            let mut failing = self.failing_submits.write().unwrap();
+            if *failing > 0 {
+                *failing -= 1;
+                return Err(ChainError::RequestError(format!(
+                    "fake {} signer unavailable",
+                    self.chain
+                )));
+            }
+        }
+
+        let mut payouts = self.payouts.write().unwrap();
+        let payout = payouts
+            .entry(request.request_id.clone())
+            .or_insert_with(|| {
+                let digest =
+                    Sha256::digest(format!("{}:{}", self.chain, request.request_id).as_bytes());
+                FakePayout {
+                    tx_hash: digest.iter().map(|b| format!("{:02x}", b)).collect(),
+                    request: request.clone(),
+                    confirmations: 0,
+                    failure: None,
+                }
+            });
+        Ok(payout.tx_hash.clone())
+    }
+
+    async fn status(&self, tx_hash: &str) -> Result<PayoutStatus> {
+        if self.auto_mine {
+            self.mine(1);
+        }
+        let payouts = self.payouts.read().unwrap();
+        let Some(payout) = payouts.values().find(|payout| payout.tx_hash == tx_hash) else {
+            return Ok(PayoutStatus::Pending { confirmations: 0 });
+        };
+
+        Ok(match &payout.failure {
+            Some(reason) => PayoutStatus::Failed(reason.clone()),
+            None => PayoutStatus::Pending {
+                confirmations: payout.confirmations,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn transfer(hash: &str, to: &str, amount: i64, timestamp: OffsetDateTime) -> ChainTransfer {
+        ChainTransfer {
+            tx_hash: hash.to_string(),
+            from_address: "TSender".to_string(),
+            to_address: to.to_string(),
+            amount: Decimal::from(amount),
+            timestamp: Some(timestamp),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fake_watcher_filters_by_address_and_time() {
+        let watcher = FakeChainWatcher::new("TRC20", 19);
+        let now = OffsetDateTime::now_utc();
+        watcher.push_transfer(transfer("h1", "TAddr", 100, now - Duration::hours(2)));
+        watcher.push_transfer(transfer("h2", "TAddr", 200, now));
+        watcher.push_transfer(transfer("h3", "TOther", 300, now));
+
+        let transfers = watcher
+            .incoming_transfers("TAddr", now - Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].tx_hash, "h2");
+    }
+
+    #[tokio::test]
+    async fn test_fake_address_provider_is_deterministic() {
+        let provider = FakeAddressProvider::new(CHAIN_ERC20);
+        let first = provider.derive_address(0).await.unwrap();
+        assert_eq!(first.len(), 42);
+        assert_eq!(first, provider.derive_address(0).await.unwrap());
+        assert_ne!(first, provider.derive_address(1).await.unwrap());
+        assert_eq!(
+            FakeAddressProvider::new("TRC20").derive_address(0).await.unwrap().len(),
+            34
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fake_watcher_confirmations() {
+        let watcher = FakeChainWatcher::new("ERC20", 12);
+        let now = OffsetDateTime::now_utc();
+        watcher.push_transfer(transfer("h1", "0xaddr", 100, now));
+        assert_eq!(watcher.confirmations("h1").await.unwrap(), 0);
+
+        watcher.set_confirmations("h1", 12);
+        assert_eq!(watcher.confirmations("h1").await.unwrap(), 12);
+        assert_eq!(watcher.confirmations("missing").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_file_watcher_reads_feed() {
+        let path = std::env::temp_dir().join(format!("dg-deposit-feed-{}.json", std::process::id()));
+        let watcher = FileChainWatcher::new("TRC20", 19, &path);
+        let since = OffsetDateTime::now_utc() - Duration::hours(1);
+        assert!(watcher.incoming_transfers("TAddr", since).await.unwrap().is_empty());
+
+        let ts = OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap();
+        std::fs::write(
+            &path,
+            format!(
+                r#"[
+                    {{"chain": "TRC20", "txHash": "h1", "fromAddress": "TSender", "toAddress": "TAddr", "amount": "100.5", "timestamp": "{ts}", "confirmations": 20}},
+                    {{"chain": "TRC20", "txHash": "h2", "fromAddress": "TSender", "toAddress": "TOther", "amount": "1"}},
+                    {{"chain": "ERC20", "txHash": "h3", "fromAddress": "0xsender", "toAddress": "TAddr", "amount": "1"}}
+                ]"#
+            ),
+        )
+        .unwrap();
+
+        let transfers = watcher.incoming_transfers("TAddr", since).await.unwrap();
+        let confirmations = watcher.confirmations("h1").await.unwrap();
+        let other_chain = watcher.confirmations("h3").await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].tx_hash, "h1");
+        assert_eq!(transfers[0].amount, Decimal::new(1005, 1));
+        assert_eq!(confirmations, 20);
+        assert_eq!(other_chain, 0);
+    }
+
+    fn payout_request(request_id: &str) -> PayoutRequest {
+        PayoutRequest {
+            request_id: request_id.to_string(),
+            to_address: "TReceiver".to_string(),
+            amount: Decimal::from(99),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fake_payout_submit_is_idempotent() {
+        let provider = FakePayoutProvider::new("TRC20", 19);
+        let first = provider.submit(&payout_request("W1")).await.unwrap();
+        assert_eq!(first, provider.submit(&payout_request("W1")).await.unwrap());
+        assert_ne!(first, provider.submit(&payout_request("W2")).await.unwrap());
+        assert_eq!(provider.payouts().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fake_payout_confirmations_and_failures() {
+        let provider = FakePayoutProvider::new("ERC20", 12);
+        provider.fail_next_submits(1);
+        assert!(provider.submit(&payout_request("W1")).await.is_err());
+
+        let hash = provider.submit(&payout_request("W1")).await.unwrap();
+        assert_eq!(
+            provider.status(&hash).await.unwrap(),
+            PayoutStatus::Pending { confirmations: 0 }
+        );
+        provider.mine(12);
+        assert_eq!(
+            provider.status(&hash).await.unwrap(),
+            PayoutStatus::Pending { confirmations: 12 }
+        );
+
+        provider.revert(&hash, "REVERT");
+        assert_eq!(
+            provider.status(&hash).await.unwrap(),
+            PayoutStatus::Failed("REVERT".to_string())
+        );
+    }
+}
@@ -0,0 +1,144 @@
+pub mod address;
+pub mod erc20;
+pub mod error;
+pub mod fake;
+pub mod payout;
+pub mod trc20;
+
+use crate::config::ChainConfig;
+use axum::async_trait;
+use error::{ChainError, Result};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+pub const CHAIN_TRC20: &str = "TRC20";
+pub const CHAIN_ERC20: &str = "ERC20";
+
+/// 链上入账转账记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainTransfer {
+    pub tx_hash: String,
+    pub from_address: String,
+    pub to_address: String,
+    /// 已按代币精度换算后的金额
+    pub amount: Decimal,
+    /// 出块时间，节点未返回时为 None
+    pub timestamp: Option<OffsetDateTime>,
+}
+
+/// 链上支付监听器，每条链一个实现
+#[async_trait]
+pub trait ChainWatcher: Send + Sync {
+    /// 链类型，与 orders.blockchain_type 一致，如 TRC20、ERC20
+    fn chain(&self) -> &str;
+
+    /// 认定支付完成所需的最少确认数
+    fn required_confirmations(&self) -> u64;
+
+    /// 查询地址在指定时间之后收到的代币转账
+    async fn incoming_transfers(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+    ) -> Result<Vec<ChainTransfer>>;
+
+    /// 查询交易当前的确认数，未上链返回 0
+    async fn confirmations(&self, tx_hash: &str) -> Result<u64>;
+}
+
+/// 按链类型注册的监听器集合
+#[derive(Clone, Default)]
+pub struct ChainWatchers {
+    watchers: HashMap<String, Arc<dyn ChainWatcher>>,
+}
+
+impl ChainWatchers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 根据配置创建监听器，mode = fake 时使用进程内模拟链，
+    /// 配置了充值模拟数据文件时改用文件驱动的模拟链
+    pub fn from_config(config: &ChainConfig) -> Result<Self> {
+        let mut watchers = Self::new();
+        if config.mode == "fake" && !config.deposit_feed_file.is_empty() {
+            watchers.register(Arc::new(fake::FileChainWatcher::new(
+                CHAIN_TRC20,
+                config.trc20_confirmations,
+                &config.deposit_feed_file,
+            )));
+            watchers.register(Arc::new(fake::FileChainWatcher::new(
+                CHAIN_ERC20,
+                config.erc20_confirmations,
+                &config.deposit_feed_file,
+            )));
+            return Ok(watchers);
+        }
+        if config.mode == "fake" {
+            watchers.register(Arc::new(fake::FakeChainWatcher::new(
+                CHAIN_TRC20,
+                config.trc20_confirmations,
+            )));
+            watchers.register(Arc::new(fake::FakeChainWatcher::new(
+                CHAIN_ERC20,
+                config.erc20_confirmations,
+            )));
+            return Ok(watchers);
+        }
+
+        if !config.trc20_api_url.is_empty() {
+            watchers.register(Arc::new(trc20::Trc20Watcher::new(config)?));
+        }
+        if !config.erc20_rpc_url.is_empty() {
+            watchers.register(Arc::new(erc20::Erc20Watcher::new(config)?));
+        }
+        Ok(watchers)
+    }
+
+    pub fn register(&mut self, watcher: Arc<dyn ChainWatcher>) {
+        self.watchers.insert(watcher.chain().to_string(), watcher);
+    }
+
+    pub fn get(&self, chain: &str) -> Result<Arc<dyn ChainWatcher>> {
+        self.watchers
+            .get(chain)
+            .cloned()
+            .ok_or_else(|| ChainError::UnsupportedChain(chain.to_string()))
+    }
+}
+
+/// 将链上整数金额按代币精度换算为 Decimal
+pub fn parse_token_amount(raw: &str, radix: u32, decimals: u32) -> Result<Decimal> {
+    let digits = raw.trim_start_matches("0x");
+    let digits = if digits.is_empty() { "0" } else { digits };
+    let value = u128::from_str_radix(digits, radix)
+        .map_err(|e| ChainError::ResponseError(format!("invalid token amount {}: {}", raw, e)))?;
+    let value = i128::try_from(value)
+        .map_err(|_| ChainError::ResponseError(format!("token amount overflow: {}", raw)))?;
+    Decimal::try_from_i128_with_scale(value, decimals)
+        .map(|amount| amount.normalize())
+        .map_err(|e| ChainError::ResponseError(format!("token amount overflow {}: {}", raw, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_token_amount() {
+        assert_eq!(
+            parse_token_amount("1500000", 10, 6).unwrap(),
+            Decimal::from_str("1.5").unwrap()
+        );
+        assert_eq!(
+            parse_token_amount("0x00000000000000000000000000000000000000000000000000000000003d0900", 16, 6)
+                .unwrap(),
+            Decimal::from(4)
+        );
+        assert_eq!(parse_token_amount("0x", 16, 6).unwrap(), Decimal::ZERO);
+        assert!(parse_token_amount("abc", 10, 6).is_err());
+    }
+}
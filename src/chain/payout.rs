@@ -0,0 +1,168 @@
+use super::error::{ChainError, Result};
+use crate::config::ChainConfig;
+use axum::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 出账请求
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutRequest {
+    /// 提现单号，作为签名服务的幂等键，同一单号重复提交不会重复转账
+    pub request_id: String,
+    pub to_address: String,
+    /// 实际转出金额（已扣除手续费）
+    pub amount: Decimal,
+}
+
+/// 出账交易的链上状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayoutStatus {
+    /// 未上链或确认中，未上链时确认数为 0
+    Pending { confirmations: u64 },
+    /// 链上执行失败（如合约回滚），资金未转出
+    Failed(String),
+}
+
+/// 提现出账广播器，每条链一个实现
+#[async_trait]
+pub trait PayoutProvider: Send + Sync {
+    /// 链类型，与 withdrawal_requests.blockchain_type 一致
+    fn chain(&self) -> &str;
+
+    /// 认定出账完成所需的最少确认数
+    fn required_confirmations(&self) -> u64;
+
+    /// 广播代币转账，返回交易哈希
+    async fn submit(&self, request: &PayoutRequest) -> Result<String>;
+
+    /// 查询出账交易的链上状态
+    async fn status(&self, tx_hash: &str) -> Result<PayoutStatus>;
+}
+
+/// 按链类型注册的出账广播器集合，未注册的链不会自动出账
+#[derive(Clone, Default)]
+pub struct PayoutProviders {
+    providers: HashMap<String, Arc<dyn PayoutProvider>>,
+}
+
+impl PayoutProviders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 根据配置创建广播器：mode = fake 时使用模拟链，配置了签名服务时使用真实链
+    pub fn from_config(config: &ChainConfig) -> Result<Self> {
+        let mut providers = Self::new();
+        if config.mode == "fake" {
+            providers.register(Arc::new(
+                super::fake::FakePayoutProvider::new(super::CHAIN_TRC20, config.trc20_confirmations)
+                    .with_auto_mine(),
+            ));
+            providers.register(Arc::new(
+                super::fake::FakePayoutProvider::new(super::CHAIN_ERC20, config.erc20_confirmations)
+                    .with_auto_mine(),
+            ));
+            return Ok(providers);
+        }
+
+        if config.payout_signer_url.is_empty() {
+            return Ok(providers);
+        }
+        if !config.trc20_api_url.is_empty() {
+            providers.register(Arc::new(super::trc20::Trc20Payout::new(config)?));
+        }
+        if !config.erc20_rpc_url.is_empty() {
+            providers.register(Arc::new(super::erc20::Erc20Payout::new(config)?));
+        }
+        Ok(providers)
+    }
+
+    pub fn register(&mut self, provider: Arc<dyn PayoutProvider>) {
+        self.providers.insert(provider.chain().to_string(), provider);
+    }
+
+    pub fn get(&self, chain: &str) -> Result<Arc<dyn PayoutProvider>> {
+        self.providers
+            .get(chain)
+            .cloned()
+            .ok_or_else(|| ChainError::UnsupportedChain(chain.to_string()))
+    }
+
+    /// 已注册的链类型
+    pub fn chains(&self) -> Vec<String> {
+        self.providers.keys().cloned().collect()
+    }
+}
+
+/// 调用外部钱包/签名服务广播代币转账：
+/// POST {"chain", "contract", "requestId", "to", "amount"}，返回 {"txHash"}。
+/// 私钥不进入本服务，签名服务需按 requestId 去重
+pub struct PayoutSigner {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignerResponse {
+    tx_hash: String,
+}
+
+impl PayoutSigner {
+    pub fn new(config: &ChainConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.payout_signer_url.clone(),
+            token: config.payout_signer_token.clone(),
+        })
+    }
+
+    pub async fn transfer(
+        &self,
+        chain: &str,
+        contract: &str,
+        request: &PayoutRequest,
+    ) -> Result<String> {
+        let mut builder = self.client.post(&self.url).json(&json!({
+            "chain": chain,
+            "contract": contract,
+            "requestId": request.request_id,
+            "to": request.to_address,
+            "amount": request.amount.normalize().to_string(),
+        }));
+        if !self.token.is_empty() {
+            builder = builder.bearer_auth(&self.token);
+        }
+        let resp = builder.send().await?;
+        let status = resp.status();
+        // 4xx 为签名服务明确拒绝（参数、余额、风控等），限流与超时仍可能已处理，不视为拒绝
+        if status.is_client_error()
+            && status != reqwest::StatusCode::REQUEST_TIMEOUT
+            && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+        {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ChainError::Rejected(format!(
+                "{} signer rejected payout {}: {} {}",
+                chain, request.request_id, status, body
+            )));
+        }
+        let resp: SignerResponse = resp.error_for_status()?.json().await?;
+        if resp.tx_hash.is_empty() {
+            return Err(ChainError::ResponseError(format!(
+                "empty {} tx hash for payout {}",
+                chain, request.request_id
+            )));
+        }
+
+        Ok(resp.tx_hash)
+    }
+}
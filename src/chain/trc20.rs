@@ -0,0 +1,297 @@
+use super::error::{ChainError, Result};
+use super::payout::{PayoutProvider, PayoutRequest, PayoutSigner, PayoutStatus};
+use super::{parse_token_amount, ChainTransfer, ChainWatcher, CHAIN_TRC20};
+use crate::config::ChainConfig;
+use axum::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// 每页返回的最大转账条数（TronGrid 上限 200），超过时按 fingerprint 翻页
+const PAGE_LIMIT: u32 = 200;
+
+/// 基于 TronGrid HTTP API 的 TRC20 支付监听器
+pub struct Trc20Watcher {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: String,
+    contract: String,
+    required_confirmations: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Trc20TransfersResponse {
+    #[serde(default)]
+    data: Vec<Trc20Transfer>,
+    #[serde(default)]
+    success: bool,
+    meta: Option<Trc20Meta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Trc20Meta {
+    /// 下一页游标，最后一页不返回
+    fingerprint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Trc20Transfer {
+    transaction_id: String,
+    token_info: Trc20TokenInfo,
+    block_timestamp: i64,
+    from: String,
+    to: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Trc20TokenInfo {
+    address: String,
+    decimals: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionInfo {
+    #[serde(rename = "blockNumber")]
+    block_number: Option<u64>,
+    receipt: Option<TransactionReceipt>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionReceipt {
+    /// 合约执行结果，SUCCESS 以外（如 REVERT、OUT_OF_ENERGY）均为失败
+    result: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NowBlock {
+    block_header: BlockHeader,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    raw_data: BlockRawData,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockRawData {
+    number: u64,
+}
+
+impl Trc20Watcher {
+    pub fn new(config: &ChainConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            api_url: config.trc20_api_url.trim_end_matches('/').to_string(),
+            api_key: config.trc20_api_key.clone(),
+            contract: config.trc20_usdt_contract.clone(),
+            required_confirmations: config.trc20_confirmations,
+        })
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if self.api_key.is_empty() {
+            builder
+        } else {
+            builder.header("TRON-PRO-API-KEY", &self.api_key)
+        }
+    }
+
+    async fn current_block(&self) -> Result<u64> {
+        let url = format!("{}/wallet/getnowblock", self.api_url);
+        let block: NowBlock = self
+            .request(self.client.post(url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(block.block_header.raw_data.number)
+    }
+
+    /// 查询交易的执行结果与确认数
+    async fn transaction_status(&self, tx_hash: &str) -> Result<PayoutStatus> {
+        let url = format!("{}/wallet/gettransactioninfobyid", self.api_url);
+        let info: TransactionInfo = self
+            .request(self.client.post(url))
+            .json(&json!({ "value": tx_hash }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let Some(block_number) = info.block_number else {
+            return Ok(PayoutStatus::Pending { confirmations: 0 });
+        };
+        if let Some(result) = info.receipt.and_then(|receipt| receipt.result) {
+            if result != "SUCCESS" {
+                return Ok(PayoutStatus::Failed(result));
+            }
+        }
+
+        let current = self.current_block().await?;
+        Ok(PayoutStatus::Pending {
+            confirmations: current.saturating_sub(block_number) + 1,
+        })
+    }
+
+    /// 查询地址自 since 起收到的一页 TRC20 转账，fingerprint 为上一页返回的游标
+    async fn transfers_page(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+        fingerprint: Option<&str>,
+    ) -> Result<Trc20TransfersResponse> {
+        let url = format!("{}/v1/accounts/{}/transactions/trc20", self.api_url, address);
+        let min_timestamp = (since.unix_timestamp_nanos() / 1_000_000).to_string();
+        let limit = PAGE_LIMIT.to_string();
+        let mut query = vec![
+            ("only_to", "true"),
+            ("contract_address", self.contract.as_str()),
+            ("min_timestamp", min_timestamp.as_str()),
+            ("order_by", "block_timestamp,asc"),
+            ("limit", limit.as_str()),
+        ];
+        if let Some(fingerprint) = fingerprint {
+            query.push(("fingerprint", fingerprint));
+        }
+        let resp: Trc20TransfersResponse = self
+            .request(self.client.get(url))
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if !resp.success {
+            return Err(ChainError::ResponseError(format!(
+                "TronGrid returned unsuccessful response for {}",
+                address
+            )));
+        }
+
+        Ok(resp)
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for Trc20Watcher {
+    fn chain(&self) -> &str {
+        CHAIN_TRC20
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.required_confirmations
+    }
+
+    async fn incoming_transfers(
+        &self,
+        address: &str,
+        since: OffsetDateTime,
+    ) -> Result<Vec<ChainTransfer>> {
+        let mut transfers = Vec::new();
+        let mut fingerprint: Option<String> = None;
+        loop {
+            let page = self.transfers_page(address, since, fingerprint.as_deref()).await?;
+            for item in page.data {
+                if item.token_info.address != self.contract || item.to != address {
+                    continue;
+                }
+                let amount = parse_token_amount(&item.value, 10, item.token_info.decimals)?;
+                let timestamp = OffsetDateTime::from_unix_timestamp_nanos(
+                    item.block_timestamp as i128 * 1_000_000,
+                )
+                .ok();
+                transfers.push(ChainTransfer {
+                    tx_hash: item.transaction_id,
+                    from_address: item.from,
+                    to_address: item.to,
+                    amount,
+                    timestamp,
+                });
+            }
+
+            match page.meta.and_then(|meta| meta.fingerprint) {
+                Some(next) if fingerprint.as_ref() == Some(&next) => {
+                    return Err(ChainError::ResponseError(format!(
+                        "TronGrid returned a repeated page cursor for {}",
+                        address
+                    )));
+                }
+                Some(next) => fingerprint = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(transfers)
+    }
+
+    async fn confirmations(&self, tx_hash: &str) -> Result<u64> {
+        // 执行失败的交易永远不会被确认
+        match self.transaction_status(tx_hash).await? {
+            PayoutStatus::Pending { confirmations } => Ok(confirmations),
+            PayoutStatus::Failed(_) => Ok(0),
+        }
+    }
+}
+
+/// TRC20 USDT 出账：通过签名服务广播，通过 TronGrid 查询确认
+pub struct Trc20Payout {
+    signer: PayoutSigner,
+    watcher: Trc20Watcher,
+}
+
+impl Trc20Payout {
+    pub fn new(config: &ChainConfig) -> Result<Self> {
+        Ok(Self {
+            signer: PayoutSigner::new(config)?,
+            watcher: Trc20Watcher::new(config)?,
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutProvider for Trc20Payout {
+    fn chain(&self) -> &str {
+        CHAIN_TRC20
+    }
+
+    fn required_confirmations(&self) -> u64 {
+        self.watcher.required_confirmations
+    }
+
+    async fn submit(&self, request: &PayoutRequest) -> Result<String> {
+        self.signer
+            .transfer(CHAIN_TRC20, &self.watcher.contract, request)
+            .await
+    }
+
+    async fn status(&self, tx_hash: &str) -> Result<PayoutStatus> {
+        self.watcher.transaction_status(tx_hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfers_response_page_cursor() {
+        let page: Trc20TransfersResponse = serde_json::from_str(
+            r#"{"data":[],"success":true,"meta":{"at":1700000000000,"fingerprint":"abc","page_size":200}}"#,
+        )
+        .unwrap();
+        assert_eq!(page.meta.and_then(|meta| meta.fingerprint).as_deref(), Some("abc"));
+
+        let last: Trc20TransfersResponse =
+            serde_json::from_str(r#"{"data":[],"success":true,"meta":{"at":1700000000000,"page_size":0}}"#)
+                .unwrap();
+        assert!(last.meta.and_then(|meta| meta.fingerprint).is_none());
+    }
+}
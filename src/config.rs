@@ -12,6 +12,9 @@ pub struct Config {
     pub upload: UploadConfig,
     pub security: SecurityConfig,
     pub app: AppConfig,
+    pub chain: ChainConfig,
+    pub order: OrderConfig,
+    pub price: PriceConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +75,7 @@ pub struct SecurityConfig {
     pub password_require_uppercase: bool,
     pub rate_limit_requests: u32,
     pub rate_limit_window: u64, // 秒
+    pub idempotency_ttl: i64,   // 秒
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +88,44 @@ pub struct AppConfig {
     pub timezone: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub mode: String, // live 真实链上查询 / fake 进程内模拟链
+    pub request_timeout: u64, // 秒
+    pub trc20_api_url: String,
+    pub trc20_api_key: String,
+    pub trc20_usdt_contract: String,
+    pub trc20_confirmations: u64,
+    pub erc20_rpc_url: String,
+    pub erc20_usdt_contract: String,
+    pub erc20_token_decimals: u32,
+    pub erc20_confirmations: u64,
+    pub erc20_lookback_blocks: u64,
+    pub address_derive_url: String, // 收款地址派生服务，为空时只使用预导入的地址池
+    pub address_derive_token: String,
+    pub address_cooldown_hours: i64, // 地址释放后再次分配前的冷却时间（小时）
+    pub payout_signer_url: String, // 提现出账签名服务，为空时不自动出账
+    pub payout_signer_token: String,
+    pub deposit_lookback_hours: i64, // 每轮扫描充值地址的回溯时间（小时）
+    pub deposit_max_confirm_checks: u32, // 充值确认检查的最大次数，超过后标记为过期并需人工核对
+    pub deposit_feed_file: String, // fake 模式下的充值模拟数据文件，为空时使用进程内模拟链
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderConfig {
+    pub expire_minutes: i64, // 待支付订单过期时间（分钟）
+    pub expire_batch_size: u32, // 每轮过期处理的最大订单数
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceConfig {
+    pub source: String, // rest 交易所行情接口 / csv 本地行情文件，为空时不同步行情
+    pub request_timeout: u64, // 秒
+    pub rest_api_url: String, // 兼容 Binance /api/v3 的行情接口
+    pub csv_file: String,
+    pub sync_days: i64, // 每轮同步最近几天的日K线
+}
+
 impl Config {
     pub fn from_env() -> anyhow::Result<Self> {
         dotenvy::dotenv().ok();
@@ -216,6 +258,9 @@ impl Config {
                 rate_limit_window: env::var("APP_SECURITY__RATE_LIMIT_WINDOW")
                     .unwrap_or_else(|_| "60".to_string())
                     .parse()?,
+                idempotency_ttl: env::var("APP_SECURITY__IDEMPOTENCY_TTL")
+                    .unwrap_or_else(|_| "86400".to_string())
+                    .parse()?,
             },
             app: AppConfig {
                 name: env::var("APP_APP__NAME").unwrap_or_else(|_| "Astra Ai API".to_string()),
@@ -229,6 +274,68 @@ impl Config {
                     .unwrap_or(true),
                 timezone: env::var("APP_APP__TIMEZONE").unwrap_or_else(|_| "UTC".to_string()),
             },
+            chain: ChainConfig {
+                mode: env::var("APP_CHAIN__MODE").unwrap_or_else(|_| "live".to_string()),
+                request_timeout: env::var("APP_CHAIN__REQUEST_TIMEOUT")
+                    .unwrap_or_else(|_| "15".to_string())
+                    .parse()?,
+                trc20_api_url: env::var("APP_CHAIN__TRC20_API_URL")
+                    .unwrap_or_else(|_| "https://api.trongrid.io".to_string()),
+                trc20_api_key: env::var("APP_CHAIN__TRC20_API_KEY").unwrap_or_default(),
+                trc20_usdt_contract: env::var("APP_CHAIN__TRC20_USDT_CONTRACT")
+                    .unwrap_or_else(|_| "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".to_string()),
+                trc20_confirmations: env::var("APP_CHAIN__TRC20_CONFIRMATIONS")
+                    .unwrap_or_else(|_| "19".to_string())
+                    .parse()?,
+                erc20_rpc_url: env::var("APP_CHAIN__ERC20_RPC_URL").unwrap_or_default(),
+                erc20_usdt_contract: env::var("APP_CHAIN__ERC20_USDT_CONTRACT")
+                    .unwrap_or_else(|_| "0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string()),
+                erc20_token_decimals: env::var("APP_CHAIN__ERC20_TOKEN_DECIMALS")
+                    .unwrap_or_else(|_| "6".to_string())
+                    .parse()?,
+                erc20_confirmations: env::var("APP_CHAIN__ERC20_CONFIRMATIONS")
+                    .unwrap_or_else(|_| "12".to_string())
+                    .parse()?,
+                erc20_lookback_blocks: env::var("APP_CHAIN__ERC20_LOOKBACK_BLOCKS")
+                    .unwrap_or_else(|_| "7200".to_string())
+                    .parse()?,
+                address_derive_url: env::var("APP_CHAIN__ADDRESS_DERIVE_URL").unwrap_or_default(),
+                address_derive_token: env::var("APP_CHAIN__ADDRESS_DERIVE_TOKEN")
+                    .unwrap_or_default(),
+                address_cooldown_hours: env::var("APP_CHAIN__ADDRESS_COOLDOWN_HOURS")
+                    .unwrap_or_else(|_| "72".to_string())
+                    .parse()?,
+                payout_signer_url: env::var("APP_CHAIN__PAYOUT_SIGNER_URL").unwrap_or_default(),
+                payout_signer_token: env::var("APP_CHAIN__PAYOUT_SIGNER_TOKEN")
+                    .unwrap_or_default(),
+                deposit_lookback_hours: env::var("APP_CHAIN__DEPOSIT_LOOKBACK_HOURS")
+                    .unwrap_or_else(|_| "24".to_string())
+                    .parse()?,
+                deposit_max_confirm_checks: env::var("APP_CHAIN__DEPOSIT_MAX_CONFIRM_CHECKS")
+                    .unwrap_or_else(|_| "1440".to_string())
+                    .parse()?,
+                deposit_feed_file: env::var("APP_CHAIN__DEPOSIT_FEED_FILE").unwrap_or_default(),
+            },
+            order: OrderConfig {
+                expire_minutes: env::var("APP_ORDER__EXPIRE_MINUTES")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()?,
+                expire_batch_size: env::var("APP_ORDER__EXPIRE_BATCH_SIZE")
+                    .unwrap_or_else(|_| "100".to_string())
+                    .parse()?,
+            },
+            price: PriceConfig {
+                source: env::var("APP_PRICE__SOURCE").unwrap_or_default(),
+                request_timeout: env::var("APP_PRICE__REQUEST_TIMEOUT")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()?,
+                rest_api_url: env::var("APP_PRICE__REST_API_URL")
+                    .unwrap_or_else(|_| "https://api.binance.com".to_string()),
+                csv_file: env::var("APP_PRICE__CSV_FILE").unwrap_or_default(),
+                sync_days: env::var("APP_PRICE__SYNC_DAYS")
+                    .unwrap_or_else(|_| "7".to_string())
+                    .parse()?,
+            },
         };
 
         Ok(config)
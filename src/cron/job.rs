@@ -0,0 +1,158 @@
+use super::error::{CronError, Result};
+use crate::state::AppState;
+use crate::utils::time_zone::TimeZone;
+use axum::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub type JobResult = std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// 定时任务：名称唯一，cron 表达式（含秒，6 段）可由 system_configs.cron_schedules 按名称覆盖
+#[async_trait]
+pub trait CronJob: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// 默认 cron 表达式
+    fn schedule(&self) -> &'static str;
+
+    /// cron 表达式所在时区
+    fn timezone(&self) -> TimeZone {
+        TimeZone::Beijing
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult;
+}
+
+/// 任务最近一次执行情况
+#[derive(Debug, Clone, Default)]
+pub struct JobStats {
+    pub last_run_at: Option<OffsetDateTime>,
+    pub last_duration_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// 已注册的任务：生效的 cron 表达式、调度器中的任务 ID 与执行情况
+pub struct JobEntry {
+    pub job: Arc<dyn CronJob>,
+    schedule: Mutex<String>,
+    job_id: Mutex<Option<Uuid>>,
+    running: AtomicBool,
+    stats: Mutex<JobStats>,
+}
+
+impl JobEntry {
+    pub fn new(job: Arc<dyn CronJob>) -> Self {
+        Self {
+            schedule: Mutex::new(job.schedule().to_string()),
+            job,
+            job_id: Mutex::new(None),
+            running: AtomicBool::new(false),
+            stats: Mutex::new(JobStats::default()),
+        }
+    }
+
+    pub async fn schedule(&self) -> String {
+        self.schedule.lock().await.clone()
+    }
+
+    pub async fn job_id(&self) -> Option<Uuid> {
+        *self.job_id.lock().await
+    }
+
+    /// 记录加入调度器后的 cron 表达式与任务 ID，调度器停止时清空任务 ID
+    pub async fn set_scheduled(&self, schedule: String, job_id: Option<Uuid>) {
+        *self.schedule.lock().await = schedule;
+        *self.job_id.lock().await = job_id;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub async fn stats(&self) -> JobStats {
+        self.stats.lock().await.clone()
+    }
+
+    /// 占用任务的执行标记，上一次未执行完时返回 None；返回的守卫释放时（包括任务 panic）清除标记
+    pub fn claim(self: &Arc<Self>) -> Option<RunningGuard> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+
+        Some(RunningGuard(self.clone()))
+    }
+
+    /// 执行任务并记录执行时间、耗时与错误，同一任务上一次未执行完时不重复执行
+    pub async fn execute(self: &Arc<Self>, state: Arc<AppState>) -> Result<()> {
+        let guard = self.claim().ok_or_else(|| {
+            CronError::TaskError(format!("Job {} is already running", self.job.name()))
+        })?;
+
+        self.execute_claimed(guard, state).await
+    }
+
+    /// 在已占用执行标记的情况下执行任务
+    pub async fn execute_claimed(&self, _guard: RunningGuard, state: Arc<AppState>) -> Result<()> {
+        let started_at = TimeZone::Beijing.get_time();
+        let result = self.job.run(state).await;
+        let duration = TimeZone::Beijing.get_time() - started_at;
+        {
+            let mut stats = self.stats.lock().await;
+            stats.last_run_at = Some(started_at);
+            stats.last_duration_ms = Some(duration.whole_milliseconds().max(0) as u64);
+            stats.last_error = result.as_ref().err().map(|err| err.to_string());
+        }
+
+        result.map_err(|err| CronError::TaskError(format!("{}: {}", self.job.name(), err)))
+    }
+}
+
+/// 任务执行标记守卫，释放时清除任务的执行标记
+pub struct RunningGuard(Arc<JobEntry>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoopJob;
+
+    #[async_trait]
+    impl CronJob for NoopJob {
+        fn name(&self) -> &'static str {
+            "noop"
+        }
+
+        fn schedule(&self) -> &'static str {
+            "0 * * * * *"
+        }
+
+        async fn run(&self, _state: Arc<AppState>) -> JobResult {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_running_flag_is_cleared_when_job_panics() {
+        let entry = Arc::new(JobEntry::new(Arc::new(NoopJob)));
+        let guard = entry.claim().unwrap();
+        assert!(entry.is_running());
+        assert!(entry.claim().is_none());
+
+        let handle = tokio::spawn(async move {
+            let _guard = guard;
+            panic!("job panicked");
+        });
+        assert!(handle.await.is_err());
+        assert!(!entry.is_running());
+        assert!(entry.claim().is_some());
+    }
+}
@@ -0,0 +1,173 @@
+use super::job::{CronJob, JobResult};
+use super::tasks::{
+    daily_midnight_task, deposit_scan_task, order_expiration_task, payment_confirmation_task,
+    price_sync_task, reconciliation_task, withdrawal_payout_task,
+};
+use crate::state::AppState;
+use axum::async_trait;
+use std::sync::Arc;
+
+/// 全部定时任务，调度器启动时按此顺序注册
+pub fn all() -> Vec<Arc<dyn CronJob>> {
+    vec![
+        Arc::new(DailySettlementJob),
+        Arc::new(PaymentConfirmationJob),
+        Arc::new(OrderExpirationJob),
+        Arc::new(WithdrawalPayoutJob),
+        Arc::new(DepositScanJob),
+        Arc::new(ReconciliationJob),
+        Arc::new(PriceSyncJob),
+    ]
+}
+
+/// 每日收益结算（每天 00:05 执行，交易日已结算时直接跳过）
+pub struct DailySettlementJob;
+
+#[async_trait]
+impl CronJob for DailySettlementJob {
+    fn name(&self) -> &'static str {
+        "daily_settlement"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 5 0 * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        daily_midnight_task(state).await
+    }
+}
+
+/// 链上支付确认（每分钟）
+pub struct PaymentConfirmationJob;
+
+#[async_trait]
+impl CronJob for PaymentConfirmationJob {
+    fn name(&self) -> &'static str {
+        "payment_confirmation"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 * * * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        payment_confirmation_task(state).await
+    }
+}
+
+/// 超时订单过期（每分钟，与支付确认错开30秒）
+pub struct OrderExpirationJob;
+
+#[async_trait]
+impl CronJob for OrderExpirationJob {
+    fn name(&self) -> &'static str {
+        "order_expiration"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "30 * * * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        order_expiration_task(state).await
+    }
+}
+
+/// 提现出账（每分钟，与支付确认错开15秒）
+pub struct WithdrawalPayoutJob;
+
+#[async_trait]
+impl CronJob for WithdrawalPayoutJob {
+    fn name(&self) -> &'static str {
+        "withdrawal_payout"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "15 * * * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        withdrawal_payout_task(state).await
+    }
+}
+
+/// 充值扫描（每分钟，与支付确认、提现出账错开）
+pub struct DepositScanJob;
+
+#[async_trait]
+impl CronJob for DepositScanJob {
+    fn name(&self) -> &'static str {
+        "deposit_scan"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "45 * * * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        deposit_scan_task(state).await
+    }
+}
+
+/// 余额对账（每日北京时间02:30，在收益结算之后）
+pub struct ReconciliationJob;
+
+#[async_trait]
+impl CronJob for ReconciliationJob {
+    fn name(&self) -> &'static str {
+        "reconciliation"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 30 2 * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        reconciliation_task(state).await
+    }
+}
+
+/// 行情同步（每5分钟）
+pub struct PriceSyncJob;
+
+#[async_trait]
+impl CronJob for PriceSyncJob {
+    fn name(&self) -> &'static str {
+        "price_sync"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 */5 * * * *"
+    }
+
+    async fn run(&self, state: Arc<AppState>) -> JobResult {
+        price_sync_task(state).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_schedules() {
+        let schedules: Vec<(&str, &str)> = all()
+            .iter()
+            .map(|job| (job.name(), job.schedule()))
+            .collect();
+
+        assert_eq!(
+            schedules,
+            vec![
+                ("daily_settlement", "0 5 0 * * *"),
+                ("payment_confirmation", "0 * * * * *"),
+                ("order_expiration", "30 * * * * *"),
+                ("withdrawal_payout", "15 * * * * *"),
+                ("deposit_scan", "45 * * * * *"),
+                ("reconciliation", "0 30 2 * * *"),
+                ("price_sync", "0 */5 * * * *"),
+            ]
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod scheduler;
 pub mod tasks;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod job;
+pub mod jobs;
\ No newline at end of file
@@ -1,6 +1,15 @@
-use super::{error::{Result, CronError}, tasks::daily_midnight_task};
+use super::{
+    error::{CronError, Result},
+    job::JobEntry,
+    jobs,
+};
+use crate::model::system_config::SystemConfigType;
+use crate::repository::SystemConfigRepo;
 use crate::state::AppState;
+use crate::AppError;
+use std::collections::HashMap;
 use std::sync::Arc;
+use time::OffsetDateTime;
 use tokio::sync::Mutex;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info, warn};
@@ -9,18 +18,20 @@ use tracing::{error, info, warn};
 pub struct CronSchedulerManager {
     scheduler: Arc<Mutex<Option<JobScheduler>>>,
     is_running: Arc<Mutex<bool>>,
+    jobs: Vec<Arc<JobEntry>>,
 }
 
 impl CronSchedulerManager {
-    /// 创建新的调度器管理器
+    /// 创建新的调度器管理器并注册全部定时任务
     pub fn new() -> Self {
         Self {
             scheduler: Arc::new(Mutex::new(None)),
             is_running: Arc::new(Mutex::new(false)),
+            jobs: jobs::all().into_iter().map(|job| Arc::new(JobEntry::new(job))).collect(),
         }
     }
 
-    /// 启动调度器
+    /// 启动调度器，cron 表达式按 system_configs.cron_schedules 覆盖，修改后重启调度器生效
     pub async fn start(&self, app_state: Arc<AppState>) -> Result<()> {
         let mut is_running = self.is_running.lock().await;
         if *is_running {
@@ -31,8 +42,11 @@ impl CronSchedulerManager {
         let scheduler = JobScheduler::new().await
             .map_err(|e| CronError::SchedulerError(format!("Failed to create scheduler: {}", e)))?;
 
-        // 添加每日23:59:59执行的任务
-        self.add_daily_midnight_job(scheduler.clone(), app_state).await?;
+        let schedules = load_schedules(&app_state).await;
+        for entry in &self.jobs {
+            let schedule = schedules.get(entry.job.name()).map(String::as_str);
+            self.add_job(&scheduler, entry.clone(), app_state.clone(), schedule).await?;
+        }
 
         // 启动调度器
         scheduler.start().await
@@ -65,6 +79,9 @@ impl CronSchedulerManager {
                 info!("Cron scheduler has been stopped");
             }
         }
+        for entry in &self.jobs {
+            entry.set_scheduled(entry.schedule().await, None).await;
+        }
 
         *is_running = false;
         Ok(())
@@ -76,41 +93,131 @@ impl CronSchedulerManager {
         *is_running
     }
 
-        /// 添加每日午夜任务 (23:59:59)
-    async fn add_daily_midnight_job(&self, scheduler: JobScheduler, app_state: Arc<AppState>) -> Result<()> {
-        // 使用预定义的 @daily 表达式，每天午夜执行
-        // 这种方法更简单，避免了所有权冲突问题
-        let app_state_clone = app_state.clone();//0 * * * * * @daily
-        let job = Job::new_async("@daily", move |_uuid, _l| {
-            let app_state = app_state_clone.clone();
-            Box::pin(async move {
-                info!("Trigger daily midnight task");
-                if let Err(e) = daily_midnight_task(app_state).await {
-                    error!("每日午夜任务执行失败: {}", e);
-                }
+    /// 将任务加入调度器，覆盖的 cron 表达式无效时使用任务默认表达式
+    async fn add_job(
+        &self,
+        scheduler: &JobScheduler,
+        entry: Arc<JobEntry>,
+        app_state: Arc<AppState>,
+        schedule: Option<&str>,
+    ) -> Result<()> {
+        let name = entry.job.name();
+        let default_schedule = entry.job.schedule();
+        let timezone = entry.job.timezone();
+        let offset = chrono::FixedOffset::east_opt(timezone.offset().whole_seconds())
+            .ok_or_else(|| CronError::TimeParseError(format!("Invalid timezone {}", timezone)))?;
+        let new_job = |schedule: &str| {
+            let entry = entry.clone();
+            let app_state = app_state.clone();
+            Job::new_async_tz(schedule, offset, move |_uuid, _l| {
+                let entry = entry.clone();
+                let app_state = app_state.clone();
+                Box::pin(async move {
+                    if let Err(e) = entry.execute(app_state).await {
+                        error!("Cron job failed: {}", e);
+                    }
+                })
             })
-        })
-        .map_err(|e| CronError::SchedulerError(format!("Failed to create cron job: {}", e)))?;
+        };
+
+        let (schedule, job) = match schedule.map(|schedule| (schedule, new_job(schedule))) {
+            Some((schedule, Ok(job))) => (schedule, job),
+            Some((schedule, Err(e))) => {
+                warn!(
+                    "Invalid cron expression {} for job {}, using default {}: {}",
+                    schedule, name, default_schedule, e
+                );
+                (default_schedule, new_job(default_schedule).map_err(|e| {
+                    CronError::SchedulerError(format!("Failed to create cron job {}: {}", name, e))
+                })?)
+            }
+            None => (default_schedule, new_job(default_schedule).map_err(|e| {
+                CronError::SchedulerError(format!("Failed to create cron job {}: {}", name, e))
+            })?),
+        };
 
-        scheduler.add(job).await
-            .map_err(|e| CronError::SchedulerError(format!("Failed to add cron job: {}", e)))?;
-        info!("Daily 23:59:59 cron job has been added");
+        let job_id = scheduler.add(job).await
+            .map_err(|e| CronError::SchedulerError(format!("Failed to add cron job {}: {}", name, e)))?;
+        entry.set_scheduled(schedule.to_string(), Some(job_id)).await;
+        info!("Cron job {} has been added: {} ({})", name, schedule, timezone);
 
         Ok(())
     }
+
+    /// 立即在后台执行一次任务，不影响原有调度
+    pub async fn trigger(&self, name: &str, app_state: Arc<AppState>) -> Result<()> {
+        let Some(entry) = self.jobs.iter().find(|entry| entry.job.name() == name).cloned() else {
+            return Err(CronError::TaskError(format!("Unknown cron job: {}", name)));
+        };
+        // 先占用执行标记再启动后台任务，避免并发触发同时通过检查
+        let Some(guard) = entry.claim() else {
+            return Err(CronError::TaskError(format!("Job {} is already running", name)));
+        };
+
+        info!("Cron job {} triggered manually", name);
+        tokio::spawn(async move {
+            if let Err(e) = entry.execute_claimed(guard, app_state).await {
+                error!("Cron job failed: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
     pub async fn get_status(&self) -> CronSchedulerStatus {
-        let is_running = self.is_running.lock().await;
-        let scheduler_guard = self.scheduler.lock().await;
+        let is_running = *self.is_running.lock().await;
+        let scheduler = self.scheduler.lock().await.clone();
+
+        let mut jobs = Vec::with_capacity(self.jobs.len());
+        for entry in &self.jobs {
+            let timezone = entry.job.timezone();
+            let next_run_at = match (scheduler.clone(), entry.job_id().await) {
+                (Some(mut scheduler), Some(job_id)) => scheduler
+                    .next_tick_for_job(job_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|tick| OffsetDateTime::from_unix_timestamp(tick.timestamp()).ok())
+                    .map(|tick| tick.to_offset(timezone.offset())),
+                _ => None,
+            };
+            let stats = entry.stats().await;
+            jobs.push(CronJobStatus {
+                name: entry.job.name().to_string(),
+                schedule: entry.schedule().await,
+                timezone: timezone.to_string(),
+                running: entry.is_running(),
+                last_run_at: stats.last_run_at,
+                next_run_at,
+                last_duration_ms: stats.last_duration_ms,
+                last_error: stats.last_error,
+            });
+        }
 
         CronSchedulerStatus {
-            is_running: *is_running,
-            jobs_count: if scheduler_guard.is_some() {
-                // 这里可以获取实际的任务数量，但需要更多API调用
-                // 为了简化，暂时返回固定值
-                1
-            } else {
-                0
-            },
+            is_running,
+            jobs_count: if scheduler.is_some() { jobs.len() } else { 0 },
+            jobs,
+        }
+    }
+}
+
+/// 读取 system_configs.cron_schedules（任务名 -> cron 表达式），未配置或格式错误时使用默认表达式
+async fn load_schedules(app_state: &AppState) -> HashMap<String, String> {
+    let config = SystemConfigRepo::get_config_by_key(
+        &app_state.db,
+        &SystemConfigType::CronSchedules.to_string(),
+    )
+    .await;
+    match config {
+        Ok(config) => serde_json::from_str(&config.config_value).unwrap_or_else(|e| {
+            warn!("Invalid cron_schedules config, using default schedules: {}", e);
+            HashMap::new()
+        }),
+        Err(AppError::NotFound(_)) => HashMap::new(),
+        Err(e) => {
+            warn!("Failed to load cron_schedules config, using default schedules: {}", e);
+            HashMap::new()
         }
     }
 }
@@ -120,6 +227,20 @@ impl CronSchedulerManager {
 pub struct CronSchedulerStatus {
     pub is_running: bool,
     pub jobs_count: usize,
+    pub jobs: Vec<CronJobStatus>,
+}
+
+/// 单个定时任务的状态
+#[derive(Debug, Clone)]
+pub struct CronJobStatus {
+    pub name: String,
+    pub schedule: String,
+    pub timezone: String,
+    pub running: bool,
+    pub last_run_at: Option<OffsetDateTime>,
+    pub next_run_at: Option<OffsetDateTime>,
+    pub last_duration_ms: Option<u64>,
+    pub last_error: Option<String>,
 }
 
 impl Default for CronSchedulerManager {
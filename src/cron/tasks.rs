@@ -1,15 +1,16 @@
-use crate::model::USER_POWER_RECORD_STATUS_ACTIVE;
-use crate::repository::power_repo::PowerRepo;
+use crate::service::deposit::DepositService;
+use crate::service::order::OrderService;
+use crate::service::payment::PaymentService;
+use crate::service::payout::PayoutService;
+use crate::service::price::PriceService;
+use crate::service::reconciliation::ReconciliationService;
+use crate::service::settlement::SettlementService;
 use crate::utils::time_zone::TimeZone;
 use crate::{error::AppError, state::AppState};
-use dashmap::DashMap;
-use rust_decimal::Decimal;
-use sqlx::QueryBuilder;
 use std::sync::Arc;
-use time::Duration;
 use tracing::{error, info};
 
-/// 每日23:59:59执行的定时任务
+/// 每日收益结算任务，每小时触发一次，交易日结算完成后直接跳过
 pub async fn daily_midnight_task(
     state: Arc<AppState>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -29,6 +30,87 @@ pub async fn daily_midnight_task(
     Ok(())
 }
 
+/// 定时确认待支付订单的链上支付
+pub async fn payment_confirmation_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let paid = PaymentService::new(&state).confirm_pending_orders().await?;
+    if paid > 0 {
+        info!("Payment confirmation task settled {} orders", paid);
+    }
+
+    Ok(())
+}
+
+/// 定时过期超时未支付的订单并退还资产抵扣部分
+pub async fn order_expiration_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let expired = OrderService::new(&state).expire_pending_orders().await?;
+    if expired > 0 {
+        info!("Order expiration task expired {} orders", expired);
+    }
+
+    Ok(())
+}
+
+/// 定时广播审核通过的提现并确认出账
+pub async fn withdrawal_payout_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let summary = PayoutService::new(&state).process_payouts().await?;
+    if summary.submitted > 0 || summary.completed > 0 || summary.failed > 0 || summary.review > 0
+    {
+        info!(
+            "Withdrawal payout task submitted {}, completed {}, failed {}, sent to review {}",
+            summary.submitted, summary.completed, summary.failed, summary.review
+        );
+    }
+
+    Ok(())
+}
+
+/// 定时扫描用户充值地址并为达到确认数的充值入账
+pub async fn deposit_scan_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let summary = DepositService::new(&state).scan_deposits().await?;
+    if summary.detected > 0 || summary.credited > 0 {
+        info!(
+            "Deposit scan task detected {} ({} late order payments), credited {}",
+            summary.detected, summary.late_payments, summary.credited
+        );
+    }
+
+    Ok(())
+}
+
+/// 每日核对用户钱包余额与账本
+pub async fn reconciliation_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let run_date = TimeZone::Beijing.get_time().date();
+    let summary = ReconciliationService::new(&state).reconcile(run_date).await?;
+    info!(
+        "Reconciliation task checked {} users, {} mismatched accounts, {} users frozen",
+        summary.users, summary.mismatches, summary.frozen
+    );
+
+    Ok(())
+}
+
+/// 定时同步 DG/USDT 实时价格与日K线
+pub async fn price_sync_task(
+    state: Arc<AppState>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let summary = PriceService::new(&state).sync_prices().await?;
+    if summary.ticker_updated {
+        info!("Price sync task updated ticker and {} daily candles", summary.candles);
+    }
+
+    Ok(())
+}
+
 /// 执行每日具体的任务
 async fn execute_daily_tasks(state: Arc<AppState>) -> Result<(), AppError> {
     // 2. 更新用户每日收益统计
@@ -36,55 +118,20 @@ async fn execute_daily_tasks(state: Arc<AppState>) -> Result<(), AppError> {
     Ok(())
 }
 
-/// 更新用户每日收益统计
+/// 结算用户每日挖矿收益并入账
 async fn update_daily_earnings(state: &AppState) -> Result<(), AppError> {
     info!("Starting to update user daily earnings statistics");
-    // 减少2小时减少误差
-    let curr = TimeZone::Beijing.get_time() - Duration::hours(2);
-    let user_power =
-        PowerRepo::get_power_record_by_time(&state.db, USER_POWER_RECORD_STATUS_ACTIVE, &curr)
-            .await;
-    let user_power = match user_power {
-        Ok(user_power) => user_power,
+    let run = match SettlementService::new(state).settle_daily_earnings().await {
+        Ok(run) => run,
         Err(err) => {
-            error!("Failed to get all active power records for cron task: {}", err);
+            error!("Failed to settle daily earnings: {}", err);
             return Err(err);
         }
     };
-    if user_power.len() == 0 {
-        return Ok(());
-    }
-    let mut qb = QueryBuilder::<sqlx::MySql>::new(
-        r#"
-            INSERT INTO user_power_record (
-                user_id, user_power_id, power_package_id, lv, daily_yield_percentage, close_price,
-                package_amount, amount, created_at)
-            "#,
+    info!(
+        "Daily earnings of {} settled at close price {}: {} users, {} power records, {} DG credited",
+        run.business_date, run.close_price, run.users, run.records, run.credited
     );
-    let curr_time = TimeZone::Beijing.get_time();
-    let closing_price = Decimal::from(2);
-    let amount_hub: Arc<DashMap<u64, Decimal>> = Arc::new(DashMap::new());
-    qb.push_values(&user_power, |mut b, power| {
-        let percent = Decimal::from(100);
-        let amount = (power.amount * power.daily_yield_percentage) / percent;
-        let real = amount / (closing_price / percent);
-        b.push_bind(power.user_id)
-            .push_bind(power.id)
-            .push_bind(power.power_package_id)
-            .push_bind(power.lv)
-            .push_bind(power.daily_yield_percentage)
-            .push_bind(&closing_price)
-            .push_bind(power.amount)
-            .push_bind(real)
-            .push_bind(&curr_time);
-        let am = amount_hub.clone();
-        am.entry(power.user_id)
-            .and_modify(|v| *v += real)
-            .or_insert(real);
-    });
-    qb.push("ON DUPLICATE KEY UPDATE created_at = VALUES(created_at)");
-    let query = qb.build();
-    query.execute(state.db.as_ref()).await?;
 
     Ok(())
 }
@@ -164,6 +164,25 @@ impl From<anyhow::Error> for AppError {
     }
 }
 
+// 为链上查询错误添加转换
+impl From<crate::chain::error::ChainError> for AppError {
+    fn from(err: crate::chain::error::ChainError) -> Self {
+        match err {
+            crate::chain::error::ChainError::UnsupportedChain(chain) => {
+                AppError::Validation(format!("Unsupported payment type: {}", chain))
+            }
+            err => AppError::Internal(err.to_string()),
+        }
+    }
+}
+
+// 为行情查询错误添加转换
+impl From<crate::price::error::PriceError> for AppError {
+    fn from(err: crate::price::error::PriceError) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}
+
 // 为数据库迁移错误添加转换
 impl From<sqlx::migrate::MigrateError> for AppError {
     fn from(err: sqlx::migrate::MigrateError) -> Self {
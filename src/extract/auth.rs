@@ -2,6 +2,7 @@ use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 
 use crate::{
     error::{AppError, Result},
+    model::UserRole,
     utils::jwt::Claims,
 };
 
@@ -140,9 +141,9 @@ pub fn check_user_level(user_level: i32, required_level: i32) -> Result<()> {
     Ok(())
 }
 
-// 检查用户是否为管理员
-pub fn is_admin_user(user_level: i32) -> bool {
-    user_level >= 5 // 假设等级5及以上为管理员
+// 检查用户是否为管理员，管理员由 users.role 决定，会员等级再高也不是管理员
+pub fn is_admin_user(role: &str) -> bool {
+    role == UserRole::Admin.to_string()
 }
 
 // 检查用户是否有特定权限
@@ -168,10 +169,9 @@ mod tests {
 
     #[test]
     fn test_is_admin_user() {
-        assert!(is_admin_user(5));
-        assert!(is_admin_user(9));
-        assert!(!is_admin_user(3));
-        assert!(!is_admin_user(0));
+        assert!(is_admin_user("admin"));
+        assert!(!is_admin_user("user"));
+        assert!(!is_admin_user(""));
     }
 
     #[test]
@@ -0,0 +1,209 @@
+use crate::{
+    error::AppError::*,
+    error::Result,
+    extract::AuthUser,
+    middleware::get_client_ip,
+    model::withdrawal::WithdrawalStatus,
+    schema::asset::{
+        BatchApproveWithdrawalRequest, BatchReviewResult, RejectWithdrawalRequest, WithdrawalItem,
+        WithdrawalReviewItem,
+    },
+    schema::common::{ApiResponse, PaginationData, PaginationRequest},
+    schema::reconciliation::{ReconciliationReportItem, ReconciliationReportQuery},
+    schema::settlement::{SettlementBackfillRequest, SettlementReportItem},
+    service::reconciliation::ReconciliationService,
+    service::settlement::SettlementService,
+    service::withdrawal::{ReviewDecision, WithdrawalService},
+    state::AppState,
+    utils::time_zone::TimeZone,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Json},
+};
+use serde_json::json;
+use time::{format_description::well_known::Iso8601, Date};
+use validator::Validate;
+
+/// 获取待审核提现列表（含风险提示）
+pub async fn get_pending_withdrawals(
+    State(state): State<AppState>,
+    Query(pagination): Query<PaginationRequest>,
+) -> Result<impl IntoResponse> {
+    list_withdrawal_reviews(&state, WithdrawalStatus::Pending, pagination).await
+}
+
+/// 获取出账结果无法确认、待人工复核的提现列表，核对链上结果后通过（已到账）或拒绝（退回）
+pub async fn get_payout_reviews(
+    State(state): State<AppState>,
+    Query(pagination): Query<PaginationRequest>,
+) -> Result<impl IntoResponse> {
+    list_withdrawal_reviews(&state, WithdrawalStatus::Review, pagination).await
+}
+
+async fn list_withdrawal_reviews(
+    state: &AppState,
+    status: WithdrawalStatus,
+    pagination: PaginationRequest,
+) -> Result<impl IntoResponse> {
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100);
+
+    let (reviews, total) = WithdrawalService::new(state)
+        .get_pending_reviews(status, page, limit)
+        .await?;
+    let now = TimeZone::Beijing.get_time();
+    let items: Vec<WithdrawalReviewItem> = reviews
+        .into_iter()
+        .map(|review| WithdrawalReviewItem::new(review, now))
+        .collect();
+
+    Ok(Json(ApiResponse::success(PaginationData::new(
+        page, limit, total, items,
+    ))))
+}
+
+/// 审核通过提现
+pub async fn approve_withdrawal(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    Path(withdrawal_id): Path<String>,
+) -> Result<impl IntoResponse> {
+    let withdrawal = WithdrawalService::new(&state)
+        .review_withdrawal(
+            auth_user.id,
+            &withdrawal_id,
+            &ReviewDecision::Approve,
+            &get_client_ip(&headers),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        WithdrawalItem::from(withdrawal),
+        "Withdrawal approved",
+    )))
+}
+
+/// 拒绝提现，冻结资金退回用户
+pub async fn reject_withdrawal(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    Path(withdrawal_id): Path<String>,
+    Json(payload): Json<RejectWithdrawalRequest>,
+) -> Result<impl IntoResponse> {
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
+    let withdrawal = WithdrawalService::new(&state)
+        .review_withdrawal(
+            auth_user.id,
+            &withdrawal_id,
+            &ReviewDecision::Reject(payload.reason),
+            &get_client_ip(&headers),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        WithdrawalItem::from(withdrawal),
+        "Withdrawal rejected",
+    )))
+}
+
+/// 批量审核通过提现，逐笔返回处理结果
+pub async fn batch_approve_withdrawals(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    Json(payload): Json<BatchApproveWithdrawalRequest>,
+) -> Result<impl IntoResponse> {
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
+    let results: Vec<BatchReviewResult> = WithdrawalService::new(&state)
+        .batch_approve(auth_user.id, &payload.withdrawal_ids, &get_client_ip(&headers))
+        .await
+        .into_iter()
+        .map(|(withdrawal_id, result)| BatchReviewResult {
+            withdrawal_id,
+            success: result.is_ok(),
+            message: result.err().map(|err| err.to_string()),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(results)))
+}
+
+/// 获取对账差异报告，可按对账日期（YYYY-MM-DD）与用户过滤
+pub async fn get_reconciliation_reports(
+    State(state): State<AppState>,
+    Query(pagination): Query<PaginationRequest>,
+    Query(query): Query<ReconciliationReportQuery>,
+) -> Result<impl IntoResponse> {
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100);
+    let run_date = match query.date.as_deref() {
+        Some(date) => Some(
+            Date::parse(date, &Iso8601::DATE)
+                .map_err(|_| Validation(format!("Invalid reconciliation date: {}", date)))?,
+        ),
+        None => None,
+    };
+
+    let (reports, total) = ReconciliationService::new(&state)
+        .get_reports(run_date, query.user_id, page, limit)
+        .await?;
+    let items: Vec<ReconciliationReportItem> = reports
+        .into_iter()
+        .map(ReconciliationReportItem::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(PaginationData::new(
+        page, limit, total, items,
+    ))))
+}
+
+/// 立即执行一次余额对账
+pub async fn run_reconciliation(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    let run_date = TimeZone::Beijing.get_time().date();
+    let summary = ReconciliationService::new(&state).reconcile(run_date).await?;
+
+    Ok(Json(ApiResponse::success(summary)))
+}
+
+/// 核查后解除用户资金冻结
+pub async fn unfreeze_user_funds(
+    State(state): State<AppState>,
+    Path(user_id): Path<u64>,
+) -> Result<impl IntoResponse> {
+    ReconciliationService::new(&state)
+        .unfreeze_user(user_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        json!({ "userId": user_id, "fundsFrozen": false }),
+        "User funds unfrozen",
+    )))
+}
+
+/// 补结算指定交易日范围（YYYY-MM-DD）的挖矿收益，试运行时只返回预计入账数据
+pub async fn backfill_settlements(
+    State(state): State<AppState>,
+    Json(payload): Json<SettlementBackfillRequest>,
+) -> Result<impl IntoResponse> {
+    let parse = |date: &str| {
+        Date::parse(date, &Iso8601::DATE)
+            .map_err(|_| Validation(format!("Invalid settlement date: {}", date)))
+    };
+    let reports = SettlementService::new(&state)
+        .backfill(
+            parse(&payload.start_date)?,
+            parse(&payload.end_date)?,
+            payload.dry_run,
+        )
+        .await?;
+    let items: Vec<SettlementReportItem> = reports
+        .into_iter()
+        .map(SettlementReportItem::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(items)))
+}
@@ -4,106 +4,119 @@ use axum::{
 };
 use serde_json::json;
 use crate::{
+    error::AppError::Validation,
+    error::Result,
     extract::AuthUser,
-    schema::common::{ApiResponse, PaginationRequest},
+    model::withdrawal::WithdrawalStatus,
+    schema::asset::{
+        ConversionRecordItem, DepositAddressItem, DepositAddressQuery, ExchangeQuoteItem,
+        ExchangeQuoteRequest, ExchangeRequest, RechargeRecordItem, SupportedBlockchainItem,
+        WalletBalanceItem, WithdrawalItem, WithdrawalListQuery, WithdrawalRequest,
+    },
+    schema::common::{ApiResponse, PaginationData, PaginationRequest},
+    service::blockchain::BlockchainService,
+    service::deposit::DepositService,
+    service::exchange::ExchangeService,
+    service::wallet::WalletService,
+    service::withdrawal::WithdrawalService,
     state::AppState,
-    error::Result,
+    utils::money::Currency,
 };
+use strum::IntoEnumIterator;
+use validator::Validate;
+
+// 获取钱包各币种余额
+pub async fn get_wallet(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<impl IntoResponse> {
+    let wallet = WalletService::new(&state).get_wallet(auth_user.id).await?;
+    let balances: Vec<WalletBalanceItem> = Currency::iter()
+        .map(|currency| WalletBalanceItem::new(&wallet, currency))
+        .collect();
+
+    let response = ApiResponse::success(balances);
+    Ok(Json(response))
+}
+
+// 获取充值地址，首次获取时为用户绑定
+pub async fn get_deposit_address(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(query): Query<DepositAddressQuery>,
+) -> Result<impl IntoResponse> {
+    let address = DepositService::new(&state)
+        .get_or_assign_address(auth_user.id, &query.blockchain_code)
+        .await?;
+
+    let response = ApiResponse::success(DepositAddressItem {
+        blockchain_code: query.blockchain_code,
+        address,
+    });
+    Ok(Json(response))
+}
 
 // 获取充值记录
 pub async fn get_recharge_records(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
     Query(pagination): Query<PaginationRequest>,
 ) -> Result<impl IntoResponse> {
-    let records = json!({
-        "records": [
-            {
-                "id": 1,
-                "type": "USDT",
-                "amount": 1000.00,
-                "transactionHash": "0x1234567890abcdef",
-                "address": "TRX742d35Cc6634C0532925a3b844Bc454e4438f33e",
-                "confirmations": 12,
-                "fee": 1.5,
-                "status": "completed",
-                "createdAt": "2025-11-20T10:30:00Z"
-            }
-        ],
-        "pagination": {
-            "page": pagination.page,
-            "limit": pagination.limit,
-            "total": 1,
-            "totalPages": 1
-        }
-    });
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100);
+
+    let (deposits, total) = DepositService::new(&state)
+        .get_user_deposits(auth_user.id, page, limit)
+        .await?;
+    let records: Vec<RechargeRecordItem> =
+        deposits.into_iter().map(RechargeRecordItem::from).collect();
 
-    let response = ApiResponse::success(records);
+    let response = ApiResponse::success(PaginationData::new(page, limit, total, records));
     Ok(Json(response))
 }
 
 // 获取提现记录
 pub async fn get_withdrawal_records(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
     Query(pagination): Query<PaginationRequest>,
+    Query(query): Query<WithdrawalListQuery>,
 ) -> Result<impl IntoResponse> {
-    let records = json!({
-        "records": [
-            {
-                "id": "WDR123456",
-                "amount": 500.00,
-                "currency": "USDT",
-                "blockchainCode": "TRC20",
-                "address": "TRX742d35Cc6634C0532925a3b844Bc454e4438f33e",
-                "fee": 2.5,
-                "status": "pending",
-                "estimatedProcessingTime": "24小时",
-                "createdAt": "2025-11-23T15:45:00Z"
-            }
-        ],
-        "pagination": {
-            "page": pagination.page,
-            "limit": pagination.limit,
-            "total": 1,
-            "totalPages": 1
-        }
-    });
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100);
+    let status = match query.status.as_deref() {
+        Some(name) => Some(
+            name.parse::<WithdrawalStatus>()
+                .map_err(|_| Validation(format!("Unsupported withdrawal status: {}", name)))?,
+        ),
+        None => None,
+    };
+
+    let (withdrawals, total) = WithdrawalService::new(&state)
+        .get_user_withdrawals(auth_user.id, status, page, limit)
+        .await?;
+    let records: Vec<WithdrawalItem> = withdrawals.into_iter().map(WithdrawalItem::from).collect();
 
-    let response = ApiResponse::success(records);
+    let response = ApiResponse::success(PaginationData::new(page, limit, total, records));
     Ok(Json(response))
 }
 
 // 获取兑换记录
 pub async fn get_conversion_records(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
     Query(pagination): Query<PaginationRequest>,
 ) -> Result<impl IntoResponse> {
-    let records = json!({
-        "records": [
-            {
-                "id": 1,
-                "fromCurrency": "USDT",
-                "toCurrency": "BTC",
-                "fromAmount": 1000.00,
-                "toAmount": 0.025,
-                "exchangeRate": 0.000025,
-                "fee": 0.5,
-                "transactionId": "TXN789012",
-                "status": "completed",
-                "createdAt": "2025-11-22T09:15:00Z"
-            }
-        ],
-        "pagination": {
-            "page": pagination.page,
-            "limit": pagination.limit,
-            "total": 1,
-            "totalPages": 1
-        }
-    });
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100);
 
-    let response = ApiResponse::success(records);
+    let (records, total) = ExchangeService::new(&state)
+        .get_conversion_records(auth_user.id, page, limit)
+        .await?;
+    let records: Vec<ConversionRecordItem> =
+        records.into_iter().map(ConversionRecordItem::from).collect();
+
+    let response = ApiResponse::success(PaginationData::new(page, limit, total, records));
     Ok(Json(response))
 }
 
@@ -151,30 +164,14 @@ pub async fn get_asset_earnings(
 
 // 获取支持的区块链
 pub async fn get_supported_blockchains(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Result<impl IntoResponse> {
-    let blockchains = json!([
-        {
-            "code": "TRC20",
-            "name": "TRC20 (USDT)",
-            "fee": 1.0,
-            "minAmount": 10.0,
-            "maxAmount": 100000.0,
-            "icon": "https://example.com/icons/trc20.png",
-            "confirmationTime": "5分钟",
-            "isAvailable": true
-        },
-        {
-            "code": "ERC20",
-            "name": "ERC20 (USDT)",
-            "fee": 2.5,
-            "minAmount": 10.0,
-            "maxAmount": 100000.0,
-            "icon": "https://example.com/icons/erc20.png",
-            "confirmationTime": "15分钟",
-            "isAvailable": true
-        }
-    ]);
+    let blockchains: Vec<SupportedBlockchainItem> = BlockchainService::new(&state)
+        .get_blockchains()
+        .await?
+        .into_iter()
+        .map(SupportedBlockchainItem::from)
+        .collect();
 
     let response = ApiResponse::success(blockchains);
     Ok(Json(response))
@@ -182,128 +179,122 @@ pub async fn get_supported_blockchains(
 
 // 创建提现请求
 pub async fn create_withdrawal(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Json(payload): Json<serde_json::Value>,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<WithdrawalRequest>,
 ) -> Result<impl IntoResponse> {
-    let withdrawal_data = json!({
-        "id": "WDR789012",
-        "amount": payload.get("amount"),
-        "currency": payload.get("currency"),
-        "blockchainCode": payload.get("blockchainCode"),
-        "address": payload.get("address"),
-        "fee": 2.5,
-        "status": "pending",
-        "estimatedProcessingTime": "24小时",
-        "createdAt": chrono::Utc::now().to_rfc3339()
-    });
+    let withdrawal = submit_withdrawal(&state, auth_user.id, payload).await?;
 
-    let response = ApiResponse::success_with_message(withdrawal_data, "Withdrawal request submitted");
+    let response = ApiResponse::success_with_message(withdrawal, "Withdrawal request submitted");
     Ok(Json(response))
 }
 
 // 取消提现请求
 pub async fn cancel_asset_withdrawal(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
     Path(withdrawal_id): Path<String>,
 ) -> Result<impl IntoResponse> {
-    let cancel_data = json!({
-        "withdrawalId": withdrawal_id,
-        "status": "cancelled",
-        "cancelTime": chrono::Utc::now().to_rfc3339(),
-        "refundAmount": 500.00
-    });
+    let withdrawal = WithdrawalService::new(&state)
+        .cancel_withdrawal(auth_user.id, &withdrawal_id)
+        .await?;
 
-    let response = ApiResponse::success_with_message(cancel_data, "Withdrawal request cancelled");
+    let response = ApiResponse::success_with_message(
+        WithdrawalItem::from(withdrawal),
+        "Withdrawal request cancelled",
+    );
     Ok(Json(response))
 }
 
 // 币种兑换
 pub async fn exchange_currency(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Json(payload): Json<serde_json::Value>,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<ExchangeRequest>,
 ) -> Result<impl IntoResponse> {
-    let exchange_result = json!({
-        "userId": _auth_user.id,
-        "fromCurrency": payload.get("fromCurrency"),
-        "toCurrency": payload.get("toCurrency"),
-        "fromAmount": payload.get("fromAmount"),
-        "toAmount": payload.get("toAmount"),
-        "exchangeRate": 1.0,
-        "fee": 0.5,
-        "transactionId": format!("EXCH{}", chrono::Utc::now().timestamp()),
-        "status": "completed",
-        "completedAt": chrono::Utc::now().to_rfc3339(),
-        "message": "币种兑换成功"
-    });
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
+    let tran = ExchangeService::new(&state)
+        .exchange(auth_user.id, &payload.quote_id)
+        .await?;
 
-    let response = ApiResponse::success_with_message(exchange_result, "Currency exchange successful");
+    let response = ApiResponse::success_with_message(
+        ConversionRecordItem::from(tran),
+        "Currency exchange successful",
+    );
+    Ok(Json(response))
+}
+
+// 获取币种兑换报价，汇率在有效期内锁定
+pub async fn quote_exchange(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<ExchangeQuoteRequest>,
+) -> Result<impl IntoResponse> {
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
+    let quote = ExchangeService::new(&state)
+        .quote(
+            auth_user.id,
+            &payload.from_currency.to_uppercase(),
+            &payload.to_currency.to_uppercase(),
+            payload.from_amount,
+        )
+        .await?;
+
+    let response = ApiResponse::success(ExchangeQuoteItem::from(quote));
     Ok(Json(response))
 }
 
 // 资产提现
 pub async fn withdraw_asset(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Json(payload): Json<serde_json::Value>,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<WithdrawalRequest>,
 ) -> Result<impl IntoResponse> {
-    let withdrawal_result = json!({
-        "userId": _auth_user.id,
-        "withdrawalId": format!("WITHDRAW{}", chrono::Utc::now().timestamp()),
-        "currency": payload.get("currency"),
-        "amount": payload.get("amount"),
-        "address": payload.get("address"),
-        "blockchain": payload.get("blockchain"),
-        "fee": 2.0,
-        "actualAmount": payload.get("amount").map(|v| v.as_f64().unwrap_or(0.0) - 2.0),
-        "status": "pending",
-        "createdAt": chrono::Utc::now().to_rfc3339(),
-        "message": "提现申请已提交，等待审核"
-    });
+    let withdrawal = submit_withdrawal(&state, auth_user.id, payload).await?;
 
-    let response = ApiResponse::success_with_message(withdrawal_result, "Withdrawal request submitted");
+    let response = ApiResponse::success_with_message(withdrawal, "Withdrawal request submitted");
     Ok(Json(response))
 }
 
 // 获取提现详情
 pub async fn get_withdrawal_detail(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
     Path(withdrawal_id): Path<String>,
 ) -> Result<impl IntoResponse> {
-    let withdrawal_detail = json!({
-        "withdrawalId": withdrawal_id,
-        "currency": "USDT",
-        "amount": 1000.0,
-        "address": "TRX742d35Cc6634C0532925a3b844Bc454e4438f33e",
-        "blockchain": "TRC-20",
-        "status": "completed",
-        "fee": 2.0,
-        "actualAmount": 998.0,
-        "transactionHash": "0x1234567890abcdef1234567890abcdef12345678",
-        "createdAt": "2025-11-20T10:30:00Z",
-        "completedAt": "2025-11-20T12:15:00Z"
-    });
+    let withdrawal = WithdrawalService::new(&state)
+        .get_withdrawal(auth_user.id, &withdrawal_id)
+        .await?;
 
-    let response = ApiResponse::success(withdrawal_detail);
+    let response = ApiResponse::success(WithdrawalItem::from(withdrawal));
     Ok(Json(response))
 }
 
 // 取消资产提现（别名函数）
 pub async fn cancel_withdrawal_asset(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Path(withdrawal_id): Path<String>,
+    state: State<AppState>,
+    auth_user: AuthUser,
+    withdrawal_id: Path<String>,
 ) -> Result<impl IntoResponse> {
-    let cancel_data = json!({
-        "withdrawalId": withdrawal_id,
-        "status": "cancelled",
-        "cancelTime": chrono::Utc::now().to_rfc3339(),
-        "refundAmount": 1000.0
-    });
+    cancel_asset_withdrawal(state, auth_user, withdrawal_id).await
+}
 
-    let response = ApiResponse::success_with_message(cancel_data, "Withdrawal request cancelled");
-    Ok(Json(response))
-}
\ No newline at end of file
+/// 校验提现申请并提交，供资产提现与算力提现入口共用
+pub async fn submit_withdrawal(
+    state: &AppState,
+    user_id: u64,
+    payload: WithdrawalRequest,
+) -> Result<WithdrawalItem> {
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
+    let withdrawal = WithdrawalService::new(state)
+        .create_withdrawal(
+            user_id,
+            &payload.currency,
+            &payload.blockchain_code,
+            &payload.address,
+            payload.amount,
+        )
+        .await?;
+
+    Ok(WithdrawalItem::from(withdrawal))
+}
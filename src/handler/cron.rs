@@ -1,23 +1,45 @@
 use crate::state::AppState;
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
     response::Json,
 };
 use serde_json::{json, Value};
 use std::sync::Arc;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+fn format_time(time: Option<OffsetDateTime>) -> Option<String> {
+    time.and_then(|time| time.format(&Rfc3339).ok())
+}
 
 /// 获取定时任务调度器状态
 pub async fn get_cron_status(
     State(app_state): State<AppState>,
 ) -> Result<Json<Value>, StatusCode> {
     let status = app_state.cron_scheduler.get_status().await;
+    let jobs: Vec<Value> = status
+        .jobs
+        .into_iter()
+        .map(|job| {
+            json!({
+                "name": job.name,
+                "schedule": job.schedule,
+                "timezone": job.timezone,
+                "running": job.running,
+                "last_run_at": format_time(job.last_run_at),
+                "next_run_at": format_time(job.next_run_at),
+                "last_duration_ms": job.last_duration_ms,
+                "last_error": job.last_error
+            })
+        })
+        .collect();
 
     Ok(Json(json!({
         "success": true,
         "data": {
             "is_running": status.is_running,
-            "jobs_count": status.jobs_count
+            "jobs_count": status.jobs_count,
+            "jobs": jobs
         }
     })))
 }
@@ -59,4 +81,25 @@ pub async fn stop_cron_scheduler(
             })))
         }
     }
-}
\ No newline at end of file
+}
+
+/// 立即执行一次指定的定时任务（管理员）
+pub async fn trigger_cron_job(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let app_state_clone = Arc::new(app_state.clone());
+    match app_state.cron_scheduler.trigger(&name, app_state_clone).await {
+        Ok(_) => Ok(Json(json!({
+            "success": true,
+            "message": format!("定时任务 {} 已开始执行", name)
+        }))),
+        Err(e) => {
+            tracing::error!("执行定时任务失败: {}", e);
+            Ok(Json(json!({
+                "success": false,
+                "message": format!("执行定时任务失败: {}", e)
+            })))
+        }
+    }
+}
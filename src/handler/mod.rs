@@ -1,4 +1,5 @@
 pub mod about_us;
+pub mod admin;
 pub mod activity;
 pub mod airdrop;
 pub mod asset;
@@ -1,3 +1,5 @@
+use crate::handler::asset::{cancel_asset_withdrawal, get_withdrawal_records, submit_withdrawal};
+use crate::schema::asset::{WithdrawalListQuery, WithdrawalRequest};
 use crate::schema::UserPowerRecordStatsReq;
 use crate::utils::convert::FromWith;
 use crate::{
@@ -5,6 +7,7 @@ use crate::{
     extract::AuthUser,
     model::power::{convert_power_packages, convert_user_power_records},
     repository::power_repo::PowerRepo,
+    service::power::PowerService,
     schema::{
         common::{ApiResponse, PaginationRequest},
         power::{PowerPackagesResponse, PowerRecordsPagination, PowerRecordsResponse},
@@ -46,7 +49,7 @@ pub async fn start_power(
     auth_user: AuthUser,
     Path(upp): Path<u64>,
 ) -> Result<impl IntoResponse> {
-    let m_power = PowerRepo::start_user_power_record(&state.db, auth_user.id, upp).await;
+    let m_power = PowerService::new(&state).start_power(auth_user.id, upp).await;
     let resp: HashMap<String, String> = match m_power {
         Ok(_) => HashMap::new(),
         Err(err) => {
@@ -153,64 +156,32 @@ pub async fn upgrade_level(
 
 // 提现Power
 pub async fn withdraw_power(
-    _state: State<AppState>,
-    _auth_user: AuthUser,
-    Json(_payload): Json<serde_json::Value>,
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<WithdrawalRequest>,
 ) -> Result<impl IntoResponse> {
-    // 暂时简化实现，避免复杂逻辑的编译错误
-    // TODO: 实现真正的提现逻辑
-    let response = ApiResponse::success_with_message(
-        json!({
-            "withdrawalId": format!("withdraw_{}", chrono::Utc::now().timestamp_millis()),
-            "status": "pending",
-            "createdAt": chrono::Utc::now().to_rfc3339()
-        }),
-        "Withdrawal application submitted",
-    );
+    let withdrawal = submit_withdrawal(&state, auth_user.id, payload).await?;
 
+    let response =
+        ApiResponse::success_with_message(withdrawal, "Withdrawal application submitted");
     Ok(Json(response))
 }
 
 // 获取提现记录
 pub async fn get_withdrawal(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Query(pagination): Query<PaginationRequest>,
+    state: State<AppState>,
+    auth_user: AuthUser,
+    pagination: Query<PaginationRequest>,
+    query: Query<WithdrawalListQuery>,
 ) -> Result<impl IntoResponse> {
-    let records = json!({
-        "records": [
-            {
-                "id": "WDR123456",
-                "amount": 100.0,
-                "status": "pending",
-                "createdAt": "2025-11-23T10:30:00Z",
-                "processedAt": null
-            }
-        ],
-        "pagination": {
-            "page": pagination.page,
-            "limit": pagination.limit,
-            "total": 1,
-            "totalPages": 1
-        }
-    });
-
-    let response = ApiResponse::success(records);
-    Ok(Json(response))
+    get_withdrawal_records(state, auth_user, pagination, query).await
 }
 
 // 取消提现
 pub async fn cancel_withdrawal(
-    State(_state): State<AppState>,
-    _auth_user: AuthUser,
-    Path(_withdrawal_id): Path<String>,
+    state: State<AppState>,
+    auth_user: AuthUser,
+    withdrawal_id: Path<String>,
 ) -> Result<impl IntoResponse> {
-    let cancel_result = json!({
-        "withdrawalId": _withdrawal_id,
-        "status": "cancelled",
-        "cancelledAt": chrono::Utc::now().to_rfc3339()
-    });
-
-    let response = ApiResponse::success_with_message(cancel_result, "Withdrawal cancelled");
-    Ok(Json(response))
+    cancel_asset_withdrawal(state, auth_user, withdrawal_id).await
 }
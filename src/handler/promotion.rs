@@ -1,8 +1,11 @@
 use crate::{
     error::Result,
     extract::AuthUser,
+    repository::PromotionRepo,
     schema::common::{ApiResponse, PaginationRequest},
+    schema::content::PromotionPackageItem,
     state::AppState,
+    utils::time_zone::TimeZone,
 };
 use axum::{
     extract::{Path, Query, State},
@@ -228,52 +231,18 @@ pub async fn get_my_promotions(
     Ok(Json(response))
 }
 
-// 获取限时优惠套餐
+// 获取限时优惠套餐（含库存与售罄状态）
 pub async fn get_promotion_packages(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _auth_user: AuthUser,
 ) -> Result<impl IntoResponse> {
-    let packages = json!({
-        "packages": [
-            {
-                "id": 1,
-                "name": "AI算力体验套餐",
-                "originalPrice": 200.0,
-                "promotionPrice": 100.0,
-                "discount": 50,
-                "description": "限时5折优惠，体验AI算力挖矿",
-                "features": [
-                    "100 USDT算力套餐",
-                    "30天有效期",
-                    "日均收益3-5 USDT",
-                    "支持多种加密货币"
-                ],
-                "endTime": chrono::Utc::now() + chrono::Duration::days(7),
-                "stock": 100,
-                "sold": 75,
-                "tags": ["热销", "限时", "新手推荐"]
-            },
-            {
-                "id": 2,
-                "name": "专业矿工套餐",
-                "originalPrice": 1000.0,
-                "promotionPrice": 750.0,
-                "discount": 25,
-                "description": "专业矿工专享，高性能算力套餐",
-                "features": [
-                    "500 USDT算力套餐",
-                    "60天有效期",
-                    "日均收益25-30 USDT",
-                    "专属客服支持"
-                ],
-                "endTime": chrono::Utc::now() + chrono::Duration::days(14),
-                "stock": 50,
-                "sold": 30,
-                "tags": ["专业", "高收益", "VIP"]
-            }
-        ]
-    });
+    let now = TimeZone::Beijing.get_time();
+    let packages: Vec<PromotionPackageItem> = PromotionRepo::get_active_packages(&state.db, now)
+        .await?
+        .into_iter()
+        .map(|package| PromotionPackageItem::new(package, now))
+        .collect();
 
-    let response = ApiResponse::success(packages);
+    let response = ApiResponse::success(json!({ "packages": packages }));
     Ok(Json(response))
 }
@@ -1,27 +1,26 @@
 use crate::model::power::convert_power_package_to_package_item;
-use crate::model::{POWER_PACKAGE_STATUS_NO_UPGRADE, USER_POWER_RECORD_STATUS_ACTIVE};
+use crate::model::{order_status_from_name, OrderFilter};
 use crate::repository::power_repo::PowerRepo;
-use crate::schema::UpgradeOrderRequest;
-use crate::service::system_config::SystemConfigService;
+use crate::schema::{UpgradeOrderRequest, UpgradeOrderResponse, UpgradeQuoteQuery, UpgradeQuoteResponse};
+use crate::service::payment::PaymentService;
 use crate::service::{order::OrderService, power::PowerService, UserService};
 use crate::{
     error::AppError::*,
     error::Result,
     extract::AuthUser,
-    schema::common::ApiResponse,
+    schema::common::{ApiResponse, PaginationData, PaginationRequest},
     schema::order::{
-        CreateOrderRequest, CreateOrderResponse
+        CreateOrderRequest, CreateOrderResponse, OrderListQuery
     },
     schema::power::PowerPackageItem,
     state::AppState,
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::{IntoResponse, Json},
 };
 use serde_json::json;
 use validator::Validate;
-use crate::utils::time_zone::TimeZone;
 
 // 获取算力详情
 pub async fn get_package_detail(
@@ -50,10 +49,7 @@ pub async fn create_order(
     auth_user: AuthUser,
     Json(payload): Json<CreateOrderRequest>,
 ) -> Result<impl IntoResponse> {
-    let addr = SystemConfigService::new(&state)
-        .get_chain_addr(&payload.blockchain_type)
-        .await?;
-
+    payload.validate().map_err(|e| Validation(e.to_string()))?;
     let package = PowerRepo::get_power_record_by_id(&state.db, payload.power_id).await?;
     let Some(package) = package else {
         return Err(NotFound("Computing power package to purchase not found".to_string()));
@@ -64,12 +60,68 @@ pub async fn create_order(
 
     // 使用 OrderService 创建订单
     let order_service = OrderService::new(&state);
-    let (_order_id, order_number) = order_service
-        .create_order(&userinfo, &package, &payload.blockchain_type, &addr)
+    let order = order_service
+        .create_order(
+            &userinfo,
+            &package,
+            payload.quantity,
+            payload.promotion_id,
+            payload.coupon_code.as_deref(),
+            &payload.blockchain_type,
+        )
+        .await?;
+
+    let response = ApiResponse::success_with_message(
+        CreateOrderResponse {
+            order_number: order.order_number,
+            blockchain_type: payload.blockchain_type,
+            deposit_address: order.deposit_address,
+            pay_amount: order.coin_pay.amount(),
+        },
+        "Order created successfully",
+    );
+    Ok(Json(response))
+}
+
+// 获取订单列表
+pub async fn get_orders(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(pagination): Query<PaginationRequest>,
+    Query(query): Query<OrderListQuery>,
+) -> Result<impl IntoResponse> {
+    // 使用分页请求的默认值
+    let page = pagination.page.unwrap_or(1).max(1);
+    let limit = pagination.limit.unwrap_or(20).clamp(1, 100); // 限制最大每页数量
+
+    let status = match query.status.as_deref() {
+        Some(name) => Some(
+            order_status_from_name(name)
+                .ok_or_else(|| Validation(format!("Unsupported order status: {}", name)))?,
+        ),
+        None => None,
+    };
+    if let (Some(start), Some(end)) = (query.start_date, query.end_date) {
+        if start > end {
+            return Err(Validation("Start date cannot be later than end date".to_string()));
+        }
+    }
+    let filter = OrderFilter {
+        status,
+        power_package_id: query.power_id,
+        start_time: query.start_date.and_then(|date| date.and_hms_opt(0, 0, 0)),
+        end_time: query
+            .end_date
+            .and_then(|date| date.succ_opt())
+            .and_then(|date| date.and_hms_opt(0, 0, 0)),
+    };
+
+    let order_service = OrderService::new(&state);
+    let (records, total) = order_service
+        .get_user_orders(auth_user.id, &filter, page, limit, &auth_user.lang)
         .await?;
 
-    let response =
-        ApiResponse::success_with_message(CreateOrderResponse { order_number }, "Order created successfully");
+    let response = ApiResponse::success(PaginationData::new(page, limit, total, records));
     Ok(Json(response))
 }
 
@@ -138,16 +190,49 @@ pub async fn cancel_order(
     Ok(Json(response))
 }
 
+/// 查询订单链上支付状态，匹配到的转账达到确认数后订单才会被标记为已支付
 pub async fn paid_order(
     State(state): State<AppState>,
     auth_user: AuthUser,
     Path(order_id): Path<String>,
 ) -> Result<impl IntoResponse> {
-    // 使用 OrderService 取消订单
+    let payment_service = PaymentService::new(&state);
+    let confirmation = payment_service
+        .confirm_user_order(auth_user.id, &order_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(confirmation)))
+}
+
+/// 升级报价预览：旧算力包剩余价值抵扣及新订单支付拆分
+pub async fn get_upgrade_quote(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(query): Query<UpgradeQuoteQuery>,
+) -> Result<impl IntoResponse> {
+    query.validate().map_err(|e| Validation(e.to_string()))?;
+    let user_service = UserService::new(&state);
+    let userinfo = user_service.get_user_info(auth_user.id).await?;
     let order_service = OrderService::new(&state);
-    order_service.paid_order(auth_user.id, &order_id).await?;
+    let quote = order_service
+        .quote_upgrade(&userinfo, query.old_user_power_id, query.power_id)
+        .await?;
 
-    Ok(Json(ApiResponse::empty_object()))
+    let response = UpgradeQuoteResponse {
+        old_user_power_id: quote.old_power.id,
+        power_id: quote.package.id,
+        amount: quote.package.amount,
+        old_amount: quote.old_power.amount,
+        old_paid_amount: quote.paid.amount(),
+        old_earnings: quote.old_power.earnings,
+        old_earnings_value: quote.earned.amount(),
+        duration_days: quote.duration_days,
+        elapsed_days: quote.elapsed_days,
+        credit: quote.credit.amount(),
+        asset_pay: quote.asset_pay.amount(),
+        coin_pay: quote.coin_pay.amount(),
+    };
+    Ok(Json(ApiResponse::success(response)))
 }
 
 /// 升级订单
@@ -157,50 +242,24 @@ pub async fn upgrade_order(
     Json(payload): Json<UpgradeOrderRequest>,
 ) -> Result<impl IntoResponse> {
     payload.validate().map_err(|e| Validation(e.to_string()))?;
-    let old_package = PowerRepo::get_power_record_by_id_and_user(
-        &state.db,
-        payload.old_user_power_id,
-        auth_user.id,
-    )
-    .await?;
-
-    if old_package.status != USER_POWER_RECORD_STATUS_ACTIVE {
-        return Err(OrderNotPaid("Computing power package not paid".to_string()));
-    }
-    let addr = SystemConfigService::new(&state)
-        .get_chain_addr(&payload.blockchain_type)
-        .await?;
-    let package = PowerRepo::get_power_record_by_id(&state.db, payload.power_id).await?;
-    let Some(package) = package else {
-        return Err(NotFound("Upgrade computing power package not found".to_string()));
-    };
-    if old_package.lv as u16 >= package.lv {
-        return Err(Validation(
-            "Upgrade computing power package has the same level as current package, cannot upgrade".to_string(),
-        ));
-    }
-
-    if package.is_upgrade == POWER_PACKAGE_STATUS_NO_UPGRADE {
-        return Err(NotFound("Computing power package upgrade channel not enabled".to_string()));
-    }
     let user_service = UserService::new(&state);
     let userinfo = user_service.get_user_info(auth_user.id).await?;
-    let total_assets = userinfo.total_assets + old_package.amount;
     let order_service = OrderService::new(&state);
-    let (_order_id, num) = order_service
+    let (order, quote) = order_service
         .upgrade_order(
             &userinfo,
-            &package,
-            total_assets,
+            payload.old_user_power_id,
+            payload.power_id,
             &payload.blockchain_type,
-            &addr,
-            old_package.id,
         )
         .await?;
     // 构建响应数据
-    let response = json!({
-        "orderId": num,
-        "cancelTime": TimeZone::Beijing.get_time(),
+    let response = ApiResponse::success(UpgradeOrderResponse {
+        order_number: order.order_number,
+        blockchain_type: payload.blockchain_type,
+        deposit_address: order.deposit_address,
+        pay_amount: order.coin_pay.amount(),
+        upgrade_credit: quote.credit.amount(),
     });
     Ok(Json(response))
 }
@@ -1,8 +1,6 @@
 use crate::schema::SystemConfigCreateRequest;
 use crate::{
-    error::AppError::*,
     error::Result,
-    extract::AuthUser,
     schema::common::ApiResponse,
     schema::system_config::{SystemConfigRequest, SystemConfigResponse},
     service::system_config::SystemConfigService,
@@ -15,17 +13,11 @@ use axum::{
 };
 use std::collections::HashMap;
 
-/// 创建系统配置
+/// 创建系统配置（管理员）
 pub async fn create_config(
     State(state): State<AppState>,
-    auth_user: AuthUser,
     Json(request): Json<SystemConfigCreateRequest>,
 ) -> Result<impl IntoResponse> {
-    // 检查用户权限（需要管理员权限）
-    if auth_user.user_level < 2 {
-        return Err(Authorization("Administrator permission required".to_string()));
-    }
-
     let config_service = SystemConfigService::new(&state);
     let id = config_service
         .create_config(
@@ -51,7 +43,7 @@ pub async fn get_config_by_key(
     Ok(Json(ApiResponse::success(config)))
 }
 
-/// 更新系统配置
+/// 更新系统配置（管理员）
 pub async fn update_config(
     State(state): State<AppState>,
     Path(key): Path<String>,
@@ -68,17 +60,11 @@ pub async fn update_config(
     )))
 }
 
-/// 删除系统配置
+/// 删除系统配置（管理员）
 pub async fn delete_config(
     State(state): State<AppState>,
-    auth_user: AuthUser,
     Path(key): Path<String>,
 ) -> Result<impl IntoResponse> {
-    // 检查用户权限（需要管理员权限）
-    if auth_user.user_level < 2 {
-        return Err(Authorization("Administrator permission required".to_string()));
-    }
-
     let config_service = SystemConfigService::new(&state);
     let message = config_service.delete_config(&key).await?;
 
@@ -2,7 +2,7 @@ use crate::{
     error::Result,
     extract::AuthUser,
     schema::{common::ApiResponse, user::UserInfoRes},
-    service::UserService,
+    service::{wallet::WalletService, UserService},
     state::AppState,
 };
 use axum::{
@@ -18,9 +18,9 @@ pub async fn get_user_info(
 ) -> Result<impl IntoResponse> {
     let user_service = UserService::new(&state);
     let user = user_service.get_user_info(auth_user.id).await?;
+    let wallet = WalletService::new(&state).get_wallet(auth_user.id).await?;
 
-    // 使用 From trait 转换为响应格式
-    let user_info_res = UserInfoRes::from(user);
+    let user_info_res = UserInfoRes::new(user, &wallet);
 
     let response = ApiResponse::success(user_info_res);
     Ok(Json(response))
@@ -5,6 +5,7 @@ pub use state::AppState;
 
 // 公共模块
 pub mod app;
+pub mod chain;
 pub mod config;
 pub mod cron;
 pub mod error;
@@ -12,6 +13,7 @@ pub mod extract;
 pub mod handler;
 pub mod middleware;
 pub mod model;
+pub mod price;
 pub mod repository;
 pub mod schema;
 pub mod service;
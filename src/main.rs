@@ -1,4 +1,5 @@
 mod app;
+mod chain;
 mod config;
 mod cron;
 mod error;
@@ -6,6 +7,7 @@ mod extract;
 mod handler;
 mod middleware;
 mod model;
+mod price;
 mod repository;
 mod schema;
 mod service;
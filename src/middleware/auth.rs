@@ -1,6 +1,7 @@
 use crate::{
     error::{AppError, Result},
-    extract::{AuthUser, JwtClaims},
+    extract::{is_admin_user, AuthUser, JwtClaims},
+    repository::UserRepo,
     state::AppState,
     utils::jwt::JwtService,
 };
@@ -54,12 +55,14 @@ pub async fn jwt_auth_middleware(
 
 // 管理员权限中间件
 pub async fn admin_middleware(
+    State(app_state): State<Arc<AppState>>,
     auth_user: AuthUser,
     request: Request,
     next: Next,
 ) -> Result<Response> {
-    // 检查用户是否为管理员（等级5及以上）
-    if auth_user.user_level < 5 {
+    // 按数据库中的 users.role 判断是否为管理员，令牌签发后角色可能已被收回，会员等级也不能作为后台权限依据
+    let user = UserRepo::find_by_id(&app_state.db, auth_user.id).await?;
+    if !is_admin_user(&user.role) {
         return Err(AppError::Authorization("Administrator permission required".to_string()));
     }
 
@@ -0,0 +1,260 @@
+use crate::{
+    error::{AppError, Result},
+    extract::AuthUser,
+    model::idempotency::IdempotencyKey,
+    repository::IdempotencyRepo,
+    schema::common::{ApiResponse, EmptyObject},
+    state::AppState,
+    utils::time_zone::TimeZone,
+};
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use time::Duration;
+use tracing::{error, info};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 128;
+const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
+
+/// 幂等中间件 - 携带 Idempotency-Key 的请求在有效期内只会执行一次
+///
+/// 同一用户重复提交相同键和相同请求时重放首次响应；
+/// 相同键但请求内容不同、或首次请求仍在处理中时返回 409。
+/// 首次请求返回 5xx 时释放该键，允许客户端重试；
+/// 响应保存失败时只记录状态码，重试时返回该状态码并提示原响应不可用。
+pub async fn idempotency_middleware(
+    State(app_state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LENGTH)
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Idempotency-Key must be 1-{} visible ASCII characters",
+                MAX_KEY_LENGTH
+            ))
+        })?
+        .to_string();
+    let user_id = request
+        .extensions()
+        .get::<AuthUser>()
+        .map(|user| user.id)
+        .ok_or_else(|| {
+            AppError::Auth("User not authenticated or authentication expired".to_string())
+        })?;
+
+    // 读取请求体计算请求指纹，之后再放回请求中
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_REQUEST_BODY_SIZE)
+        .await
+        .map_err(|e| AppError::Validation(format!("Failed to read request body: {}", e)))?;
+    let method = parts.method.to_string();
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| parts.uri.path())
+        .to_string();
+    let request_hash = request_hash(&method, &path, &body);
+
+    let db = app_state.db.as_ref();
+    let now = TimeZone::Beijing.get_time();
+    if let Some(record) = IdempotencyRepo::find(db, user_id, &key).await? {
+        if record.expired_at > now {
+            return replay(record, &request_hash);
+        }
+        IdempotencyRepo::delete_expired(db, user_id, &key, now).await?;
+    }
+
+    let expired_at = now + Duration::seconds(app_state.config.security.idempotency_ttl);
+    let Some(record_id) =
+        IdempotencyRepo::try_lock(db, user_id, &key, &method, &path, &request_hash, expired_at)
+            .await?
+    else {
+        return Err(AppError::Conflict(
+            "A request with this Idempotency-Key is already being processed".to_string(),
+        ));
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(err) => {
+            IdempotencyRepo::release(db, user_id, &key).await?;
+            return Err(AppError::Internal(format!(
+                "Failed to read response body: {}",
+                err
+            )));
+        }
+    };
+
+    if parts.status.is_server_error() {
+        IdempotencyRepo::release(db, user_id, &key).await?;
+    } else if let Err(err) = IdempotencyRepo::save_response(
+        db,
+        record_id,
+        parts.status.as_u16(),
+        &String::from_utf8_lossy(&body),
+    )
+    .await
+    {
+        // 请求已经执行，保存失败不影响本次返回；标记为已完成，避免重试在有效期内一直被当作处理中拒绝
+        error!("Failed to save idempotent response for key {}: {}", key, err);
+        if let Err(err) = IdempotencyRepo::mark_completed(db, record_id, parts.status.as_u16()).await
+        {
+            error!("Failed to mark idempotency key {} as completed: {}", key, err);
+        }
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// 重放首次请求的响应
+fn replay(record: IdempotencyKey, request_hash: &str) -> Result<Response> {
+    if record.request_hash != request_hash {
+        return Err(AppError::Conflict(
+            "Idempotency-Key has already been used with a different request".to_string(),
+        ));
+    }
+    let Some(status) = record.response_status else {
+        return Err(AppError::Conflict(
+            "A request with this Idempotency-Key is already being processed".to_string(),
+        ));
+    };
+    let body = match record.response_body {
+        Some(body) => body,
+        // 首次响应保存失败，只能告知请求已处理
+        None => serde_json::to_string(&ApiResponse {
+            code: status as i32,
+            message: "Request has already been processed, the original response is unavailable"
+                .to_string(),
+            data: EmptyObject,
+        })?,
+    };
+
+    info!(
+        user_id = record.user_id,
+        path = %record.request_path,
+        "Replaying idempotent response"
+    );
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = StatusCode::from_u16(status)
+        .map_err(|e| AppError::Internal(format!("Invalid stored response status: {}", e)))?;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+
+    Ok(response)
+}
+
+/// 请求指纹：方法、路径（含查询参数）与请求体的 SHA-256
+fn request_hash(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(response_status: Option<u16>, response_body: Option<&str>) -> IdempotencyKey {
+        let now = TimeZone::Beijing.get_time();
+        IdempotencyKey {
+            id: 1,
+            user_id: 1,
+            idempotency_key: "key".to_string(),
+            request_method: "POST".to_string(),
+            request_path: "/api/purchase/order".to_string(),
+            request_hash: request_hash("POST", "/api/purchase/order", b"{}"),
+            response_status,
+            response_body: response_body.map(str::to_string),
+            expired_at: now + Duration::days(1),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn body_string(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let hash = request_hash("POST", "/api/purchase/order", b"{}");
+
+        // 已保存的响应原样重放
+        let body = r#"{"code":200,"message":"success","data":{}}"#;
+        let response = replay(record(Some(200), Some(body)), &hash).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(body_string(response).await, body);
+
+        // 仍在处理中或请求内容不同
+        assert!(matches!(replay(record(None, None), &hash), Err(AppError::Conflict(_))));
+        let other = request_hash("POST", "/api/purchase/order", br#"{"powerId":1}"#);
+        assert!(matches!(
+            replay(record(Some(200), Some(body)), &other),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replay_after_save_failure() {
+        // 响应保存失败后只记录了状态码：重试不再被当作处理中拒绝，返回原状态码
+        let hash = request_hash("POST", "/api/purchase/order", b"{}");
+        let response = replay(record(Some(200), None), &hash).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        let body: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+        assert_eq!(body["code"], 200);
+        assert_eq!(
+            body["message"],
+            "Request has already been processed, the original response is unavailable"
+        );
+    }
+
+    #[test]
+    fn test_request_hash() {
+        let hash = request_hash("POST", "/api/purchase/order", br#"{"powerId":1}"#);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            hash,
+            request_hash("POST", "/api/purchase/order", br#"{"powerId":1}"#)
+        );
+        assert_ne!(
+            hash,
+            request_hash("POST", "/api/purchase/order", br#"{"powerId":2}"#)
+        );
+        assert_ne!(
+            hash,
+            request_hash("POST", "/api/purchase/orders/upgrade", br#"{"powerId":1}"#)
+        );
+    }
+}
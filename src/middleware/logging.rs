@@ -184,7 +184,7 @@ pub async fn security_logging_middleware(request: Request, next: Next) -> Respon
 }
 
 // 获取客户端IP
-fn get_client_ip(headers: &HeaderMap) -> String {
+pub fn get_client_ip(headers: &HeaderMap) -> String {
     headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
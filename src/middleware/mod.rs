@@ -2,8 +2,10 @@ pub mod auth;
 pub mod rate_limit;
 pub mod cors;
 pub mod logging;
+pub mod idempotency;
 
 pub use auth::*;
 pub use rate_limit::*;
 pub use cors::*;
-pub use logging::*;
\ No newline at end of file
+pub use logging::*;
+pub use idempotency::*;
\ No newline at end of file
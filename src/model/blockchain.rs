@@ -0,0 +1,148 @@
+use crate::chain::{CHAIN_ERC20, CHAIN_TRC20};
+use crate::config::ChainConfig;
+use crate::utils::address::{check_evm_address, check_tron_address};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 链地址格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AddressFormat {
+    /// TRON 地址，T 开头的 Base58Check
+    Tron,
+    /// EVM 地址，0x 开头的十六进制，EIP-55 校验
+    Evm,
+}
+
+impl AddressFormat {
+    /// 校验地址，失败时返回具体原因
+    pub fn check(&self, address: &str) -> Result<(), &'static str> {
+        match self {
+            AddressFormat::Tron => check_tron_address(address),
+            AddressFormat::Evm => check_evm_address(address),
+        }
+    }
+}
+
+/// 支持的链配置，system_configs.blockchain 为该结构的数组，
+/// 订单支付、用户充值与提现统一按该配置校验链、地址与确认数
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Blockchain {
+    /// 链类型，如 TRC20、ERC20，与 orders.blockchain_type 一致
+    pub code: String,
+    pub name: String,
+    /// 停用的链不再接受新订单、充值地址与提现，已发起的支付与出账照常确认
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 每笔提现固定手续费（USDT），从提现金额中扣除
+    pub fee: Decimal,
+    #[serde(rename = "minAmount")]
+    pub min_amount: Decimal,
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Decimal,
+    /// 认定支付、充值与出账完成所需的最少确认数
+    pub confirmations: u64,
+    #[serde(rename = "addressFormat")]
+    pub address_format: AddressFormat,
+    #[serde(default)]
+    pub icon: String,
+    /// 预计到账时间，展示用
+    #[serde(rename = "confirmationTime", default)]
+    pub confirmation_time: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Blockchain {
+    /// 未配置 blockchain 时使用的默认链，确认数取自环境配置
+    pub fn defaults(config: &ChainConfig) -> Vec<Blockchain> {
+        vec![
+            Blockchain {
+                code: CHAIN_TRC20.to_string(),
+                name: "TRC20 (USDT)".to_string(),
+                enabled: true,
+                fee: Decimal::ONE,
+                min_amount: Decimal::from(10),
+                max_amount: Decimal::from(100000),
+                confirmations: config.trc20_confirmations,
+                address_format: AddressFormat::Tron,
+                icon: String::new(),
+                confirmation_time: "5分钟".to_string(),
+            },
+            Blockchain {
+                code: CHAIN_ERC20.to_string(),
+                name: "ERC20 (USDT)".to_string(),
+                enabled: true,
+                fee: Decimal::new(25, 1),
+                min_amount: Decimal::from(10),
+                max_amount: Decimal::from(100000),
+                confirmations: config.erc20_confirmations,
+                address_format: AddressFormat::Evm,
+                icon: String::new(),
+                confirmation_time: "15分钟".to_string(),
+            },
+        ]
+    }
+
+    /// 校验地址是否符合链的地址格式
+    pub fn check_address(&self, address: &str) -> Result<(), &'static str> {
+        self.address_format.check(address)
+    }
+
+    /// 校验配置取值：手续费与限额不能为负、最小限额不超过最大限额、确认数至少为 1
+    pub fn validate(&self) -> Result<(), String> {
+        if self.code.is_empty() {
+            return Err("Blockchain code cannot be empty".to_string());
+        }
+        if self.fee < Decimal::ZERO {
+            return Err(format!("{}: fee must not be negative", self.code));
+        }
+        if self.min_amount < Decimal::ZERO || self.min_amount > self.max_amount {
+            return Err(format!(
+                "{}: minAmount must be between 0 and maxAmount",
+                self.code
+            ));
+        }
+        if self.confirmations < 1 {
+            return Err(format!("{}: confirmations must be at least 1", self.code));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_blockchain() {
+        let chains: Vec<Blockchain> = serde_json::from_str(
+            r#"[{"code": "TRC20", "name": "TRC20 (USDT)", "fee": 1, "minAmount": 10,
+                 "maxAmount": 1000, "confirmations": 19, "addressFormat": "tron"}]"#,
+        )
+        .unwrap();
+        assert_eq!(chains.len(), 1);
+        assert!(chains[0].enabled);
+        assert_eq!(chains[0].address_format, AddressFormat::Tron);
+        assert!(chains[0].check_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t").is_ok());
+        assert!(chains[0].check_address("0xdAC17F958D2ee523a2206206994597C13D831ec7").is_err());
+    }
+
+    #[test]
+    fn test_validate_blockchain() {
+        let chain: Blockchain = serde_json::from_str(
+            r#"{"code": "TRC20", "name": "TRC20 (USDT)", "fee": 1, "minAmount": 10,
+                "maxAmount": 1000, "confirmations": 19, "addressFormat": "tron"}"#,
+        )
+        .unwrap();
+        assert!(chain.validate().is_ok());
+        assert!(Blockchain { fee: Decimal::NEGATIVE_ONE, ..chain.clone() }.validate().is_err());
+        assert!(Blockchain { confirmations: 0, ..chain.clone() }.validate().is_err());
+        assert!(Blockchain { min_amount: Decimal::from(2000), ..chain }.validate().is_err());
+    }
+}
@@ -1,6 +1,9 @@
+use crate::error::Result;
+use crate::AppError;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct CarouselItem {
@@ -16,24 +19,70 @@ pub struct CarouselItem {
     pub updated_at: NaiveDateTime,
 }
 
+/// 限时优惠套餐，按优惠价售卖关联的算力包，stock 为 0 表示不限库存
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PromotionPackage {
-    pub id: i64,
+    pub id: u64,
+    pub power_package_id: u64,
     pub name: String,
     pub price: rust_decimal::Decimal,
-    pub original_price: rust_decimal::Decimal,
+    pub original_price: Option<rust_decimal::Decimal>,
     pub currency: String,
-    pub description: Option<String>,
+    pub description: String,
     pub profit_percentage: rust_decimal::Decimal,
-    pub duration_days: i32,
+    pub duration_days: u32,
     pub features: Option<serde_json::Value>,
-    pub start_time: NaiveDateTime,
-    pub end_time: NaiveDateTime,
-    pub stock: i32,
-    pub sold: i32,
+    pub start_time: OffsetDateTime,
+    pub end_time: OffsetDateTime,
+    pub stock: u32,
+    pub sold: u32,
     pub is_available: bool,
-    pub created_at: NaiveDateTime,
-    pub updated_at: NaiveDateTime,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl PromotionPackage {
+    /// 剩余库存，不限库存时返回 None
+    pub fn remaining(&self) -> Option<u32> {
+        (self.stock > 0).then(|| self.stock.saturating_sub(self.sold))
+    }
+
+    pub fn is_sold_out(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
+    /// 校验套餐当前是否可购买指定数量，库存的最终扣减以数据库条件更新为准
+    pub fn check_purchasable(&self, now: OffsetDateTime, quantity: u32) -> Result<()> {
+        if !self.is_available {
+            return Err(AppError::Business(format!(
+                "Promotion {} is not available",
+                self.name
+            )));
+        }
+        if now < self.start_time {
+            return Err(AppError::Business(format!(
+                "Promotion {} has not started yet",
+                self.name
+            )));
+        }
+        if now >= self.end_time {
+            return Err(AppError::Business(format!(
+                "Promotion {} has ended",
+                self.name
+            )));
+        }
+        match self.remaining() {
+            Some(0) => Err(AppError::Business(format!(
+                "Promotion {} is sold out",
+                self.name
+            ))),
+            Some(remaining) if remaining < quantity => Err(AppError::Business(format!(
+                "Promotion {} only has {} left in stock",
+                self.name, remaining
+            ))),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -150,3 +199,56 @@ pub struct UpdateStatistics {
     pub system_status: Option<String>,
     pub last_update_time: Option<NaiveDateTime>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use time::Duration;
+
+    fn package(stock: u32, sold: u32, now: OffsetDateTime) -> PromotionPackage {
+        PromotionPackage {
+            id: 1,
+            power_package_id: 1,
+            name: "flash".to_string(),
+            price: Decimal::from(100),
+            original_price: Some(Decimal::from(200)),
+            currency: "USDT".to_string(),
+            description: String::new(),
+            profit_percentage: Decimal::from(15),
+            duration_days: 30,
+            features: None,
+            start_time: now - Duration::days(1),
+            end_time: now + Duration::days(1),
+            stock,
+            sold,
+            is_available: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_promotion_stock() {
+        let now = OffsetDateTime::now_utc();
+        assert!(package(0, 1000, now).check_purchasable(now, 5).is_ok());
+        assert!(package(10, 7, now).check_purchasable(now, 3).is_ok());
+        assert!(package(10, 8, now).check_purchasable(now, 3).is_err());
+        assert!(package(10, 10, now).is_sold_out());
+        assert!(!package(0, 10, now).is_sold_out());
+    }
+
+    #[test]
+    fn test_promotion_time_window() {
+        let now = OffsetDateTime::now_utc();
+        let promotion = package(10, 0, now);
+        assert!(promotion
+            .check_purchasable(now - Duration::days(2), 1)
+            .is_err());
+        assert!(promotion.check_purchasable(promotion.end_time, 1).is_err());
+
+        let mut disabled = package(10, 0, now);
+        disabled.is_available = false;
+        assert!(disabled.check_purchasable(now, 1).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::error::Result;
+use crate::utils::money::{Currency, Money};
+use crate::AppError;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+///折扣类型：1 百分比折扣 / 2 固定金额减免
+pub const COUPON_TYPE_PERCENTAGE: u8 = 1;
+pub const COUPON_TYPE_FIXED: u8 = 2;
+
+///优惠券状态：0 停用 / 1 启用
+pub const COUPON_STATUS_DISABLED: u8 = 0;
+pub const COUPON_STATUS_ACTIVE: u8 = 1;
+
+///使用记录状态：0 已释放 / 1 已使用
+pub const COUPON_USAGE_STATUS_RELEASED: u8 = 0;
+pub const COUPON_USAGE_STATUS_USED: u8 = 1;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Coupon {
+    pub id: u64,
+    pub code: String,
+    pub discount_type: u8,
+    pub discount_value: Decimal,
+    pub max_discount: Decimal, // 0 表示不限
+    pub min_amount: Decimal,
+    pub total_limit: u32,    // 0 表示不限
+    pub per_user_limit: u32, // 0 表示不限
+    pub used_count: u32,
+    pub starts_at: OffsetDateTime,
+    pub ends_at: OffsetDateTime,
+    pub status: u8,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl Coupon {
+    /// 校验优惠券状态、有效期、全局次数和最低金额，返回订单金额可减免的金额
+    pub fn discount_for(&self, amount: Money, now: OffsetDateTime) -> Result<Money> {
+        if self.status != COUPON_STATUS_ACTIVE || now < self.starts_at || now >= self.ends_at {
+            return Err(AppError::Business(format!(
+                "Coupon {} is not available",
+                self.code
+            )));
+        }
+        if self.total_limit > 0 && self.used_count >= self.total_limit {
+            return Err(AppError::Business(format!(
+                "Coupon {} has been fully redeemed",
+                self.code
+            )));
+        }
+        if amount.amount() < self.min_amount {
+            return Err(AppError::Business(format!(
+                "Coupon {} requires a minimum order amount of {}",
+                self.code, self.min_amount
+            )));
+        }
+
+        let discount = match self.discount_type {
+            COUPON_TYPE_PERCENTAGE => {
+                let discount = amount.percent(self.discount_value);
+                if self.max_discount > Decimal::ZERO {
+                    discount.min(Money::usdt(self.max_discount))
+                } else {
+                    discount
+                }
+            }
+            COUPON_TYPE_FIXED => Money::usdt(self.discount_value),
+            _ => {
+                return Err(AppError::Internal(format!(
+                    "Unknown discount type {} for coupon {}",
+                    self.discount_type, self.code
+                )))
+            }
+        };
+        if !discount.is_positive() {
+            return Ok(Money::zero(Currency::Usdt));
+        }
+
+        Ok(discount.min(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn coupon(discount_type: u8, value: i64, max_discount: i64) -> Coupon {
+        let now = OffsetDateTime::now_utc();
+        Coupon {
+            id: 1,
+            code: "WELCOME".to_string(),
+            discount_type,
+            discount_value: Decimal::from(value),
+            max_discount: Decimal::from(max_discount),
+            min_amount: Decimal::from(50),
+            total_limit: 10,
+            per_user_limit: 1,
+            used_count: 0,
+            starts_at: now - Duration::days(1),
+            ends_at: now + Duration::days(1),
+            status: COUPON_STATUS_ACTIVE,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_coupon_discount() {
+        let now = OffsetDateTime::now_utc();
+        let amount = Money::usdt(Decimal::from(200));
+
+        let percentage = coupon(COUPON_TYPE_PERCENTAGE, 15, 0);
+        assert_eq!(percentage.discount_for(amount, now).unwrap(), Money::usdt(Decimal::from(30)));
+
+        let capped = coupon(COUPON_TYPE_PERCENTAGE, 15, 20);
+        assert_eq!(capped.discount_for(amount, now).unwrap(), Money::usdt(Decimal::from(20)));
+
+        let fixed = coupon(COUPON_TYPE_FIXED, 500, 0);
+        assert_eq!(fixed.discount_for(amount, now).unwrap(), amount);
+    }
+
+    #[test]
+    fn test_coupon_rejections() {
+        let now = OffsetDateTime::now_utc();
+        let amount = Money::usdt(Decimal::from(200));
+
+        let mut expired = coupon(COUPON_TYPE_FIXED, 10, 0);
+        expired.ends_at = now - Duration::hours(1);
+        assert!(expired.discount_for(amount, now).is_err());
+
+        let mut exhausted = coupon(COUPON_TYPE_FIXED, 10, 0);
+        exhausted.used_count = exhausted.total_limit;
+        assert!(exhausted.discount_for(amount, now).is_err());
+
+        let small = coupon(COUPON_TYPE_FIXED, 10, 0);
+        assert!(small.discount_for(Money::usdt(Decimal::from(20)), now).is_err());
+    }
+}
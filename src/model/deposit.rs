@@ -0,0 +1,63 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use strum::{Display, EnumString};
+use time::OffsetDateTime;
+
+/// 充值状态：pending 确认中 / credited 已入账 / expired 超过确认检查次数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum DepositStatus {
+    Pending,
+    Credited,
+    /// 长期无法确认，不再自动检查，由管理员核对链上结果
+    Expired,
+}
+
+/// 扫描到的新充值
+#[derive(Debug, Clone)]
+pub struct NewDeposit<'a> {
+    pub user_id: u64,
+    /// 迟到的订单支付对应的订单号，普通充值为 None
+    pub order_id: Option<&'a str>,
+    pub chain: &'a str,
+    pub address: &'a str,
+    pub tx_hash: &'a str,
+    pub from_address: &'a str,
+    pub amount: Decimal,
+    pub currency: &'a str,
+}
+
+/// 用户充值记录（deposits）
+#[derive(Debug, Clone, FromRow)]
+pub struct Deposit {
+    pub id: u64,
+    pub deposit_id: String,
+    pub user_id: u64,
+    /// 迟到的订单支付对应的订单号，普通充值为 None
+    pub order_id: Option<String>,
+    pub chain: String,
+    pub address: String,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub confirmations: u32,
+    pub check_attempts: u32,
+    pub checked_at: Option<OffsetDateTime>,
+    pub status: String,
+    pub detected_at: OffsetDateTime,
+    pub credited_at: Option<OffsetDateTime>,
+}
+
+/// 一轮充值扫描的结果
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositScanSummary {
+    /// 新发现的充值数
+    pub detected: usize,
+    /// 达到确认数并入账的充值数
+    pub credited: usize,
+    /// 新发现的迟到订单支付数（已计入 detected）
+    pub late_payments: usize,
+}
@@ -0,0 +1,24 @@
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+///地址状态：0 空闲 / 1 已分配 / 2 停用 / 3 用户充值地址
+pub const DEPOSIT_ADDRESS_STATUS_FREE: u8 = 0;
+pub const DEPOSIT_ADDRESS_STATUS_ALLOCATED: u8 = 1;
+pub const DEPOSIT_ADDRESS_STATUS_DISABLED: u8 = 2;
+/// 长期绑定给用户的充值地址，不会被释放回地址池
+pub const DEPOSIT_ADDRESS_STATUS_USER: u8 = 3;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DepositAddress {
+    pub id: u64,
+    pub chain: String,
+    pub address: String,
+    pub derivation_index: Option<u32>, // 预导入地址为 None
+    pub status: u8,
+    pub user_id: Option<u64>,
+    pub order_id: Option<String>,
+    pub allocated_at: Option<OffsetDateTime>,
+    pub released_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
@@ -0,0 +1,178 @@
+use crate::utils::money::{Currency, Money, MONEY_ROUNDING, MONEY_SCALE};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// DG 兑 USDT 交易对，real_time_prices.current_price 为 1 DG 的 USDT 价格
+pub const DG_USDT_SYMBOL: &str = "DG/USDT";
+
+/// 兑换配置，system_configs.exchange 为该结构，未配置时使用默认值
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExchangeConfig {
+    /// 点差（%）：买入 DG 按市场价上浮、卖出 DG 按市场价下调
+    #[serde(rename = "spreadPercent")]
+    pub spread_percent: Decimal,
+    /// 手续费（%），从转出金额中扣除
+    #[serde(rename = "feePercent")]
+    pub fee_percent: Decimal,
+    /// 报价锁定时间（秒）
+    #[serde(rename = "quoteTtlSeconds")]
+    pub quote_ttl_seconds: i64,
+    /// 实时价格超过该时间（秒）未更新时拒绝报价
+    #[serde(rename = "maxPriceAgeSeconds")]
+    pub max_price_age_seconds: i64,
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        Self {
+            spread_percent: Decimal::new(5, 1),
+            fee_percent: Decimal::new(1, 1),
+            quote_ttl_seconds: 30,
+            max_price_age_seconds: 300,
+        }
+    }
+}
+
+/// 按市场价格计算的兑换结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangePricing {
+    /// 1 单位转出币种 = rate 单位转入币种，已含点差
+    pub rate: Decimal,
+    /// 手续费，以转出币种计
+    pub fee: Money,
+    /// 到账金额
+    pub to_amount: Money,
+}
+
+impl ExchangeConfig {
+    /// 校验配置取值：点差与手续费在 [0, 100) 内，报价锁定时间与价格有效期为正数
+    pub fn validate(&self) -> Result<(), String> {
+        let percent = Decimal::ZERO..Decimal::ONE_HUNDRED;
+        if !percent.contains(&self.spread_percent) {
+            return Err("spreadPercent must be between 0 and 100".to_string());
+        }
+        if !percent.contains(&self.fee_percent) {
+            return Err("feePercent must be between 0 and 100".to_string());
+        }
+        if self.quote_ttl_seconds <= 0 || self.max_price_age_seconds <= 0 {
+            return Err("quoteTtlSeconds and maxPriceAgeSeconds must be positive".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// 按 DG/USDT 市场价格计算兑换汇率、手续费与到账金额，仅支持 USDT 与 DG 互换
+    pub fn price(
+        &self,
+        from: Money,
+        to: Currency,
+        price: Decimal,
+    ) -> Result<ExchangePricing, String> {
+        if !from.is_positive() {
+            return Err("Exchange amount must be positive".to_string());
+        }
+        if price <= Decimal::ZERO {
+            return Err("DG price is unavailable".to_string());
+        }
+        let spread = self.spread_percent / Decimal::ONE_HUNDRED;
+        let rate = match (from.currency(), to) {
+            (Currency::Usdt, Currency::Dg) => Decimal::ONE / (price * (Decimal::ONE + spread)),
+            (Currency::Dg, Currency::Usdt) => price * (Decimal::ONE - spread),
+            (from, to) => return Err(format!("Unsupported exchange pair: {} to {}", from, to)),
+        };
+        let rate = rate.round_dp_with_strategy(MONEY_SCALE, MONEY_ROUNDING);
+
+        let fee = from.percent(self.fee_percent);
+        let to_amount = (from - fee).exchange(rate, to);
+        if !to_amount.is_positive() {
+            return Err("Exchange amount is too small".to_string());
+        }
+
+        Ok(ExchangePricing {
+            rate,
+            fee,
+            to_amount,
+        })
+    }
+}
+
+/// 兑换报价（exchange_quotes）
+#[derive(Debug, Clone, FromRow)]
+pub struct ExchangeQuote {
+    pub id: u64,
+    pub quote_id: String,
+    pub user_id: u64,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub from_amount: Decimal, // 转出金额（含手续费）
+    pub fee: Decimal,
+    pub to_amount: Decimal,
+    pub exchange_rate: Decimal,
+    pub market_price: Decimal,
+    pub expires_at: OffsetDateTime,
+    pub transaction_id: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ExchangeConfig {
+        ExchangeConfig {
+            spread_percent: Decimal::ONE,
+            fee_percent: Decimal::ONE,
+            ..ExchangeConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_price_buy_dg() {
+        // 1 DG = 0.5 USDT，买入按 0.505 计价
+        let pricing = config()
+            .price(
+                Money::usdt(Decimal::from(101)),
+                Currency::Dg,
+                Decimal::new(5, 1),
+            )
+            .unwrap();
+        assert_eq!(pricing.fee, Money::usdt(Decimal::new(101, 2)));
+        assert_eq!(pricing.rate, Decimal::new(198019802, 8));
+        // 到账金额按锁定汇率计算：99.99 × 1.98019802
+        assert_eq!(pricing.to_amount, Money::dg(Decimal::new(19800000002, 8)));
+    }
+
+    #[test]
+    fn test_price_sell_dg() {
+        let pricing = config()
+            .price(
+                Money::dg(Decimal::from(200)),
+                Currency::Usdt,
+                Decimal::new(5, 1),
+            )
+            .unwrap();
+        assert_eq!(pricing.rate, Decimal::new(495, 3));
+        assert_eq!(pricing.fee, Money::dg(Decimal::from(2)));
+        assert_eq!(pricing.to_amount, Money::usdt(Decimal::new(9801, 2)));
+    }
+
+    #[test]
+    fn test_price_rejects_invalid_input() {
+        let config = config();
+        let price = Decimal::new(5, 1);
+        assert!(config
+            .price(Money::zero(Currency::Usdt), Currency::Dg, price)
+            .is_err());
+        assert!(config
+            .price(Money::usdt(Decimal::ONE), Currency::Usdt, price)
+            .is_err());
+        assert!(config
+            .price(Money::usdt(Decimal::ONE), Currency::Points, price)
+            .is_err());
+        assert!(config
+            .price(Money::usdt(Decimal::ONE), Currency::Dg, Decimal::ZERO)
+            .is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct IdempotencyKey {
+    pub id: u64,
+    pub user_id: u64,
+    pub idempotency_key: String,
+    pub request_method: String,
+    pub request_path: String,
+    pub request_hash: String,
+    pub response_status: Option<u16>, // null 表示首次请求仍在处理中
+    pub response_body: Option<String>,
+    pub expired_at: OffsetDateTime,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
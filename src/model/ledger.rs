@@ -0,0 +1,327 @@
+use crate::model::wallet::Wallet;
+use crate::utils::money::{Currency, Money};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::FromRow;
+use strum::{Display, EnumString, IntoEnumIterator};
+
+/// 平台系统科目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum SystemAccount {
+    /// 算力销售收入
+    Sales,
+    /// 奖励支出：挖矿收益、邀请分成、活动福利
+    Rewards,
+    /// 提现手续费收入
+    Fees,
+    /// 币种兑换
+    Exchange,
+    /// 账本上线前的期初余额
+    Opening,
+}
+
+/// 记账科目：用户可用余额、冻结余额、用户链上钱包（平台外）或平台系统科目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerAccount {
+    Available(u64),
+    Frozen(u64),
+    External(u64),
+    System(SystemAccount),
+}
+
+/// 用户可用余额科目编码
+pub const ACCOUNT_AVAILABLE: &str = "available";
+/// 用户冻结余额科目编码
+pub const ACCOUNT_FROZEN: &str = "frozen";
+/// 用户链上钱包科目编码
+pub const ACCOUNT_EXTERNAL: &str = "external";
+
+impl LedgerAccount {
+    /// 科目编码，与 ledger_postings.account 一致
+    pub fn code(&self) -> String {
+        match self {
+            LedgerAccount::Available(_) => ACCOUNT_AVAILABLE.to_string(),
+            LedgerAccount::Frozen(_) => ACCOUNT_FROZEN.to_string(),
+            LedgerAccount::External(_) => ACCOUNT_EXTERNAL.to_string(),
+            LedgerAccount::System(account) => account.to_string(),
+        }
+    }
+
+    /// 用户科目所属用户，系统科目为 None
+    pub fn user_id(&self) -> Option<u64> {
+        match self {
+            LedgerAccount::Available(user_id)
+            | LedgerAccount::Frozen(user_id)
+            | LedgerAccount::External(user_id) => Some(*user_id),
+            LedgerAccount::System(_) => None,
+        }
+    }
+}
+
+/// 借贷方向：科目余额 = 贷方合计 - 借方合计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Direction {
+    Debit,
+    Credit,
+}
+
+/// 一笔资金划转：借记 from、贷记 to，天然借贷平衡
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub from: LedgerAccount,
+    pub to: LedgerAccount,
+    pub amount: Money,
+}
+
+/// 记账凭证：一次业务的全部资金划转，owner 为交易记录所反映的用户科目
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    owner: LedgerAccount,
+    transfers: Vec<Transfer>,
+}
+
+impl JournalEntry {
+    pub fn new(owner: LedgerAccount) -> Self {
+        Self {
+            owner,
+            transfers: Vec::new(),
+        }
+    }
+
+    /// 追加一笔划转，零金额忽略
+    pub fn transfer(mut self, from: LedgerAccount, to: LedgerAccount, amount: Money) -> Self {
+        if !amount.is_zero() {
+            self.transfers.push(Transfer { from, to, amount });
+        }
+        self
+    }
+
+    pub fn owner(&self) -> LedgerAccount {
+        self.owner
+    }
+
+    pub fn transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transfers.is_empty()
+    }
+
+    /// 借贷分录：每笔划转拆为借方与贷方两条
+    pub fn postings(&self) -> Vec<(LedgerAccount, Direction, Money)> {
+        self.transfers
+            .iter()
+            .flat_map(|t| {
+                [
+                    (t.from, Direction::Debit, t.amount),
+                    (t.to, Direction::Credit, t.amount),
+                ]
+            })
+            .collect()
+    }
+
+    /// 科目在指定币种上的净变动（贷方 - 借方）
+    pub fn net_change(&self, account: LedgerAccount, currency: Currency) -> Money {
+        self.postings()
+            .into_iter()
+            .filter(|(a, _, amount)| *a == account && amount.currency() == currency)
+            .fold(
+                Money::zero(currency),
+                |acc, (_, direction, amount)| match direction {
+                    Direction::Credit => acc + amount,
+                    Direction::Debit => acc - amount,
+                },
+            )
+    }
+
+    /// 交易记录的金额：owner 科目在首笔划转币种上的净变动
+    pub fn record_amount(&self) -> Option<Money> {
+        let currency = self.transfers.first()?.amount.currency();
+        Some(self.net_change(self.owner, currency))
+    }
+
+    /// 校验凭证：至少一笔划转，金额为正，借贷双方不同且按币种平衡
+    pub fn validate(&self) -> Result<(), String> {
+        if self.transfers.is_empty() {
+            return Err("journal entry has no transfers".to_string());
+        }
+        for transfer in &self.transfers {
+            if !transfer.amount.is_positive() {
+                return Err(format!(
+                    "transfer amount must be positive: {}",
+                    transfer.amount
+                ));
+            }
+            if transfer.from == transfer.to {
+                return Err(format!("transfer to the same account: {:?}", transfer.from));
+            }
+        }
+        let mut balances: Vec<(Currency, Decimal)> = Vec::new();
+        for (_, direction, amount) in self.postings() {
+            let signed = match direction {
+                Direction::Credit => amount.amount(),
+                Direction::Debit => -amount.amount(),
+            };
+            match balances.iter_mut().find(|(c, _)| *c == amount.currency()) {
+                Some((_, sum)) => *sum += signed,
+                None => balances.push((amount.currency(), signed)),
+            }
+        }
+        if let Some((currency, sum)) = balances.iter().find(|(_, sum)| !sum.is_zero()) {
+            return Err(format!("journal entry unbalanced in {}: {}", currency, sum));
+        }
+
+        Ok(())
+    }
+}
+
+/// 按科目与币种汇总的账本余额
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LedgerBalance {
+    pub user_id: u64,
+    pub account: String,
+    pub currency: String,
+    pub balance: Decimal,
+}
+
+/// 钱包余额与账本推导余额的差异
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LedgerDrift {
+    pub account: String,
+    pub currency: String,
+    /// 由账本分录推导的余额
+    pub ledger: Decimal,
+    /// user_assets 中的余额
+    pub wallet: Decimal,
+}
+
+/// 按币种核对钱包可用/冻结余额与账本，返回不一致的科目
+pub fn ledger_drift(wallet: &Wallet, balances: &[LedgerBalance]) -> Vec<LedgerDrift> {
+    let ledger = |account: &str, currency: Currency| {
+        balances
+            .iter()
+            .filter(|b| b.account == account && b.currency == currency.to_string())
+            .map(|b| b.balance)
+            .sum::<Decimal>()
+    };
+    let mut drifts = Vec::new();
+    for currency in Currency::iter() {
+        for (account, amount) in [
+            (ACCOUNT_AVAILABLE, wallet.available(currency)),
+            (ACCOUNT_FROZEN, wallet.frozen(currency)),
+        ] {
+            let expected = ledger(account, currency);
+            if expected != amount.amount() {
+                drifts.push(LedgerDrift {
+                    account: account.to_string(),
+                    currency: currency.to_string(),
+                    ledger: expected,
+                    wallet: amount.amount(),
+                });
+            }
+        }
+    }
+    drifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_entry_postings_balance() {
+        let user = 7;
+        let entry = JournalEntry::new(LedgerAccount::Available(user))
+            .transfer(
+                LedgerAccount::Frozen(user),
+                LedgerAccount::External(user),
+                Money::usdt(Decimal::from(99)),
+            )
+            .transfer(
+                LedgerAccount::Frozen(user),
+                LedgerAccount::System(SystemAccount::Fees),
+                Money::usdt(Decimal::ONE),
+            )
+            .transfer(
+                LedgerAccount::System(SystemAccount::Rewards),
+                LedgerAccount::Available(user),
+                Money::zero(Currency::Usdt),
+            );
+
+        assert_eq!(entry.transfers().len(), 2);
+        assert_eq!(entry.postings().len(), 4);
+        assert!(entry.validate().is_ok());
+        assert_eq!(
+            entry.net_change(LedgerAccount::Frozen(user), Currency::Usdt),
+            Money::usdt(Decimal::from(-100))
+        );
+        assert_eq!(entry.record_amount(), Some(Money::zero(Currency::Usdt)));
+    }
+
+    #[test]
+    fn test_journal_entry_record_amount_follows_owner() {
+        let entry = JournalEntry::new(LedgerAccount::Available(3)).transfer(
+            LedgerAccount::Available(3),
+            LedgerAccount::Frozen(3),
+            Money::usdt(Decimal::from(50)),
+        );
+        assert_eq!(entry.record_amount(), Some(Money::usdt(Decimal::from(-50))));
+        assert_eq!(
+            LedgerAccount::System(SystemAccount::Rewards).code(),
+            "rewards"
+        );
+        assert_eq!(LedgerAccount::Frozen(3).user_id(), Some(3));
+    }
+
+    #[test]
+    fn test_journal_entry_validate() {
+        assert!(JournalEntry::new(LedgerAccount::Available(1))
+            .validate()
+            .is_err());
+
+        let negative = JournalEntry::new(LedgerAccount::Available(1)).transfer(
+            LedgerAccount::System(SystemAccount::Sales),
+            LedgerAccount::Available(1),
+            Money::usdt(Decimal::from(-5)),
+        );
+        assert!(negative.validate().is_err());
+
+        let same = JournalEntry::new(LedgerAccount::Available(1)).transfer(
+            LedgerAccount::Available(1),
+            LedgerAccount::Available(1),
+            Money::usdt(Decimal::ONE),
+        );
+        assert!(same.validate().is_err());
+    }
+
+    #[test]
+    fn test_ledger_drift() {
+        use crate::model::wallet::UserAsset;
+
+        let wallet = Wallet::new(vec![UserAsset {
+            user_id: 1,
+            currency: "USDT".to_string(),
+            balance: Decimal::from(80),
+            frozen_balance: Decimal::from(20),
+            total_earned: Decimal::ZERO,
+            updated_at: time::OffsetDateTime::now_utc(),
+        }]);
+        let balance = |account: &str, amount: i64| LedgerBalance {
+            user_id: 1,
+            account: account.to_string(),
+            currency: "USDT".to_string(),
+            balance: Decimal::from(amount),
+        };
+
+        assert!(
+            ledger_drift(&wallet, &[balance("available", 80), balance("frozen", 20)]).is_empty()
+        );
+        let drifts = ledger_drift(&wallet, &[balance("available", 90), balance("external", 5)]);
+        assert_eq!(drifts.len(), 2);
+        assert_eq!(drifts[0].ledger, Decimal::from(90));
+        assert_eq!(drifts[1].account, "frozen");
+    }
+}
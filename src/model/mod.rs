@@ -13,6 +13,19 @@ pub mod chat;
 pub mod user_security_questions;
 pub mod system_config;
 pub mod transactions;
+pub mod idempotency;
+pub mod coupon;
+pub mod deposit_address;
+pub mod withdrawal;
+pub mod operation_log;
+pub mod wallet;
+pub mod ledger;
+pub mod reconciliation;
+pub mod exchange;
+pub mod price;
+pub mod deposit;
+pub mod blockchain;
+pub mod settlement;
 
 pub use user::*;
 pub use power::*;
@@ -0,0 +1,27 @@
+use strum::{Display, EnumString};
+
+/// 操作日志状态：success 成功 / failed 失败 / error 错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum OperationStatus {
+    Success,
+    Failed,
+    Error,
+}
+
+/// 写入 operation_logs 的一条操作记录
+#[derive(Debug, Clone)]
+pub struct OperationLog {
+    /// 操作人ID，null 表示系统操作
+    pub user_id: Option<u64>,
+    /// 操作动作，如 withdrawal_approve
+    pub action: String,
+    /// 资源类型，如 withdrawal
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub ip_address: Option<String>,
+    /// 请求参数 JSON
+    pub request_data: Option<serde_json::Value>,
+    pub status: OperationStatus,
+    pub error_message: Option<String>,
+}
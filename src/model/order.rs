@@ -4,25 +4,66 @@ use sqlx::FromRow;
 use time::OffsetDateTime;
 use rust_decimal::Decimal;
 
-///订单状态：0 pending待支付/1 paid已支付/2 cancelled已取消/3 已升级
+///订单状态：0 pending待支付/1 paid已支付/2 cancelled已取消/3 已升级/4 expired已过期
 pub const ORDER_STATUS_PENDING: i8 = 0;
 pub const ORDER_STATUS_PAID: i8 = 1;
 pub const ORDER_STATUS_CANCELLED: i8 = 2;
 pub const ORDER_STATUS_UPGRADE: i8 = 3;
+pub const ORDER_STATUS_EXPIRED: i8 = 4;
+
+/// 订单状态值转换为接口使用的状态名
+pub fn order_status_name(status: i8) -> &'static str {
+    match status {
+        ORDER_STATUS_PENDING => "pending",
+        ORDER_STATUS_PAID => "paid",
+        ORDER_STATUS_CANCELLED => "cancelled",
+        ORDER_STATUS_UPGRADE => "upgrade",
+        ORDER_STATUS_EXPIRED => "expired",
+        _ => "unknown",
+    }
+}
+
+/// 接口状态名转换为订单状态值
+pub fn order_status_from_name(name: &str) -> Option<i8> {
+    match name {
+        "pending" => Some(ORDER_STATUS_PENDING),
+        "paid" => Some(ORDER_STATUS_PAID),
+        "cancelled" => Some(ORDER_STATUS_CANCELLED),
+        "upgrade" => Some(ORDER_STATUS_UPGRADE),
+        "expired" => Some(ORDER_STATUS_EXPIRED),
+        _ => None,
+    }
+}
+
+/// 用户订单列表筛选条件，None 表示不筛选
+#[derive(Debug, Clone, Default)]
+pub struct OrderFilter {
+    pub status: Option<i8>,
+    pub power_package_id: Option<u64>,
+    pub start_time: Option<NaiveDateTime>, // 包含
+    pub end_time: Option<NaiveDateTime>,   // 不包含
+}
 #[derive(Debug, Clone, FromRow)]
 pub struct Order {
     pub id: u64,
     pub order_id: String,
     pub user_id: u64,
     pub power_package_id: u64,
+    pub promotion_package_id: Option<u64>, // 限时优惠套餐ID
     pub quantity: u32,
     pub amount: Decimal, // 使用 sqlx 的 BigDecimal 类型
     pub asset_pay: Decimal, // 使用 sqlx 的 BigDecimal 类型
     pub coin_pay: Decimal, // 使用 sqlx 的 BigDecimal 类型
+    pub coupon_id: Option<u64>,
+    pub discount_amount: Decimal, // 优惠券减免金额
+    pub upgrade_power_id: Option<u64>, // 升级订单对应的旧算力ID
+    pub upgrade_credit: Decimal,       // 旧算力包剩余价值抵扣金额
     pub blockchain_type: String,
     pub blockchain_address: String,
     pub transaction_hash: Option<String>,
+    pub confirmations: u32, // 链上确认数
     pub status: i8,  // pending, paid, completed, cancelled, expired
+    pub expired_at: OffsetDateTime,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -53,3 +94,22 @@ pub struct PurchaseOrderRequest {
     pub power_id: i64,
     pub quantity: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_status_name_round_trip() {
+        for status in [
+            ORDER_STATUS_PENDING,
+            ORDER_STATUS_PAID,
+            ORDER_STATUS_CANCELLED,
+            ORDER_STATUS_UPGRADE,
+            ORDER_STATUS_EXPIRED,
+        ] {
+            assert_eq!(order_status_from_name(order_status_name(status)), Some(status));
+        }
+        assert_eq!(order_status_from_name("completed"), None);
+    }
+}
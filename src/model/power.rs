@@ -14,6 +14,7 @@ pub struct PowerPackage {
     pub lv: u16,                         // varchar(255) NOT NULL
     pub daily_yield_percentage: Decimal, // tinyint unsigned NOT NULL
     pub amount: Decimal,
+    pub duration_days: u32, // 算力有效期天数
     #[sqlx(json)]
     pub description: Json<Vec<u8>>,
     pub status: i8,
@@ -186,6 +187,7 @@ pub fn convert_power_package_to_package_item(
         lv: package.lv,
         daily_yield_percentage: package.daily_yield_percentage.to_f64().unwrap_or(0.0),
         amount: package.amount.to_f64().unwrap_or(0.0),
+        duration_days: package.duration_days,
         description: description_str,
         status: package.status,
         is_upgrade: if package.is_upgrade > 0 { true } else { false },
@@ -0,0 +1,46 @@
+use crate::utils::time_zone::TimeZone;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::FromRow;
+use time::{Date, OffsetDateTime};
+
+/// 日K线在 price_data.interval 中的取值
+pub const PRICE_INTERVAL_DAY: &str = "1d";
+
+/// 交易对实时价格（real_time_prices）
+#[derive(Debug, Clone, FromRow)]
+pub struct RealTimePrice {
+    pub symbol: String,
+    pub current_price: Decimal,
+    pub last_updated_at: OffsetDateTime,
+}
+
+/// 日K线在 price_data.timestamp 中的取值：交易日北京时间零点的 Unix 时间戳
+pub fn day_timestamp(date: Date) -> u64 {
+    date.midnight()
+        .assume_offset(TimeZone::Beijing.offset())
+        .unix_timestamp() as u64
+}
+
+/// 一次行情同步的结果
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceSyncSummary {
+    /// 是否更新了实时价格
+    pub ticker_updated: bool,
+    /// 写入的日K线条数
+    pub candles: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Month;
+
+    #[test]
+    fn test_day_timestamp() {
+        // 2024-06-01 00:00:00 +08:00 = 2024-05-31 16:00:00 UTC
+        let date = Date::from_calendar_date(2024, Month::June, 1).unwrap();
+        assert_eq!(day_timestamp(date), 1717171200);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::model::ledger::LedgerDrift;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::{Date, OffsetDateTime};
+
+/// 对账差异报告（reconciliation_reports）
+#[derive(Debug, Clone, FromRow)]
+pub struct ReconciliationReport {
+    pub id: u64,
+    pub run_date: Date,
+    pub user_id: u64,
+    pub account: String,
+    pub currency: String,
+    pub ledger_balance: Decimal,
+    pub wallet_balance: Decimal,
+    pub drift: Decimal, // 钱包余额 - 账本余额
+    pub account_frozen: bool,
+    pub created_at: OffsetDateTime,
+}
+
+/// 对账配置，system_configs.reconciliation 为该结构，未配置时只报告不冻结
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconciliationConfig {
+    /// 差异超过阈值时是否冻结用户资金
+    #[serde(rename = "freezeEnabled", default)]
+    pub freeze_enabled: bool,
+    /// 冻结阈值，按单个科目差异的绝对值比较
+    #[serde(rename = "freezeThreshold")]
+    pub freeze_threshold: Decimal,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            freeze_enabled: false,
+            freeze_threshold: Decimal::ONE,
+        }
+    }
+}
+
+impl ReconciliationConfig {
+    /// 校验配置取值：冻结阈值不能为负
+    pub fn validate(&self) -> Result<(), String> {
+        if self.freeze_threshold < Decimal::ZERO {
+            return Err("freezeThreshold must not be negative".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// 用户的差异是否需要冻结资金
+    pub fn should_freeze(&self, drifts: &[LedgerDrift]) -> bool {
+        self.freeze_enabled
+            && drifts
+                .iter()
+                .any(|d| (d.wallet - d.ledger).abs() > self.freeze_threshold)
+    }
+}
+
+/// 一次对账的结果
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationSummary {
+    /// 核对的用户数
+    pub users: usize,
+    /// 存在差异的科目数
+    pub mismatches: usize,
+    /// 被冻结资金的用户数
+    pub frozen: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drift(ledger: i64, wallet: i64) -> LedgerDrift {
+        LedgerDrift {
+            account: "available".to_string(),
+            currency: "USDT".to_string(),
+            ledger: Decimal::from(ledger),
+            wallet: Decimal::from(wallet),
+        }
+    }
+
+    #[test]
+    fn test_should_freeze() {
+        let config = ReconciliationConfig {
+            freeze_enabled: true,
+            freeze_threshold: Decimal::from(10),
+        };
+        assert!(!config.should_freeze(&[]));
+        assert!(!config.should_freeze(&[drift(100, 110)]));
+        assert!(config.should_freeze(&[drift(100, 110), drift(100, 89)]));
+        assert!(!ReconciliationConfig::default().should_freeze(&[drift(0, 1000)]));
+    }
+}
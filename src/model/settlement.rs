@@ -0,0 +1,107 @@
+use crate::utils::money::{Currency, Money};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use strum::{Display, EnumString};
+use time::{Date, OffsetDateTime};
+
+/// 结算批次状态：running 结算中 / completed 已完成 / failed 失败待重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum SettlementRunStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// 每日收益结算批次（settlement_runs），每个交易日一条
+#[derive(Debug, Clone, FromRow)]
+pub struct SettlementRun {
+    pub id: u64,
+    pub business_date: Date,
+    pub status: String,
+    pub close_price: Decimal,
+    pub checkpoint_user_id: u64, // 已入账的最大用户ID
+    pub users: u32,
+    pub records: u32,
+    pub credited: Decimal,
+    pub error: Option<String>,
+    pub started_at: OffsetDateTime,
+    pub finished_at: Option<OffsetDateTime>,
+    pub updated_at: OffsetDateTime,
+}
+
+/// 补结算中单个交易日的结果；试运行时为预计入账数据，不写入任何记录
+#[derive(Debug, Clone)]
+pub struct SettlementReport {
+    pub business_date: Date,
+    /// 结算批次状态；试运行且尚未结算时为 None
+    pub status: Option<String>,
+    pub close_price: Option<Decimal>,
+    pub users: u32,
+    pub records: u32,
+    pub credited: Decimal,
+    /// 失败原因或提示
+    pub message: Option<String>,
+}
+
+impl SettlementReport {
+    /// 未能结算的交易日
+    pub fn failed(business_date: Date, message: String) -> Self {
+        Self {
+            business_date,
+            status: Some(SettlementRunStatus::Failed.to_string()),
+            close_price: None,
+            users: 0,
+            records: 0,
+            credited: Decimal::ZERO,
+            message: Some(message),
+        }
+    }
+}
+
+impl From<SettlementRun> for SettlementReport {
+    fn from(run: SettlementRun) -> Self {
+        Self {
+            business_date: run.business_date,
+            status: Some(run.status),
+            close_price: Some(run.close_price),
+            users: run.users,
+            records: run.records,
+            credited: run.credited,
+            message: run.error,
+        }
+    }
+}
+
+/// 算力单日挖矿收益（DG）：算力包金额（USDT）× 日收益率，按 DG 单价折算。
+/// 收盘价为每 100 DG 的 USDT 报价，单价为收盘价 / 100，与历史 user_power_record.close_price 一致
+pub fn mining_earning(
+    package_amount: Decimal,
+    daily_yield_percentage: Decimal,
+    close_price: Decimal,
+) -> Money {
+    Money::usdt(package_amount)
+        .percent(daily_yield_percentage)
+        .convert_at_price(close_price / Decimal::ONE_HUNDRED, Currency::Dg)
+        .unwrap_or(Money::zero(Currency::Dg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mining_earning() {
+        // 1000 USDT × 1% = 10 USDT，收盘价 2 即单价 0.02 USDT，折合 500 DG
+        let earning = mining_earning(Decimal::from(1000), Decimal::ONE, Decimal::from(2));
+        assert_eq!(earning, Money::dg(Decimal::from(500)));
+        // 1000 USDT × 1.5% = 15 USDT，收盘价 0.3 即单价 0.003 USDT，折合 5000 DG
+        let earning = mining_earning(Decimal::from(1000), Decimal::new(15, 1), Decimal::new(3, 1));
+        assert_eq!(earning, Money::dg(Decimal::from(5000)));
+        assert_eq!(
+            mining_earning(Decimal::from(1000), Decimal::ONE, Decimal::ZERO),
+            Money::zero(Currency::Dg)
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::model::blockchain::Blockchain;
+use crate::model::exchange::ExchangeConfig;
+use crate::model::reconciliation::ReconciliationConfig;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use strum::{Display, EnumString};
@@ -23,7 +26,72 @@ pub struct ConfigLevel {
 #[derive(Display, EnumString, Debug, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum SystemConfigType {
+    /// 支持的链，见 model::blockchain::Blockchain
     Blockchain,
     WelcomeBonus,
     UpgradeProgress,
+    PurchaseLimit,
+    Reconciliation,
+    Exchange,
+    /// 定时任务 cron 表达式覆盖，任务名 -> cron 表达式，见 cron::jobs
+    CronSchedules,
+}
+
+impl SystemConfigType {
+    /// 写入配置前按配置类型解析并校验取值，非结构化的配置不校验
+    pub fn validate_value(&self, value: &str) -> Result<(), String> {
+        let parse_err = |e: serde_json::Error| format!("Invalid {} config: {}", self, e);
+        match self {
+            SystemConfigType::Blockchain => serde_json::from_str::<Vec<Blockchain>>(value)
+                .map_err(parse_err)?
+                .iter()
+                .try_for_each(Blockchain::validate),
+            SystemConfigType::PurchaseLimit => serde_json::from_str::<Vec<PurchaseLimit>>(value)
+                .map_err(parse_err)?
+                .iter()
+                .try_for_each(PurchaseLimit::validate),
+            SystemConfigType::Reconciliation => {
+                serde_json::from_str::<ReconciliationConfig>(value)
+                    .map_err(parse_err)?
+                    .validate()
+            }
+            SystemConfigType::Exchange => serde_json::from_str::<ExchangeConfig>(value)
+                .map_err(parse_err)?
+                .validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 未配置购买限制的算力包单笔订单最大购买数量
+pub const DEFAULT_MAX_QUANTITY_PER_ORDER: u32 = 10;
+
+/// 算力包购买限制，system_configs.purchase_limit 为该结构的数组
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PurchaseLimit {
+    #[serde(rename = "powerId")]
+    pub power_id: u64,
+    /// 单笔订单最大购买数量
+    #[serde(rename = "maxPerOrder")]
+    pub max_per_order: u32,
+    /// 每个用户累计最多持有数量（待支付+生效中），0 表示不限
+    #[serde(rename = "maxPerUser", default)]
+    pub max_per_user: u32,
+}
+
+impl PurchaseLimit {
+    /// 校验配置取值：单笔上限至少为 1，设置了持有上限时不小于单笔上限
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_per_order < 1 {
+            return Err(format!("powerId {}: maxPerOrder must be at least 1", self.power_id));
+        }
+        if self.max_per_user > 0 && self.max_per_user < self.max_per_order {
+            return Err(format!(
+                "powerId {}: maxPerUser must not be less than maxPerOrder",
+                self.power_id
+            ));
+        }
+
+        Ok(())
+    }
 }
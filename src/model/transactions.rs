@@ -68,10 +68,12 @@ pub struct Transactions {
     pub updated_at: OffsetDateTime,
 }
 
-/// 交易类型：提现/兑换/购买/撤消购买/空投/邀请/挖矿收益/福利
+/// 交易类型：充值/提现/兑换/购买/撤消购买/空投/邀请/挖矿收益/福利/升级抵扣
 #[derive(Debug, Clone, Serialize, Deserialize, EnumString, Display)]
 #[strum(serialize_all = "snake_case")]
 pub enum OrderType {
+    ///充值
+    Recharge,
     ///提现
     Withdraw,
     ///兑换
@@ -88,6 +90,8 @@ pub enum OrderType {
     MiningEarning,
     ///福利
     Welcome,
+    ///升级抵扣
+    UpgradeCredit,
 }
 
 /// 交易状态：pending待处理/processing处理中/completed已完成/failed失败/cancelled已取消
@@ -2,6 +2,7 @@ use chrono::NaiveDateTime;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use strum::{Display, EnumString};
 use time::OffsetDateTime;
 // use derive_builder::Builder;
 
@@ -11,12 +12,11 @@ pub struct User {
     pub username: String,      // varchar(50) NOT NULL
     pub password_hash: String, // varchar(255) NOT NULL
     pub user_level: u8,        // tinyint unsigned NOT NULL
+    pub role: String,          // enum('user','admin') NOT NULL，见 UserRole
     pub invite_code: String,   // varchar(20) NOT NULL
     pub inviter_id: u64,       // bigint unsigned DEFAULT NULL
     pub upgrade_progress: i32,
     pub parent_inviter_id: u64,
-    pub total_assets: Decimal,                 // decimal(20,8) NOT NULL
-    pub dg_amount: Decimal,                    // decimal(20,8) NOT NULL
     pub is_kyc_verified: i8,                   // tinyint(1) NOT NULL
     pub has_security_questions: i8,            // tinyint(1) NOT NULL
     pub is_active: i8,                         // tinyint(1) NOT NULL
@@ -29,6 +29,14 @@ pub struct User {
     pub last_login_at: Option<OffsetDateTime>, // timestamp NULL
 }
 
+/// 后台权限角色（users.role），与会员等级 user_level 无关，会员等级随消费自动提升，不能作为后台权限依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserRole {
+    User,
+    Admin,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct UserSecurityAnswer {
     pub id: i64,
@@ -0,0 +1,83 @@
+use crate::utils::money::{Currency, Money};
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// 用户单币种资产账户（user_assets）
+#[derive(Debug, Clone, FromRow)]
+pub struct UserAsset {
+    pub user_id: u64,
+    pub currency: String,
+    pub balance: Decimal,        // 可用余额
+    pub frozen_balance: Decimal, // 冻结余额，如提现处理中
+    pub total_earned: Decimal,   // 累计收益
+    pub updated_at: OffsetDateTime,
+}
+
+/// 用户钱包：按币种汇总可用与冻结余额，未开户的币种余额视为 0
+#[derive(Debug, Clone, Default)]
+pub struct Wallet {
+    assets: Vec<UserAsset>,
+}
+
+impl Wallet {
+    pub fn new(assets: Vec<UserAsset>) -> Self {
+        Self { assets }
+    }
+
+    fn find(&self, currency: Currency) -> Option<&UserAsset> {
+        let code = currency.to_string();
+        self.assets.iter().find(|asset| asset.currency == code)
+    }
+
+    /// 可用余额
+    pub fn available(&self, currency: Currency) -> Money {
+        let amount = self.find(currency).map_or(Decimal::ZERO, |asset| asset.balance);
+        Money::new(amount, currency)
+    }
+
+    /// 冻结余额
+    pub fn frozen(&self, currency: Currency) -> Money {
+        let amount = self
+            .find(currency)
+            .map_or(Decimal::ZERO, |asset| asset.frozen_balance);
+        Money::new(amount, currency)
+    }
+
+    /// 累计收益
+    pub fn total_earned(&self, currency: Currency) -> Money {
+        let amount = self
+            .find(currency)
+            .map_or(Decimal::ZERO, |asset| asset.total_earned);
+        Money::new(amount, currency)
+    }
+
+    /// 总余额 = 可用 + 冻结
+    pub fn total(&self, currency: Currency) -> Money {
+        self.available(currency) + self.frozen(currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wallet_balances_by_currency() {
+        let wallet = Wallet::new(vec![UserAsset {
+            user_id: 1,
+            currency: "USDT".to_string(),
+            balance: Decimal::from(80),
+            frozen_balance: Decimal::from(20),
+            total_earned: Decimal::from(5),
+            updated_at: OffsetDateTime::now_utc(),
+        }]);
+
+        assert_eq!(wallet.available(Currency::Usdt), Money::usdt(Decimal::from(80)));
+        assert_eq!(wallet.frozen(Currency::Usdt), Money::usdt(Decimal::from(20)));
+        assert_eq!(wallet.total(Currency::Usdt), Money::usdt(Decimal::from(100)));
+        assert_eq!(wallet.total_earned(Currency::Usdt), Money::usdt(Decimal::from(5)));
+        assert_eq!(wallet.available(Currency::Dg), Money::zero(Currency::Dg));
+        assert_eq!(wallet.total(Currency::Points), Money::zero(Currency::Points));
+    }
+}
@@ -0,0 +1,214 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use strum::{Display, EnumString};
+use time::OffsetDateTime;
+
+/// 提现状态：pending 待审核 / processing 处理中 / review 待人工复核 / completed 已完成 / failed 失败 / cancelled 已取消
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum WithdrawalStatus {
+    Pending,
+    Processing,
+    /// 无法确认是否已转出，冻结资金保留，由管理员核对链上结果后完成或退回
+    Review,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl WithdrawalStatus {
+    /// 状态流转：pending → processing/cancelled/failed(拒绝)，processing → completed/failed/review，
+    /// review → completed/failed
+    pub fn can_transition_to(&self, next: WithdrawalStatus) -> bool {
+        use WithdrawalStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Processing)
+                | (Pending, Cancelled)
+                | (Pending, Failed)
+                | (Processing, Completed)
+                | (Processing, Failed)
+                | (Processing, Review)
+                | (Review, Completed)
+                | (Review, Failed)
+        )
+    }
+
+    /// 终态的提现不再占用冻结资金
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            WithdrawalStatus::Completed | WithdrawalStatus::Failed | WithdrawalStatus::Cancelled
+        )
+    }
+}
+
+/// 新建的待审核提现申请
+#[derive(Debug, Clone)]
+pub struct NewWithdrawal<'a> {
+    pub user_id: u64,
+    pub withdrawal_id: &'a str,
+    pub amount: Decimal, // 申请金额（含手续费）
+    pub fee: Decimal,
+    pub currency: &'a str,
+    pub blockchain_type: &'a str,
+    pub destination_address: &'a str,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Withdrawal {
+    pub id: u64,
+    pub user_id: u64,
+    pub withdrawal_id: String,
+    pub amount: Decimal, // 申请金额（含手续费），实际到账 = amount - fee
+    pub currency: String,
+    pub blockchain_type: String,
+    pub destination_address: String,
+    pub status: String,
+    pub fee: Decimal,
+    pub transaction_hash: Option<String>,
+    pub confirmations: Option<u32>,
+    pub payout_attempts: u32,
+    pub next_payout_at: Option<OffsetDateTime>,
+    pub payout_error: Option<String>,
+    pub payout_submitted_at: Option<OffsetDateTime>,
+    pub reviewer_id: Option<u64>,
+    pub rejection_reason: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    pub processed_at: Option<OffsetDateTime>,
+}
+
+impl Withdrawal {
+    pub fn status(&self) -> Option<WithdrawalStatus> {
+        self.status.parse().ok()
+    }
+
+    /// 实际到账金额
+    pub fn receive_amount(&self) -> Decimal {
+        self.amount - self.fee
+    }
+}
+
+/// 出账提交的最大尝试次数，超过后仍无法确认是否已转出的提现转入人工复核
+pub const PAYOUT_MAX_ATTEMPTS: u32 = 5;
+/// 出账交易广播后超过该时间（分钟）仍未上链或查询不到时转入人工复核
+pub const PAYOUT_CONFIRM_TIMEOUT_MINUTES: i64 = 60;
+/// 出账重试的初始退避时间（秒），每次失败翻倍
+pub const PAYOUT_RETRY_BASE_SECONDS: i64 = 60;
+/// 出账重试的最大退避时间（秒）
+pub const PAYOUT_RETRY_MAX_SECONDS: i64 = 3600;
+
+/// 第 attempts 次出账提交失败后的退避时间：60s、120s、240s……，不超过一小时
+pub fn payout_backoff(attempts: u32) -> time::Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let seconds = PAYOUT_RETRY_BASE_SECONDS.saturating_mul(1 << exponent);
+    time::Duration::seconds(seconds.min(PAYOUT_RETRY_MAX_SECONDS))
+}
+
+/// 注册不足该天数的账户提示为新账户
+pub const RISK_NEW_ACCOUNT_DAYS: i64 = 7;
+/// 该小时数内修改过密码的账户提示为近期改密
+pub const RISK_PASSWORD_CHANGED_HOURS: i64 = 48;
+
+/// 待审核提现及审核所需的用户风险信息
+#[derive(Debug, Clone, FromRow)]
+pub struct WithdrawalReview {
+    pub withdrawal_id: String,
+    pub user_id: u64,
+    pub username: String,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub currency: String,
+    pub blockchain_type: String,
+    pub destination_address: String,
+    pub created_at: OffsetDateTime,
+    pub user_created_at: OffsetDateTime,
+    pub is_kyc_verified: bool,
+    pub password_changed_at: Option<OffsetDateTime>,
+}
+
+impl WithdrawalReview {
+    /// 账户注册天数
+    pub fn account_age_days(&self, now: OffsetDateTime) -> i64 {
+        (now - self.user_created_at).whole_days()
+    }
+
+    /// 风险提示：new_account 新注册账户 / kyc_unverified 未完成 KYC / recent_password_change 近期改密
+    pub fn risk_hints(&self, now: OffsetDateTime) -> Vec<&'static str> {
+        let mut hints = Vec::new();
+        if self.account_age_days(now) < RISK_NEW_ACCOUNT_DAYS {
+            hints.push("new_account");
+        }
+        if !self.is_kyc_verified {
+            hints.push("kyc_unverified");
+        }
+        if matches!(self.password_changed_at, Some(changed) if (now - changed).whole_hours() < RISK_PASSWORD_CHANGED_HOURS)
+        {
+            hints.push("recent_password_change");
+        }
+        hints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_withdrawal_status_transitions() {
+        use WithdrawalStatus::*;
+        assert!(Pending.can_transition_to(Processing));
+        assert!(Pending.can_transition_to(Cancelled));
+        assert!(Processing.can_transition_to(Completed));
+        assert!(Processing.can_transition_to(Failed));
+        assert!(!Processing.can_transition_to(Cancelled));
+        assert!(!Completed.can_transition_to(Failed));
+        assert!(!Cancelled.can_transition_to(Processing));
+        assert!(Processing.can_transition_to(Review));
+        assert!(Review.can_transition_to(Completed));
+        assert!(Review.can_transition_to(Failed));
+        assert!(!Pending.can_transition_to(Review));
+        assert!(!Review.is_final());
+        assert_eq!("processing".parse::<WithdrawalStatus>().unwrap(), Processing);
+        assert_eq!(Cancelled.to_string(), "cancelled");
+    }
+
+    #[test]
+    fn test_payout_backoff() {
+        assert_eq!(payout_backoff(1), time::Duration::seconds(60));
+        assert_eq!(payout_backoff(2), time::Duration::seconds(120));
+        assert_eq!(payout_backoff(4), time::Duration::seconds(480));
+        assert_eq!(payout_backoff(7), time::Duration::seconds(3600));
+        assert_eq!(payout_backoff(u32::MAX), time::Duration::seconds(3600));
+    }
+
+    #[test]
+    fn test_withdrawal_risk_hints() {
+        let now = OffsetDateTime::now_utc();
+        let mut review = WithdrawalReview {
+            withdrawal_id: "W1".to_string(),
+            user_id: 1,
+            username: "alice".to_string(),
+            amount: Decimal::from(100),
+            fee: Decimal::ONE,
+            currency: "USDT".to_string(),
+            blockchain_type: "TRC20".to_string(),
+            destination_address: "TAddr".to_string(),
+            created_at: now,
+            user_created_at: now - time::Duration::days(30),
+            is_kyc_verified: true,
+            password_changed_at: None,
+        };
+        assert!(review.risk_hints(now).is_empty());
+
+        review.user_created_at = now - time::Duration::days(2);
+        review.is_kyc_verified = false;
+        review.password_changed_at = Some(now - time::Duration::hours(3));
+        assert_eq!(
+            review.risk_hints(now),
+            vec!["new_account", "kyc_unverified", "recent_password_change"]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PriceError {
+    #[error("Price request error: {0}")]
+    RequestError(String),
+
+    #[error("Price response error: {0}")]
+    ResponseError(String),
+
+    #[error("Unsupported price source: {0}")]
+    UnsupportedSource(String),
+}
+
+impl From<reqwest::Error> for PriceError {
+    fn from(err: reqwest::Error) -> Self {
+        PriceError::RequestError(err.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, PriceError>;
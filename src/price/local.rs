@@ -0,0 +1,140 @@
+use super::error::{PriceError, Result};
+use super::{Candle, PriceSource, Ticker};
+use axum::async_trait;
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use time::format_description::well_known::Iso8601;
+use time::Date;
+
+/// 本地行情文件数据源，每次查询重新读取文件，便于人工维护或由外部系统导出。
+/// 文件为 CSV：symbol,date,open,high,low,close,volume,amount，date 为北京时间交易日（YYYY-MM-DD），
+/// 首行表头与 # 开头的行会被忽略
+pub struct CsvPriceSource {
+    path: PathBuf,
+}
+
+impl CsvPriceSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 读取交易对的全部日K线，按日期升序
+    fn load(&self, symbol: &str) -> Result<Vec<Candle>> {
+        let content = std::fs::read_to_string(&self.path).map_err(|err| {
+            PriceError::RequestError(format!("failed to read {}: {}", self.path.display(), err))
+        })?;
+        let mut candles = parse_csv(&content, symbol).map_err(|err| {
+            PriceError::ResponseError(format!("invalid price file {}: {}", self.path.display(), err))
+        })?;
+        candles.sort_by_key(|candle| candle.date);
+
+        Ok(candles)
+    }
+}
+
+/// 解析行情文件中指定交易对的日K线
+fn parse_csv(content: &str, symbol: &str) -> std::result::Result<Vec<Candle>, String> {
+    let mut candles = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("symbol") {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [row_symbol, date, open, high, low, close, volume, amount] = fields[..] else {
+            return Err(format!("line {}: expected 8 fields", index + 1));
+        };
+        if row_symbol != symbol {
+            continue;
+        }
+        let decimal = |value: &str| {
+            value
+                .parse::<Decimal>()
+                .map_err(|_| format!("line {}: invalid number {}", index + 1, value))
+        };
+        candles.push(Candle {
+            date: Date::parse(date, &Iso8601::DATE)
+                .map_err(|_| format!("line {}: invalid date {}", index + 1, date))?,
+            open: decimal(open)?,
+            high: decimal(high)?,
+            low: decimal(low)?,
+            close: decimal(close)?,
+            volume: decimal(volume)?,
+            amount: decimal(amount)?,
+        });
+    }
+
+    Ok(candles)
+}
+
+#[async_trait]
+impl PriceSource for CsvPriceSource {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    /// 以最后一根日K线作为最新行情，涨跌按前一日收盘价计算
+    async fn ticker(&self, symbol: &str) -> Result<Ticker> {
+        let candles = self.load(symbol)?;
+        let Some(last) = candles.last() else {
+            return Err(PriceError::ResponseError(format!(
+                "no price data for {} in {}",
+                symbol,
+                self.path.display()
+            )));
+        };
+        let prev_close = candles
+            .len()
+            .checked_sub(2)
+            .map_or(last.open, |index| candles[index].close);
+        let price_change = last.close - prev_close;
+        let price_change_percent = if prev_close.is_zero() {
+            Decimal::ZERO
+        } else {
+            (price_change / prev_close * Decimal::ONE_HUNDRED).round_dp(2)
+        };
+
+        Ok(Ticker {
+            price: last.close,
+            price_change,
+            price_change_percent,
+            high_24h: last.high,
+            low_24h: last.low,
+            volume_24h: last.volume,
+        })
+    }
+
+    async fn daily_candles(&self, symbol: &str, from: Date, to: Date) -> Result<Vec<Candle>> {
+        Ok(self
+            .load(symbol)?
+            .into_iter()
+            .filter(|candle| candle.date >= from && candle.date <= to)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Month;
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "symbol,date,open,high,low,close,volume,amount\n\
+                       # manual correction\n\
+                       DG/USDT,2024-06-02,0.55,0.60,0.54,0.58,800,460\n\
+                       BTC/USDT,2024-06-02,1,1,1,1,1,1\n\
+                       DG/USDT, 2024-06-01 ,0.50,0.56,0.48,0.55,1000,530.5\n";
+        let candles = parse_csv(content, "DG/USDT").unwrap();
+        assert_eq!(candles.len(), 2);
+        assert_eq!(
+            candles[1].date,
+            Date::from_calendar_date(2024, Month::June, 1).unwrap()
+        );
+        assert_eq!(candles[0].close, Decimal::new(58, 2));
+
+        assert!(parse_csv("DG/USDT,2024-06-01,0.5", "DG/USDT").is_err());
+        assert!(parse_csv("DG/USDT,2024-13-01,1,1,1,1,1,1", "DG/USDT").is_err());
+        assert!(parse_csv("DG/USDT,2024-06-01,x,1,1,1,1,1", "DG/USDT").is_err());
+    }
+}
@@ -0,0 +1,58 @@
+pub mod error;
+pub mod local;
+pub mod rest;
+
+use crate::config::PriceConfig;
+use axum::async_trait;
+use error::{PriceError, Result};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use time::Date;
+
+/// 日K线，按北京时间交易日划分
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    pub date: Date,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// 成交量（基础币种）
+    pub volume: Decimal,
+    /// 成交额（计价币种）
+    pub amount: Decimal,
+}
+
+/// 24 小时行情
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ticker {
+    pub price: Decimal,
+    pub price_change: Decimal,
+    pub price_change_percent: Decimal,
+    pub high_24h: Decimal,
+    pub low_24h: Decimal,
+    pub volume_24h: Decimal,
+}
+
+/// 行情数据源，同步到 price_data 与 real_time_prices。
+/// symbol 使用站内交易对格式，如 DG/USDT，由实现自行映射到数据源的交易对
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// 查询最新 24 小时行情
+    async fn ticker(&self, symbol: &str) -> Result<Ticker>;
+
+    /// 查询 [from, to] 范围内的日K线，按日期升序
+    async fn daily_candles(&self, symbol: &str, from: Date, to: Date) -> Result<Vec<Candle>>;
+}
+
+/// 根据配置创建行情数据源：rest 使用交易所行情接口，csv 使用本地行情文件，为空时不同步行情
+pub fn from_config(config: &PriceConfig) -> Result<Option<Arc<dyn PriceSource>>> {
+    match config.source.as_str() {
+        "" => Ok(None),
+        "rest" => Ok(Some(Arc::new(rest::RestPriceSource::new(config)?))),
+        "csv" => Ok(Some(Arc::new(local::CsvPriceSource::new(&config.csv_file)))),
+        source => Err(PriceError::UnsupportedSource(source.to_string())),
+    }
+}
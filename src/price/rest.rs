@@ -0,0 +1,171 @@
+use super::error::{PriceError, Result};
+use super::{Candle, PriceSource, Ticker};
+use crate::config::PriceConfig;
+use crate::utils::time_zone::TimeZone;
+use axum::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+use time::{Date, OffsetDateTime};
+
+/// 单次查询返回的最大K线条数（Binance 上限 1000）
+const KLINE_LIMIT: i64 = 1000;
+
+/// 基于交易所 REST 行情接口的数据源，兼容 Binance /api/v3 接口格式
+pub struct RestPriceSource {
+    client: reqwest::Client,
+    api_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ticker24hr {
+    last_price: String,
+    price_change: String,
+    price_change_percent: String,
+    high_price: String,
+    low_price: String,
+    volume: String,
+}
+
+impl RestPriceSource {
+    pub fn new(config: &PriceConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            api_url: config.rest_api_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// DG/USDT -> DGUSDT
+    fn market_symbol(symbol: &str) -> String {
+        symbol.replace('/', "").to_uppercase()
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    value
+        .parse()
+        .map_err(|_| PriceError::ResponseError(format!("Invalid price value: {}", value)))
+}
+
+/// 解析单条K线：[开盘时间(毫秒), 开, 高, 低, 收, 成交量, 收盘时间, 成交额, ...]
+fn parse_kline(row: &[Value]) -> Result<Candle> {
+    let field = |index: usize| -> Result<Decimal> {
+        row.get(index)
+            .and_then(Value::as_str)
+            .ok_or_else(|| PriceError::ResponseError(format!("Malformed kline: {:?}", row)))
+            .and_then(parse_decimal)
+    };
+    let open_time = row
+        .first()
+        .and_then(Value::as_i64)
+        .ok_or_else(|| PriceError::ResponseError(format!("Malformed kline: {:?}", row)))?;
+    let open_time = OffsetDateTime::from_unix_timestamp_nanos(open_time as i128 * 1_000_000)
+        .map_err(|err| PriceError::ResponseError(err.to_string()))?;
+
+    Ok(Candle {
+        date: open_time.to_offset(TimeZone::Beijing.offset()).date(),
+        open: field(1)?,
+        high: field(2)?,
+        low: field(3)?,
+        close: field(4)?,
+        volume: field(5)?,
+        amount: field(7)?,
+    })
+}
+
+#[async_trait]
+impl PriceSource for RestPriceSource {
+    fn name(&self) -> &str {
+        "rest"
+    }
+
+    async fn ticker(&self, symbol: &str) -> Result<Ticker> {
+        let url = format!("{}/api/v3/ticker/24hr", self.api_url);
+        let resp: Ticker24hr = self
+            .client
+            .get(url)
+            .query(&[("symbol", Self::market_symbol(symbol))])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(Ticker {
+            price: parse_decimal(&resp.last_price)?,
+            price_change: parse_decimal(&resp.price_change)?,
+            price_change_percent: parse_decimal(&resp.price_change_percent)?,
+            high_24h: parse_decimal(&resp.high_price)?,
+            low_24h: parse_decimal(&resp.low_price)?,
+            volume_24h: parse_decimal(&resp.volume)?,
+        })
+    }
+
+    async fn daily_candles(&self, symbol: &str, from: Date, to: Date) -> Result<Vec<Candle>> {
+        let offset = TimeZone::Beijing.offset();
+        let start = from.midnight().assume_offset(offset).unix_timestamp() * 1000;
+        let end = to.next_day().unwrap_or(to).midnight().assume_offset(offset).unix_timestamp()
+            * 1000
+            - 1;
+        let url = format!("{}/api/v3/klines", self.api_url);
+        // timeZone=8 使日K线按北京时间切分，与结算日一致
+        let rows: Vec<Vec<Value>> = self
+            .client
+            .get(url)
+            .query(&[
+                ("symbol", Self::market_symbol(symbol)),
+                ("interval", "1d".to_string()),
+                ("timeZone", "8".to_string()),
+                ("startTime", start.to_string()),
+                ("endTime", end.to_string()),
+                ("limit", KLINE_LIMIT.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        rows.iter()
+            .map(|row| parse_kline(row))
+            .filter(|candle| !matches!(candle, Ok(c) if c.date < from || c.date > to))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use time::Month;
+
+    #[test]
+    fn test_parse_kline() {
+        // 2024-06-01 00:00:00 +08:00
+        let row = json!([
+            1717171200000i64,
+            "0.50",
+            "0.56",
+            "0.48",
+            "0.55",
+            "1000",
+            1717257599999i64,
+            "530.5",
+            12
+        ]);
+        let candle = parse_kline(row.as_array().unwrap()).unwrap();
+        assert_eq!(
+            candle.date,
+            Date::from_calendar_date(2024, Month::June, 1).unwrap()
+        );
+        assert_eq!(candle.close, Decimal::new(55, 2));
+        assert_eq!(candle.amount, Decimal::new(5305, 1));
+        assert!(parse_kline(json!([1717171200000i64, 1]).as_array().unwrap()).is_err());
+    }
+}
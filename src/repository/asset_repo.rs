@@ -1,11 +1,212 @@
-use crate::{error::Result, state::AppState};
+use crate::error::Result;
+use crate::model::wallet::{UserAsset, Wallet};
+use crate::utils::money::{Currency, Money};
+use rust_decimal::Decimal;
+use sqlx::{MySql, MySqlConnection, Pool};
 
+/// 用户分币种资产仓库（user_assets）：balance 为可用余额，frozen_balance 为冻结余额。
+/// 余额变动只能经由 TransactionsRepo 记账时调用，扣减类操作以条件更新保证余额不为负
 pub struct AssetRepo;
 
 impl AssetRepo {
-    pub async fn simple_method(_state: &AppState) -> Result<()> {
-        // 暂时空实现，避免SQLX编译错误
-        // TODO: 实现真正的数据库操作
+    /// 获取用户所有币种的资产账户
+    pub async fn get_user_assets(pool: &Pool<MySql>, user_id: u64) -> Result<Vec<UserAsset>> {
+        let assets = sqlx::query_as!(
+            UserAsset,
+            r#"
+            SELECT user_id, currency as "currency: String", balance, frozen_balance, total_earned,
+                   updated_at
+            FROM user_assets
+            WHERE user_id = ?
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(assets)
+    }
+
+    /// 获取已开户用户中 ID 大于 after_user_id 的前 limit 个用户 ID，用于分批对账
+    pub async fn get_user_ids_after(
+        pool: &Pool<MySql>,
+        after_user_id: u64,
+        limit: u32,
+    ) -> Result<Vec<u64>> {
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT user_id FROM user_assets
+            WHERE user_id > ?
+            ORDER BY user_id
+            LIMIT ?
+            "#,
+            after_user_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// 在事务中批量获取用户 ID 区间内（含两端）的资产账户
+    pub async fn tx_get_assets_in_range(
+        tx: &mut MySqlConnection,
+        from_user_id: u64,
+        to_user_id: u64,
+    ) -> Result<Vec<UserAsset>> {
+        let assets = sqlx::query_as!(
+            UserAsset,
+            r#"
+            SELECT user_id, currency as "currency: String", balance, frozen_balance, total_earned,
+                   updated_at
+            FROM user_assets
+            WHERE user_id BETWEEN ? AND ?
+            "#,
+            from_user_id,
+            to_user_id
+        )
+        .fetch_all(tx)
+        .await?;
+
+        Ok(assets)
+    }
+
+    /// 获取用户钱包
+    pub async fn get_wallet(pool: &Pool<MySql>, user_id: u64) -> Result<Wallet> {
+        Ok(Wallet::new(Self::get_user_assets(pool, user_id).await?))
+    }
+
+    /// 在事务中锁定并读取币种可用余额，账户不存在时为 0
+    pub async fn tx_lock_available(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        currency: Currency,
+    ) -> Result<Money> {
+        let balance = sqlx::query_scalar!(
+            r#"
+            SELECT balance FROM user_assets WHERE user_id = ? AND currency = ? FOR UPDATE
+            "#,
+            user_id,
+            currency.to_string()
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(Money::new(balance.unwrap_or(Decimal::ZERO), currency))
+    }
+
+    /// 在事务中增加可用余额，币种账户不存在时自动创建
+    pub(super) async fn tx_credit(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        amount: &Money,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_assets (user_id, currency, balance)
+            VALUES (?, ?, ?)
+            ON DUPLICATE KEY UPDATE balance = balance + VALUES(balance)
+            "#,
+            user_id,
+            amount.currency().to_string(),
+            amount.amount()
+        )
+        .execute(&mut *tx)
+        .await?;
+
         Ok(())
     }
+
+    /// 在事务中增加可用余额并计入累计收益（挖矿、邀请分成、活动奖励等）
+    pub(super) async fn tx_credit_earning(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        amount: &Money,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_assets (user_id, currency, balance, total_earned)
+            VALUES (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE balance = balance + VALUES(balance),
+                                    total_earned = total_earned + VALUES(total_earned)
+            "#,
+            user_id,
+            amount.currency().to_string(),
+            amount.amount(),
+            amount.amount()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中扣减可用余额，余额不足时不扣减并返回 false
+    pub(super) async fn tx_debit(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        amount: &Money,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_assets
+            SET balance = balance - ?
+            WHERE user_id = ? AND currency = ? AND balance >= ?
+            "#,
+            amount.amount(),
+            user_id,
+            amount.currency().to_string(),
+            amount.amount()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中增加冻结余额，币种账户不存在时自动创建
+    pub(super) async fn tx_credit_frozen(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        amount: &Money,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_assets (user_id, currency, frozen_balance)
+            VALUES (?, ?, ?)
+            ON DUPLICATE KEY UPDATE frozen_balance = frozen_balance + VALUES(frozen_balance)
+            "#,
+            user_id,
+            amount.currency().to_string(),
+            amount.amount()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中扣减冻结余额，冻结余额不足时返回 false
+    pub(super) async fn tx_deduct_frozen(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        amount: &Money,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_assets
+            SET frozen_balance = frozen_balance - ?
+            WHERE user_id = ? AND currency = ? AND frozen_balance >= ?
+            "#,
+            amount.amount(),
+            user_id,
+            amount.currency().to_string(),
+            amount.amount()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
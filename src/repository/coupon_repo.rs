@@ -0,0 +1,135 @@
+use crate::error::Result;
+use crate::model::coupon::{Coupon, COUPON_USAGE_STATUS_RELEASED, COUPON_USAGE_STATUS_USED};
+use rust_decimal::Decimal;
+use sqlx::MySqlConnection;
+
+/// 优惠券仓库
+pub struct CouponRepo;
+
+impl CouponRepo {
+    /// 在事务中按优惠码获取优惠券并加行锁，防止并发超发
+    pub async fn tx_find_by_code_for_update(
+        tx: &mut MySqlConnection,
+        code: &str,
+    ) -> Result<Option<Coupon>> {
+        let coupon = sqlx::query_as!(
+            Coupon,
+            r#"
+            SELECT id, code as "code: String", discount_type, discount_value, max_discount, min_amount,
+                   total_limit, per_user_limit, used_count, starts_at, ends_at, status, created_at, updated_at
+            FROM coupons
+            WHERE code = ?
+            FOR UPDATE
+            "#,
+            code
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(coupon)
+    }
+
+    /// 优惠券是否适用于算力包（未配置适用算力包时适用于全部）
+    pub async fn tx_is_package_allowed(
+        tx: &mut MySqlConnection,
+        coupon_id: u64,
+        power_package_id: u64,
+    ) -> Result<bool> {
+        let record = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as total, COALESCE(SUM(power_package_id = ?), 0) as matched
+            FROM coupon_packages
+            WHERE coupon_id = ?
+            "#,
+            power_package_id,
+            coupon_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(record.total == 0 || record.matched > Decimal::ZERO)
+    }
+
+    /// 统计用户对优惠券的有效使用次数
+    pub async fn tx_count_user_usages(
+        tx: &mut MySqlConnection,
+        coupon_id: u64,
+        user_id: u64,
+    ) -> Result<i64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM coupon_usages WHERE coupon_id = ? AND user_id = ? AND status = ?
+            "#,
+            coupon_id,
+            user_id,
+            COUPON_USAGE_STATUS_USED
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// 在事务中记录优惠券使用并累加使用次数
+    pub async fn tx_create_usage(
+        tx: &mut MySqlConnection,
+        coupon_id: u64,
+        user_id: u64,
+        order_id: &str,
+        discount_amount: Decimal,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO coupon_usages (coupon_id, user_id, order_id, discount_amount, status)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            coupon_id,
+            user_id,
+            order_id,
+            discount_amount,
+            COUPON_USAGE_STATUS_USED
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE coupons SET used_count = used_count + 1 WHERE id = ?
+            "#,
+            coupon_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中释放订单占用的优惠券（订单取消或过期）
+    pub async fn tx_release_by_order(tx: &mut MySqlConnection, order_id: &str) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE coupon_usages SET status = ? WHERE order_id = ? AND status = ?
+            "#,
+            COUPON_USAGE_STATUS_RELEASED,
+            order_id,
+            COUPON_USAGE_STATUS_USED
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE coupons SET used_count = used_count - 1
+            WHERE id = (SELECT coupon_id FROM coupon_usages WHERE order_id = ?) AND used_count > 0
+            "#,
+            order_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+use crate::error::Result;
+use crate::model::deposit_address::{
+    DepositAddress, DEPOSIT_ADDRESS_STATUS_ALLOCATED, DEPOSIT_ADDRESS_STATUS_FREE,
+    DEPOSIT_ADDRESS_STATUS_USER,
+};
+use sqlx::{MySql, MySqlConnection, Pool};
+use time::OffsetDateTime;
+
+/// 收款地址池仓库
+pub struct DepositAddressRepo;
+
+impl DepositAddressRepo {
+    /// 在事务中锁定一个空闲且已过冷却期的地址，跳过其他事务正在分配的行
+    pub async fn tx_lock_free_address(
+        tx: &mut MySqlConnection,
+        chain: &str,
+        released_before: OffsetDateTime,
+    ) -> Result<Option<DepositAddress>> {
+        let address = sqlx::query_as!(
+            DepositAddress,
+            r#"
+            SELECT id, chain as "chain: String", address as "address: String", derivation_index,
+                   status, user_id, order_id as "order_id: String", allocated_at, released_at,
+                   created_at, updated_at
+            FROM deposit_addresses
+            WHERE chain = ? AND status = ? AND (released_at IS NULL OR released_at <= ?)
+            ORDER BY id ASC
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            chain,
+            DEPOSIT_ADDRESS_STATUS_FREE,
+            released_before
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(address)
+    }
+
+    /// 在事务中将地址分配给订单
+    pub async fn tx_allocate(
+        tx: &mut MySqlConnection,
+        id: u64,
+        user_id: u64,
+        order_id: &str,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE deposit_addresses
+            SET status = ?, user_id = ?, order_id = ?, allocated_at = ?
+            WHERE id = ?
+            "#,
+            DEPOSIT_ADDRESS_STATUS_ALLOCATED,
+            user_id,
+            order_id,
+            now,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中获取链的下一个派生序号，锁定序号索引区间以串行化并发派生
+    pub async fn tx_next_derivation_index(tx: &mut MySqlConnection, chain: &str) -> Result<u32> {
+        let max_index = sqlx::query_scalar!(
+            r#"
+            SELECT MAX(derivation_index) FROM deposit_addresses WHERE chain = ? FOR UPDATE
+            "#,
+            chain
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(max_index.map_or(0, |index| index + 1))
+    }
+
+    /// 在事务中写入新派生的地址并直接分配给订单
+    pub async fn tx_create_allocated(
+        tx: &mut MySqlConnection,
+        chain: &str,
+        address: &str,
+        derivation_index: u32,
+        user_id: u64,
+        order_id: &str,
+        now: OffsetDateTime,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO deposit_addresses (
+                chain, address, derivation_index, status, user_id, order_id, allocated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            chain,
+            address,
+            derivation_index,
+            DEPOSIT_ADDRESS_STATUS_ALLOCATED,
+            user_id,
+            order_id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// 在事务中释放订单占用的地址，返回是否有地址被释放
+    pub async fn tx_release_by_order(
+        tx: &mut MySqlConnection,
+        order_id: &str,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE deposit_addresses
+            SET status = ?, released_at = ?
+            WHERE order_id = ? AND status = ?
+            "#,
+            DEPOSIT_ADDRESS_STATUS_FREE,
+            now,
+            order_id,
+            DEPOSIT_ADDRESS_STATUS_ALLOCATED
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中获取用户在指定链上绑定的充值地址
+    pub async fn tx_get_user_address(
+        tx: &mut MySqlConnection,
+        chain: &str,
+        user_id: u64,
+    ) -> Result<Option<String>> {
+        let address = sqlx::query_scalar!(
+            r#"
+            SELECT address as "address: String" FROM deposit_addresses
+            WHERE chain = ? AND user_id = ? AND status = ?
+            LIMIT 1
+            "#,
+            chain,
+            user_id,
+            DEPOSIT_ADDRESS_STATUS_USER
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(address)
+    }
+
+    /// 在事务中将地址池中的地址长期绑定给用户作为充值地址
+    pub async fn tx_bind_user(
+        tx: &mut MySqlConnection,
+        id: u64,
+        user_id: u64,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE deposit_addresses
+            SET status = ?, user_id = ?, order_id = NULL, allocated_at = ?
+            WHERE id = ?
+            "#,
+            DEPOSIT_ADDRESS_STATUS_USER,
+            user_id,
+            now,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中写入新派生的地址并直接绑定给用户作为充值地址
+    pub async fn tx_create_user_bound(
+        tx: &mut MySqlConnection,
+        chain: &str,
+        address: &str,
+        derivation_index: u32,
+        user_id: u64,
+        now: OffsetDateTime,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO deposit_addresses (
+                chain, address, derivation_index, status, user_id, allocated_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            chain,
+            address,
+            derivation_index,
+            DEPOSIT_ADDRESS_STATUS_USER,
+            user_id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// 按 ID 分批获取指定链上的用户充值地址
+    pub async fn get_user_addresses(
+        pool: &Pool<MySql>,
+        chain: &str,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<DepositAddress>> {
+        let addresses = sqlx::query_as!(
+            DepositAddress,
+            r#"
+            SELECT id, chain as "chain: String", address as "address: String", derivation_index,
+                   status, user_id, order_id as "order_id: String", allocated_at, released_at,
+                   created_at, updated_at
+            FROM deposit_addresses
+            WHERE chain = ? AND status = ? AND id > ?
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+            chain,
+            DEPOSIT_ADDRESS_STATUS_USER,
+            after_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(addresses)
+    }
+
+    /// 按 ID 分批获取指定链上订单关闭后释放、仍在冷却期内的收款地址
+    pub async fn get_released_order_addresses(
+        pool: &Pool<MySql>,
+        chain: &str,
+        released_after: OffsetDateTime,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<DepositAddress>> {
+        let addresses = sqlx::query_as!(
+            DepositAddress,
+            r#"
+            SELECT id, chain as "chain: String", address as "address: String", derivation_index,
+                   status, user_id, order_id as "order_id: String", allocated_at, released_at,
+                   created_at, updated_at
+            FROM deposit_addresses
+            WHERE chain = ? AND status = ? AND released_at > ? AND order_id IS NOT NULL AND id > ?
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+            chain,
+            DEPOSIT_ADDRESS_STATUS_FREE,
+            released_after,
+            after_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(addresses)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::Result;
+use crate::model::deposit::{Deposit, DepositStatus, NewDeposit};
+use crate::utils::gen::generate_no;
+use crate::utils::time_zone::TimeZone;
+use sqlx::{MySql, MySqlConnection, Pool};
+use time::OffsetDateTime;
+
+/// 用户充值记录仓库
+pub struct DepositRepo;
+
+impl DepositRepo {
+    /// 记录扫描到的充值，同一链上同一交易到同一地址只记录一次，返回是否为新记录。
+    /// 转入订单收款地址的迟到支付记录对应的订单号
+    pub async fn insert_detected(pool: &Pool<MySql>, deposit: &NewDeposit<'_>) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO deposits (
+                deposit_id, user_id, order_id, chain, address, tx_hash, from_address, amount,
+                currency, status, detected_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            generate_no("D"),
+            deposit.user_id,
+            deposit.order_id,
+            deposit.chain,
+            deposit.address,
+            deposit.tx_hash,
+            deposit.from_address,
+            deposit.amount,
+            deposit.currency,
+            DepositStatus::Pending.to_string(),
+            TimeZone::Beijing.get_time()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 获取指定链上确认中的充值，从未检查及最久未检查的优先
+    pub async fn get_pending(pool: &Pool<MySql>, chain: &str, limit: u32) -> Result<Vec<Deposit>> {
+        let deposits = sqlx::query_as!(
+            Deposit,
+            r#"
+            SELECT id, deposit_id as "deposit_id: String", user_id,
+                   order_id as "order_id: String", chain as "chain: String",
+                   address as "address: String", tx_hash as "tx_hash: String",
+                   from_address as "from_address: String", amount, currency as "currency: String",
+                   confirmations, check_attempts, checked_at, status as "status: String",
+                   detected_at, credited_at
+            FROM deposits
+            WHERE status = ? AND chain = ?
+            ORDER BY checked_at IS NOT NULL, checked_at ASC, id ASC
+            LIMIT ?
+            "#,
+            DepositStatus::Pending.to_string(),
+            chain,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deposits)
+    }
+
+    /// 记录确认中充值的一次确认检查及最新确认数
+    pub async fn mark_checked(
+        pool: &Pool<MySql>,
+        deposit_id: &str,
+        confirmations: u32,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE deposits
+            SET confirmations = ?, check_attempts = check_attempts + 1, checked_at = ?
+            WHERE deposit_id = ? AND status = ?
+            "#,
+            confirmations,
+            now,
+            deposit_id,
+            DepositStatus::Pending.to_string()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 将超过确认检查次数的充值标记为过期，仅当仍处于确认中时生效，返回是否更新成功
+    pub async fn expire(
+        pool: &Pool<MySql>,
+        deposit_id: &str,
+        confirmations: u32,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE deposits
+            SET status = ?, confirmations = ?, check_attempts = check_attempts + 1, checked_at = ?
+            WHERE deposit_id = ? AND status = ?
+            "#,
+            DepositStatus::Expired.to_string(),
+            confirmations,
+            now,
+            deposit_id,
+            DepositStatus::Pending.to_string()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中将充值标记为已入账，仅当仍处于确认中时生效，返回是否更新成功
+    pub async fn tx_mark_credited(
+        tx: &mut MySqlConnection,
+        deposit_id: &str,
+        confirmations: u32,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE deposits SET status = ?, confirmations = ?, credited_at = ?
+            WHERE deposit_id = ? AND status = ?
+            "#,
+            DepositStatus::Credited.to_string(),
+            confirmations,
+            now,
+            deposit_id,
+            DepositStatus::Pending.to_string()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 分页获取用户充值记录
+    pub async fn get_user_deposits(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Deposit>, u64)> {
+        let offset = (page - 1) * limit;
+
+        let total = sqlx::query_scalar!(
+            "SELECT COUNT(*) as count FROM deposits WHERE user_id = ?",
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let deposits = sqlx::query_as!(
+            Deposit,
+            r#"
+            SELECT id, deposit_id as "deposit_id: String", user_id,
+                   order_id as "order_id: String", chain as "chain: String",
+                   address as "address: String", tx_hash as "tx_hash: String",
+                   from_address as "from_address: String", amount, currency as "currency: String",
+                   confirmations, check_attempts, checked_at, status as "status: String",
+                   detected_at, credited_at
+            FROM deposits
+            WHERE user_id = ?
+            ORDER BY detected_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok((deposits, total as u64))
+    }
+}
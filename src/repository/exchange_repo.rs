@@ -0,0 +1,84 @@
+use crate::error::Result;
+use crate::model::exchange::ExchangeQuote;
+use sqlx::{MySql, MySqlConnection, Pool};
+use time::OffsetDateTime;
+
+pub struct ExchangeRepo;
+
+impl ExchangeRepo {
+    /// 保存兑换报价
+    pub async fn create_quote(pool: &Pool<MySql>, quote: &ExchangeQuote) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO exchange_quotes (
+                quote_id, user_id, from_currency, to_currency, from_amount, fee, to_amount,
+                exchange_rate, market_price, expires_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            quote.quote_id,
+            quote.user_id,
+            quote.from_currency,
+            quote.to_currency,
+            quote.from_amount,
+            quote.fee,
+            quote.to_amount,
+            quote.exchange_rate,
+            quote.market_price,
+            quote.expires_at,
+            quote.created_at
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// 在事务中锁定用户的报价
+    pub async fn tx_lock_quote(
+        tx: &mut MySqlConnection,
+        quote_id: &str,
+        user_id: u64,
+    ) -> Result<Option<ExchangeQuote>> {
+        let quote = sqlx::query_as!(
+            ExchangeQuote,
+            r#"
+            SELECT id, quote_id as "quote_id: String", user_id,
+                   from_currency as "from_currency: String", to_currency as "to_currency: String",
+                   from_amount, fee, to_amount, exchange_rate, market_price, expires_at,
+                   transaction_id as "transaction_id: String", created_at
+            FROM exchange_quotes
+            WHERE quote_id = ? AND user_id = ?
+            FOR UPDATE
+            "#,
+            quote_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(quote)
+    }
+
+    /// 在事务中将未使用且未过期的报价标记为已兑换，报价已使用或已过期时返回 false
+    pub async fn tx_use_quote(
+        tx: &mut MySqlConnection,
+        quote_id: &str,
+        transaction_id: &str,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE exchange_quotes
+            SET transaction_id = ?
+            WHERE quote_id = ? AND transaction_id IS NULL AND expires_at > ?
+            "#,
+            transaction_id,
+            quote_id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
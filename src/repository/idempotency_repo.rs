@@ -0,0 +1,139 @@
+use crate::error::Result;
+use crate::model::idempotency::IdempotencyKey;
+use sqlx::{MySql, Pool};
+use time::OffsetDateTime;
+
+/// 幂等键仓库
+pub struct IdempotencyRepo;
+
+impl IdempotencyRepo {
+    /// 根据用户和幂等键获取记录
+    pub async fn find(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        idempotency_key: &str,
+    ) -> Result<Option<IdempotencyKey>> {
+        let record = sqlx::query_as!(
+            IdempotencyKey,
+            r#"
+            SELECT id, user_id, idempotency_key as "idempotency_key: String",
+                   request_method as "request_method: String", request_path as "request_path: String",
+                   request_hash as "request_hash: String", response_status,
+                   response_body as "response_body: String", expired_at, created_at, updated_at
+            FROM idempotency_keys
+            WHERE user_id = ? AND idempotency_key = ?
+            "#,
+            user_id,
+            idempotency_key
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// 占用幂等键（处理中状态），返回记录ID，键已存在时返回 None
+    pub async fn try_lock(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        idempotency_key: &str,
+        request_method: &str,
+        request_path: &str,
+        request_hash: &str,
+        expired_at: OffsetDateTime,
+    ) -> Result<Option<u64>> {
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO idempotency_keys (
+                user_id, idempotency_key, request_method, request_path, request_hash, expired_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            user_id,
+            idempotency_key,
+            request_method,
+            request_path,
+            request_hash,
+            expired_at
+        )
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(result.last_insert_id()))
+    }
+
+    /// 保存首次请求的响应
+    pub async fn save_response(
+        pool: &Pool<MySql>,
+        id: u64,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys SET response_status = ?, response_body = ? WHERE id = ?
+            "#,
+            response_status,
+            response_body,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 响应保存失败时只记录状态码，将幂等键标记为已完成，重试时不再视为处理中
+    pub async fn mark_completed(pool: &Pool<MySql>, id: u64, response_status: u16) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys SET response_status = ?, response_body = NULL WHERE id = ?
+            "#,
+            response_status,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 释放幂等键，首次请求失败（服务端错误）时允许客户端重试
+    pub async fn release(pool: &Pool<MySql>, user_id: u64, idempotency_key: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys WHERE user_id = ? AND idempotency_key = ?
+            "#,
+            user_id,
+            idempotency_key
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 删除已过期的幂等键
+    pub async fn delete_expired(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        idempotency_key: &str,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys WHERE user_id = ? AND idempotency_key = ? AND expired_at <= ?
+            "#,
+            user_id,
+            idempotency_key,
+            now
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
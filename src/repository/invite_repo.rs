@@ -1,11 +1,17 @@
 use crate::model::transactions::{OrderStatus, OrderType, Transactions, TransactionsBuilder};
-use crate::repository::UserRepo;
-use crate::utils::gen::generate_no;
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
+use crate::repository::TransactionsRepo;
+use crate::utils::money::Money;
 use crate::utils::time_zone::TimeZone;
-use crate::{error::Result, state::AppState, AppError};
+use crate::{error::Result, state::AppState};
 use rust_decimal::Decimal;
 use sqlx::MySqlConnection;
 
+/// 直接邀请人分成比例（%）
+pub const INVITE_COMMISSION_PERCENT: u32 = 10;
+/// 间接邀请人分成比例（%）
+pub const PARENT_INVITE_COMMISSION_PERCENT: u32 = 5;
+
 pub struct InviteRepo;
 
 impl InviteRepo {
@@ -15,42 +21,44 @@ impl InviteRepo {
         Ok(())
     }
 
+    /// 发放邀请分成：由奖励科目记入直接/间接邀请人可用余额，每人一笔邀请交易记录
     pub async fn invite_chain(
         tx: &mut MySqlConnection,
         invite_id: u64,
         parent_invite_id: u64,
-        amount: &Decimal,
+        amount: Money,
     ) -> Result<Vec<Transactions>> {
-        let mut invites: Vec<(u64, &Decimal)> = Vec::new();
+        let mut invites: Vec<(u64, Money)> = Vec::new();
         if invite_id > 0 {
-            let invite = (amount * Decimal::from(10)) / Decimal::from(100);
-            UserRepo::tx_update_assets(tx, invite_id, &invite, &Decimal::ZERO).await?;
-            invites.push((invite_id, amount));
+            invites.push((invite_id, amount.percent(Decimal::from(INVITE_COMMISSION_PERCENT))));
         }
         if parent_invite_id > 0 {
-            let parent_invite = (amount * Decimal::from(5)) / Decimal::from(100);
-            UserRepo::tx_update_assets(tx, parent_invite_id, &parent_invite, &Decimal::ZERO)
-                .await?;
-            invites.push((parent_invite_id, amount));
+            invites.push((
+                parent_invite_id,
+                amount.percent(Decimal::from(PARENT_INVITE_COMMISSION_PERCENT)),
+            ));
         }
 
         let mut trans: Vec<Transactions> = vec![];
         let current_time = TimeZone::Beijing.get_time();
         for (id, amount) in invites {
-            let tran = TransactionsBuilder::default()
-                .user_id(id)
-                .transaction_id(generate_no("T"))
-                .types(OrderType::Purchase.to_string())
+            if !amount.is_positive() {
+                continue;
+            }
+            let entry = JournalEntry::new(LedgerAccount::Available(id)).transfer(
+                LedgerAccount::System(SystemAccount::Rewards),
+                LedgerAccount::Available(id),
+                amount,
+            );
+            let mut builder = TransactionsBuilder::default();
+            builder
+                .types(OrderType::Referral.to_string())
                 .status(OrderStatus::Completed.to_string())
                 .created_at(current_time)
-                .updated_at(current_time)
-                .amount(amount.clone())
-                .build()
-                .map_err(|e| AppError::Internal(format!("build transaction:{}", e.to_string())))?;
-            trans.push(tran);
+                .updated_at(current_time);
+            trans.push(TransactionsRepo::tx_record(tx, &entry, builder).await?);
         }
 
-        // 暂时空实现，避免SQLX编译错误
         // TODO: 发送站内信
         Ok(trans)
     }
@@ -3,26 +3,48 @@ pub mod asset_repo;
 pub mod chart_repo;
 pub mod chat_repo;
 pub mod content_repo;
+pub mod coupon_repo;
+pub mod deposit_address_repo;
+pub mod deposit_repo;
+pub mod exchange_repo;
+pub mod idempotency_repo;
 pub mod invite_repo;
 pub mod kyc_repo;
 pub mod message_repo;
+pub mod operation_log_repo;
 pub mod order_repo;
 pub mod power_repo;
+pub mod price_repo;
+pub mod promotion_repo;
+pub mod reconciliation_repo;
+pub mod settlement_repo;
 pub mod system_config_repo;
 pub mod task_repo;
 pub mod transactions_repo;
 pub mod user_repo;
+pub mod withdrawal_repo;
 
 pub use airdrop_repo::*;
 pub use asset_repo::*;
 pub use chart_repo::*;
 pub use chat_repo::*;
 pub use content_repo::*;
+pub use coupon_repo::*;
+pub use deposit_address_repo::*;
+pub use deposit_repo::*;
+pub use exchange_repo::*;
+pub use idempotency_repo::*;
 pub use invite_repo::*;
 pub use kyc_repo::*;
 pub use message_repo::*;
+pub use operation_log_repo::*;
 pub use order_repo::*;
+pub use price_repo::*;
+pub use promotion_repo::*;
+pub use reconciliation_repo::*;
+pub use settlement_repo::*;
 pub use system_config_repo::*;
 pub use task_repo::*;
 pub use transactions_repo::*;
 pub use user_repo::*;
+pub use withdrawal_repo::*;
@@ -0,0 +1,32 @@
+use crate::error::Result;
+use crate::model::operation_log::OperationLog;
+use sqlx::MySqlConnection;
+
+/// 操作日志仓库
+pub struct OperationLogRepo;
+
+impl OperationLogRepo {
+    /// 写入操作日志，可在业务事务中调用以保证日志与操作同时生效
+    pub async fn tx_create(tx: &mut MySqlConnection, log: &OperationLog) -> Result<u64> {
+        let request_data = log.request_data.as_ref().map(|data| data.to_string());
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO operation_logs (
+                user_id, action, resource_type, resource_id, ip_address, request_data, status, error_message
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            log.user_id,
+            log.action,
+            log.resource_type,
+            log.resource_id,
+            log.ip_address,
+            request_data,
+            log.status.to_string(),
+            log.error_message
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+}
@@ -1,5 +1,7 @@
 use crate::error::Result;
-use crate::model::order::Order;
+use crate::model::order::{Order, OrderFilter, ORDER_STATUS_PAID, ORDER_STATUS_PENDING};
+use crate::model::USER_POWER_RECORD_STATUS_ACTIVE;
+use time::OffsetDateTime;
 use crate::AppError;
 use rust_decimal::Decimal;
 use sqlx::{MySql, MySqlConnection, Pool};
@@ -13,33 +15,46 @@ impl OrderRepo {
         user_id: u64,
         order_no: &str,
         power_package_id: u64,
+        promotion_package_id: Option<u64>,
         quantity: u32,
         amount: Decimal,
         asset_pay: Decimal,
         coin_pay: Decimal,
+        coupon_id: Option<u64>,
+        discount_amount: Decimal,
+        upgrade_power_id: Option<u64>,
+        upgrade_credit: Decimal,
         blockchain_type: &str,
         addr: &str,
         state: i8,
+        expired_at: OffsetDateTime,
     ) -> Result<u64> {
         // 生成支付接收地址（示例地址，实际应该从配置或服务获取）
         let result = sqlx::query!(
             r#"
             INSERT INTO orders (
-                order_id, user_id, power_package_id,
+                order_id, user_id, power_package_id, promotion_package_id,
                 quantity, amount, blockchain_type, blockchain_address,
-                status, asset_pay, coin_pay
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                status, asset_pay, coin_pay, coupon_id, discount_amount,
+                upgrade_power_id, upgrade_credit, expired_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             order_no,
             user_id,
             power_package_id,
+            promotion_package_id,
             quantity,
             amount,
             blockchain_type,
             addr,
             state,
             asset_pay,
-            coin_pay
+            coin_pay,
+            coupon_id,
+            discount_amount,
+            upgrade_power_id,
+            upgrade_credit,
+            expired_at
         )
         .execute(&mut *tx)
         .await?;
@@ -52,10 +67,11 @@ impl OrderRepo {
         let order = sqlx::query_as!(
             Order,
             r#"
-            SELECT id, order_id as "order_id: String", user_id, power_package_id, quantity,asset_pay,coin_pay,
+            SELECT id, order_id as "order_id: String", user_id, power_package_id, promotion_package_id, quantity,asset_pay,coin_pay,
+                   coupon_id, discount_amount, upgrade_power_id, upgrade_credit,
                    amount, blockchain_type as "blockchain_type: String", blockchain_address  as "blockchain_address: String",
-                   transaction_hash as "transaction_hash: String",
-                   status, created_at, updated_at
+                   transaction_hash as "transaction_hash: String", confirmations,
+                   status, expired_at, created_at, updated_at
             FROM orders
             WHERE order_id = ?
             "#,
@@ -67,6 +83,26 @@ impl OrderRepo {
         Ok(order)
     }
 
+    /// 已支付订单的实付单价（限时套餐价扣除优惠券减免后按数量均摊），订单不存在或未支付时返回 None
+    pub async fn tx_get_paid_unit_price(
+        conn: &mut MySqlConnection,
+        order_id: &str,
+    ) -> Result<Option<Decimal>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT amount, discount_amount, quantity FROM orders WHERE order_id = ? AND status = ?
+            "#,
+            order_id,
+            ORDER_STATUS_PAID
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(row
+            .filter(|row| row.quantity > 0)
+            .map(|row| (row.amount - row.discount_amount) / Decimal::from(row.quantity)))
+    }
+
     /// 根据订单ID获取订单详情
     pub async fn get_order_by_id_and_user_id(
         pool: &Pool<MySql>,
@@ -76,10 +112,11 @@ impl OrderRepo {
         let order = sqlx::query_as!(
             Order,
             r#"
-            SELECT id, order_id as "order_id: String", user_id, power_package_id, quantity,asset_pay,coin_pay,
+            SELECT id, order_id as "order_id: String", user_id, power_package_id, promotion_package_id, quantity,asset_pay,coin_pay,
+                   coupon_id, discount_amount, upgrade_power_id, upgrade_credit,
                    amount, blockchain_type as "blockchain_type: String", blockchain_address as "blockchain_address: String",
-                   transaction_hash as "transaction_hash: String",
-                   status, created_at, updated_at
+                   transaction_hash as "transaction_hash: String", confirmations,
+                   status, expired_at, created_at, updated_at
             FROM orders
             WHERE order_id = ? AND user_id = ?
             "#,
@@ -174,4 +211,270 @@ impl OrderRepo {
         .await?;
         Ok(())
     }
+
+    /// 获取用户的订单列表（分页，按创建时间倒序）
+    pub async fn get_user_orders(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        filter: &OrderFilter,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Order>, u64)> {
+        let offset = (page - 1) * limit;
+
+        // 获取总记录数
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as count FROM orders
+            WHERE user_id = ?
+              AND (? IS NULL OR status = ?)
+              AND (? IS NULL OR power_package_id = ?)
+              AND (? IS NULL OR created_at >= ?)
+              AND (? IS NULL OR created_at < ?)
+            "#,
+            user_id,
+            filter.status,
+            filter.status,
+            filter.power_package_id,
+            filter.power_package_id,
+            filter.start_time,
+            filter.start_time,
+            filter.end_time,
+            filter.end_time
+        )
+        .fetch_one(pool)
+        .await?;
+
+        // 获取分页数据
+        let orders = sqlx::query_as!(
+            Order,
+            r#"
+            SELECT id, order_id as "order_id: String", user_id, power_package_id, promotion_package_id, quantity,asset_pay,coin_pay,
+                   coupon_id, discount_amount, upgrade_power_id, upgrade_credit,
+                   amount, blockchain_type as "blockchain_type: String", blockchain_address as "blockchain_address: String",
+                   transaction_hash as "transaction_hash: String", confirmations,
+                   status, expired_at, created_at, updated_at
+            FROM orders
+            WHERE user_id = ?
+              AND (? IS NULL OR status = ?)
+              AND (? IS NULL OR power_package_id = ?)
+              AND (? IS NULL OR created_at >= ?)
+              AND (? IS NULL OR created_at < ?)
+            ORDER BY created_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            user_id,
+            filter.status,
+            filter.status,
+            filter.power_package_id,
+            filter.power_package_id,
+            filter.start_time,
+            filter.start_time,
+            filter.end_time,
+            filter.end_time,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok((orders, total as u64))
+    }
+
+    /// 获取待链上确认的订单（存在链上支付部分）
+    pub async fn get_pending_payment_orders(pool: &Pool<MySql>, limit: u32) -> Result<Vec<Order>> {
+        let orders = sqlx::query_as!(
+            Order,
+            r#"
+            SELECT id, order_id as "order_id: String", user_id, power_package_id, promotion_package_id, quantity,asset_pay,coin_pay,
+                   coupon_id, discount_amount, upgrade_power_id, upgrade_credit,
+                   amount, blockchain_type as "blockchain_type: String", blockchain_address as "blockchain_address: String",
+                   transaction_hash as "transaction_hash: String", confirmations,
+                   status, expired_at, created_at, updated_at
+            FROM orders
+            WHERE status = ? AND coin_pay > 0
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+            ORDER_STATUS_PENDING,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// 交易哈希是否已被其他订单认领
+    pub async fn exists_transaction_hash(
+        pool: &Pool<MySql>,
+        transaction_hash: &str,
+        exclude_order_id: &str,
+    ) -> Result<bool> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM orders
+            WHERE transaction_hash = ? AND order_id != ?
+            "#,
+            transaction_hash,
+            exclude_order_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// 链上交易是否已作为某个订单的支付完成结算
+    pub async fn is_paid_transaction_hash(
+        pool: &Pool<MySql>,
+        transaction_hash: &str,
+    ) -> Result<bool> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM orders
+            WHERE transaction_hash = ? AND status = ?
+            "#,
+            transaction_hash,
+            ORDER_STATUS_PAID
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// 记录待支付订单匹配到的链上交易及当前确认数
+    pub async fn update_payment_confirmations(
+        pool: &Pool<MySql>,
+        order_id: &str,
+        transaction_hash: &str,
+        confirmations: u32,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE orders
+            SET transaction_hash = ?, confirmations = ?, updated_at = ?
+            WHERE order_id = ? AND status = ?
+            "#,
+            transaction_hash,
+            confirmations,
+            chrono::Utc::now(),
+            order_id,
+            ORDER_STATUS_PENDING
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 在事务中将待支付订单标记为已支付并激活算力，订单已不是待支付状态时返回 false
+    pub async fn tx_mark_order_paid(
+        tx: &mut MySqlConnection,
+        order_id: &str,
+        transaction_hash: &str,
+        confirmations: u32,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now();
+        let result = sqlx::query!(
+            r#"
+            UPDATE orders
+            SET status = ?, transaction_hash = ?, confirmations = ?, updated_at = ?
+            WHERE order_id = ? AND status = ?
+            "#,
+            ORDER_STATUS_PAID,
+            transaction_hash,
+            confirmations,
+            now,
+            order_id,
+            ORDER_STATUS_PENDING
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE user_power SET status = ?, updated_at = ? WHERE order_id = ?
+            "#,
+            USER_POWER_RECORD_STATUS_ACTIVE,
+            now,
+            order_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(true)
+    }
+
+    /// 获取已超过过期时间的待支付订单（已匹配到链上交易、等待确认的订单除外）
+    pub async fn get_expired_pending_orders(
+        pool: &Pool<MySql>,
+        now: OffsetDateTime,
+        limit: u32,
+    ) -> Result<Vec<Order>> {
+        let orders = sqlx::query_as!(
+            Order,
+            r#"
+            SELECT id, order_id as "order_id: String", user_id, power_package_id, promotion_package_id, quantity,asset_pay,coin_pay,
+                   coupon_id, discount_amount, upgrade_power_id, upgrade_credit,
+                   amount, blockchain_type as "blockchain_type: String", blockchain_address as "blockchain_address: String",
+                   transaction_hash as "transaction_hash: String", confirmations,
+                   status, expired_at, created_at, updated_at
+            FROM orders
+            WHERE status = ? AND expired_at <= ? AND transaction_hash IS NULL
+            ORDER BY expired_at ASC
+            LIMIT ?
+            "#,
+            ORDER_STATUS_PENDING,
+            now,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// 在事务中关闭待支付订单（取消/过期）并同步算力状态，订单已不是待支付状态
+    /// 或已匹配到链上支付时返回 false
+    pub async fn tx_close_pending_order(
+        tx: &mut MySqlConnection,
+        order_id: &str,
+        new_order_status: i8,
+        new_power_status: i16,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now();
+        let result = sqlx::query!(
+            r#"
+            UPDATE orders SET status = ?, updated_at = ?
+            WHERE order_id = ? AND status = ? AND transaction_hash IS NULL
+            "#,
+            new_order_status,
+            now,
+            order_id,
+            ORDER_STATUS_PENDING
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE user_power SET status = ?, updated_at = ? WHERE order_id = ?
+            "#,
+            new_power_status,
+            now,
+            order_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(true)
+    }
 }
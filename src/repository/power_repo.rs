@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
-use crate::model::{UserPowerRecordStats, USER_POWER_RECORD_STATUS_ACTIVE, USER_POWER_RECORD_STATUS_UPGRADE,
+use crate::model::{UserPowerRecordStats, USER_POWER_RECORD_STATUS_ACTIVE, USER_POWER_RECORD_STATUS_NO_PAY,
+    USER_POWER_RECORD_STATUS_UPGRADE,
 };
 use crate::utils::time_zone::TimeZone;
 use crate::{
@@ -9,7 +10,7 @@ use crate::{
 };
 use rust_decimal::Decimal;
 use sqlx::{MySql, MySqlConnection, Pool};
-use time::OffsetDateTime;
+use time::{Date, Duration, OffsetDateTime};
 
 /// 算力记录仓库
 pub struct PowerRepo;
@@ -39,7 +40,8 @@ impl PowerRepo {
             r#"
             SELECT
                 ur.id, ur.power_package_id, order_id as "order_id: String", ur.types, ur.amount, ur.start_time,
-                ur.status, ur.earnings, pp.title, pp.lv, pp.daily_yield_percentage, pp.description
+                ur.status, ur.earnings, pp.title as "title: Vec<u8>", pp.lv, pp.daily_yield_percentage,
+                pp.description as "description: Vec<u8>"
             FROM user_power as ur
             LEFT JOIN power_packages as pp ON ur.power_package_id = pp.id
             WHERE ur.user_id = ?
@@ -64,7 +66,8 @@ impl PowerRepo {
         let record = sqlx::query_as!(
             PowerPackage,
             r#"
-            SELECT id, title, lv, daily_yield_percentage, amount, description,
+            SELECT id, title as "title: Vec<u8>", lv, daily_yield_percentage, amount, duration_days,
+                description as "description: Vec<u8>",
                 status, updated_at, sort_order, created_at, is_upgrade
             FROM power_packages
             WHERE id = ?
@@ -100,22 +103,63 @@ impl PowerRepo {
         Ok(record)
     }
 
-    /// 根据userID获取算力记录
-    pub async fn get_power_record_by_time(
+    /// 获取在交易日（北京时间）有加速记录、且当日处于生效状态的算力所属的用户，按用户 ID 分批。
+    /// 当日之后才升级的算力在当日仍然生效
+    pub async fn get_started_user_ids(
         pool: &Pool<MySql>,
-        status: i16,
-        time: &OffsetDateTime,
+        business_date: Date,
+        after_user_id: u64,
+        limit: u32,
+    ) -> Result<Vec<u64>> {
+        let (_, day_end) = Self::business_day_range(business_date);
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT a.user_id FROM user_power_accelerations a
+            JOIN user_power up ON up.id = a.user_power_id
+            WHERE a.business_date = ? AND a.user_id > ?
+              AND (up.status = ? OR (up.status = ? AND up.updated_at >= ?))
+            ORDER BY a.user_id
+            LIMIT ?
+            "#,
+            business_date,
+            after_user_id,
+            USER_POWER_RECORD_STATUS_ACTIVE,
+            USER_POWER_RECORD_STATUS_UPGRADE,
+            day_end,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// 获取用户 ID 在 [first_user_id, last_user_id] 范围内、在交易日有加速记录且当日生效的算力
+    pub async fn get_started_powers_in_range(
+        pool: &Pool<MySql>,
+        business_date: Date,
+        first_user_id: u64,
+        last_user_id: u64,
     ) -> Result<Vec<UserPower>> {
+        let (_, day_end) = Self::business_day_range(business_date);
         let record = sqlx::query_as!(
             UserPower,
             r#"
-            SELECT id, user_id, power_package_id, order_id as "order_id: String", types, amount,
-            start_time, status, earnings, created_at, updated_at, lv, daily_yield_percentage
-            FROM user_power
-            WHERE status = ? AND start_time > ? ORDER BY user_id
+            SELECT up.id, up.user_id, up.power_package_id, up.order_id as "order_id: String", up.types,
+            up.amount, up.start_time, up.status, up.earnings, up.created_at, up.updated_at, up.lv,
+            up.daily_yield_percentage
+            FROM user_power_accelerations a
+            JOIN user_power up ON up.id = a.user_power_id
+            WHERE a.business_date = ? AND a.user_id BETWEEN ? AND ?
+              AND (up.status = ? OR (up.status = ? AND up.updated_at >= ?))
+            ORDER BY up.user_id, up.id
             "#,
-            status,
-            time.date()
+            business_date,
+            first_user_id,
+            last_user_id,
+            USER_POWER_RECORD_STATUS_ACTIVE,
+            USER_POWER_RECORD_STATUS_UPGRADE,
+            day_end
         )
         .fetch_all(pool)
         .await?;
@@ -123,6 +167,28 @@ impl PowerRepo {
         Ok(record)
     }
 
+    /// 交易日（北京时间）是否有加速记录，没有记录的日期无法判断哪些算力应当结算
+    pub async fn has_accelerations(pool: &Pool<MySql>, business_date: Date) -> Result<bool> {
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM user_power_accelerations WHERE business_date = ?) as "exists: bool"
+            "#,
+            business_date
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// 交易日（北京时间）的起止时间
+    fn business_day_range(business_date: Date) -> (OffsetDateTime, OffsetDateTime) {
+        let offset = TimeZone::Beijing.offset();
+        let day_start = business_date.midnight().assume_offset(offset);
+
+        (day_start, day_start + Duration::days(1))
+    }
+
     /// 根据ID和用户id获取算力记录详情
     pub async fn get_power_record_by_id_and_user(
         pool: &Pool<MySql>,
@@ -152,13 +218,55 @@ impl PowerRepo {
         }
     }
 
+    /// 在事务中锁定用户算力记录直至事务结束
+    pub async fn tx_lock_user_power(
+        tx: &mut MySqlConnection,
+        record_id: u64,
+        user_id: u64,
+    ) -> Result<UserPower> {
+        let record = sqlx::query_as!(
+            UserPower,
+            r#"
+            SELECT id, user_id, power_package_id, order_id as "order_id: String", types, amount,
+            start_time, status, earnings, created_at, updated_at, lv, daily_yield_percentage
+            FROM user_power
+            WHERE id = ? AND user_id = ?
+            FOR UPDATE
+            "#,
+            record_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        record.ok_or_else(|| AppError::NotFound("Computing power package not found".to_string()))
+    }
+
+    /// 算力累计收益按每日收盘价折合的 USDT 价值，收盘价为每 100 DG 的 USDT 报价
+    pub async fn tx_get_earnings_value(
+        conn: &mut MySqlConnection,
+        user_power_id: u64,
+    ) -> Result<Decimal> {
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT SUM(amount * close_price) / 100 FROM user_power_record WHERE user_power_id = ?
+            "#,
+            user_power_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(total.unwrap_or(Decimal::ZERO))
+    }
+
     /// 获取所有可用的算力包
     pub async fn get_all_power_packages(pool: &Pool<MySql>) -> Result<Vec<PowerPackage>> {
         let packages = sqlx::query_as!(
             PowerPackage,
             r#"
             SELECT
-                id, title, lv, daily_yield_percentage, amount, description,
+                id, title as "title: Vec<u8>", lv, daily_yield_percentage, amount, duration_days,
+                description as "description: Vec<u8>",
                 status, updated_at, sort_order, created_at, is_upgrade
             FROM power_packages
             WHERE status = 1
@@ -204,47 +312,116 @@ impl PowerRepo {
         Ok(result.last_insert_id())
     }
 
-    /// 升级用户算力（在事务中执行）
+    /// 统计用户持有某算力包的数量（待支付+生效中）
+    pub async fn count_user_power_by_package(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        power_package_id: u64,
+    ) -> Result<i64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM user_power
+            WHERE user_id = ? AND power_package_id = ? AND status IN (?, ?)
+            "#,
+            user_id,
+            power_package_id,
+            USER_POWER_RECORD_STATUS_NO_PAY,
+            USER_POWER_RECORD_STATUS_ACTIVE
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// 升级用户算力（在事务中执行），仅生效中的算力可升级，返回是否更新成功
     pub async fn upgrade_user_power_record(
         tx: &mut MySqlConnection,
         user_id: u64,
         user_power_id: u64,
-    ) -> Result<u64> {
+    ) -> Result<bool> {
         let result = sqlx::query!(
             r#"
             UPDATE user_power
-            SET status = ?, updated_at = ? WHERE user_id = ? AND id = ?
+            SET status = ?, updated_at = ? WHERE user_id = ? AND id = ? AND status = ?
             "#,
             USER_POWER_RECORD_STATUS_UPGRADE,
             TimeZone::Beijing.get_time(),
             user_id,
-            user_power_id
+            user_power_id,
+            USER_POWER_RECORD_STATUS_ACTIVE
         )
         .execute(&mut *tx)
         .await?;
 
-        Ok(result.last_insert_id())
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// 在事务中将升级中的旧算力恢复为生效状态（升级订单取消或过期），返回是否恢复成功
+    pub async fn tx_restore_upgraded_power(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        user_power_id: u64,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_power
+            SET status = ?, updated_at = ? WHERE user_id = ? AND id = ? AND status = ?
+            "#,
+            USER_POWER_RECORD_STATUS_ACTIVE,
+            TimeZone::Beijing.get_time(),
+            user_id,
+            user_power_id,
+            USER_POWER_RECORD_STATUS_UPGRADE
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
     }
 
-    /// 算力加速
+    /// 算力加速（在事务中执行），仅生效中的算力可加速，返回是否更新成功
     pub async fn start_user_power_record(
-        pool: &Pool<MySql>,
+        tx: &mut MySqlConnection,
         user_id: u64,
         user_power_id: u64,
-    ) -> Result<u64> {
+        now: OffsetDateTime,
+    ) -> Result<bool> {
         let result = sqlx::query!(
             r#"
             UPDATE user_power SET start_time = ? WHERE user_id = ? AND id = ? AND status = ?
             "#,
-            TimeZone::Beijing.get_time(),
+            now,
             user_id,
             user_power_id,
             USER_POWER_RECORD_STATUS_ACTIVE
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected())
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// 在事务中追加算力加速记录，同一算力同一交易日只记录一次
+    pub async fn tx_record_acceleration(
+        tx: &mut MySqlConnection,
+        user_id: u64,
+        user_power_id: u64,
+        business_date: Date,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT IGNORE INTO user_power_accelerations (user_id, user_power_id, business_date)
+            VALUES (?, ?, ?)
+            "#,
+            user_id,
+            user_power_id,
+            business_date
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
     }
 
     /// 获取当日开启开速的算力
@@ -298,7 +475,7 @@ impl PowerRepo {
         let record = sqlx::query_as!(
             UserPowerRecordStats,
             r#"
-            SELECT upr.id, upr.created_at, upr.user_id, upr.power_package_id, upr.amount, upr.package_amount, upr.close_price, upr.daily_yield_percentage, upr.lv, upr.user_power_id, pp.title
+            SELECT upr.id, upr.created_at, upr.user_id, upr.power_package_id, upr.amount, upr.package_amount, upr.close_price, upr.daily_yield_percentage, upr.lv, upr.user_power_id, pp.title as "title: Vec<u8>"
             FROM user_power_record upr
             LEFT JOIN power_packages pp on pp.id = upr.power_package_id
             WHERE upr.created_at >= ? AND upr.created_at <= ? AND upr.user_id = ?
@@ -312,4 +489,56 @@ impl PowerRepo {
 
         Ok(record)
     }
+
+    /// 在事务中写入算力单日收益记录，同一算力同一日已有记录时（唯一键 user_power_id + record_date）不写入并返回 false
+    pub async fn tx_insert_power_earning(
+        tx: &mut MySqlConnection,
+        power: &UserPower,
+        close_price: &Decimal,
+        amount: &Decimal,
+        record_date: Date,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO user_power_record (
+                user_id, user_power_id, power_package_id, lv, daily_yield_percentage, close_price,
+                package_amount, amount, record_date, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            power.user_id,
+            power.id,
+            power.power_package_id,
+            power.lv,
+            power.daily_yield_percentage,
+            close_price,
+            power.amount,
+            amount,
+            record_date,
+            record_date
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// 在事务中累加算力的累计收益
+    pub async fn tx_add_earnings(
+        tx: &mut MySqlConnection,
+        user_power_id: u64,
+        amount: &Decimal,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE user_power SET earnings = earnings + ?, updated_at = ? WHERE id = ?
+            "#,
+            amount,
+            TimeZone::Beijing.get_time(),
+            user_power_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,135 @@
+use crate::error::Result;
+use crate::model::price::{day_timestamp, RealTimePrice, PRICE_INTERVAL_DAY};
+use crate::price::{Candle, Ticker};
+use crate::utils::time_zone::TimeZone;
+use rust_decimal::Decimal;
+use sqlx::{MySql, Pool, QueryBuilder};
+use time::Date;
+
+pub struct PriceRepo;
+
+impl PriceRepo {
+    /// 获取交易对的实时价格
+    pub async fn get_real_time_price(
+        pool: &Pool<MySql>,
+        symbol: &str,
+    ) -> Result<Option<RealTimePrice>> {
+        let price = sqlx::query_as!(
+            RealTimePrice,
+            r#"
+            SELECT symbol as "symbol: String", current_price, last_updated_at
+            FROM real_time_prices
+            WHERE symbol = ?
+            "#,
+            symbol
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(price)
+    }
+
+    /// 写入交易对的实时价格，已存在时覆盖
+    pub async fn upsert_real_time_price(
+        pool: &Pool<MySql>,
+        symbol: &str,
+        ticker: &Ticker,
+    ) -> Result<()> {
+        let curr_time = TimeZone::Beijing.get_time();
+        sqlx::query!(
+            r#"
+            INSERT INTO real_time_prices (
+                symbol, current_price, price_change, price_change_percent, high_24h, low_24h,
+                volume_24h, last_updated_at, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE current_price = VALUES(current_price),
+                                    price_change = VALUES(price_change),
+                                    price_change_percent = VALUES(price_change_percent),
+                                    high_24h = VALUES(high_24h),
+                                    low_24h = VALUES(low_24h),
+                                    volume_24h = VALUES(volume_24h),
+                                    last_updated_at = VALUES(last_updated_at)
+            "#,
+            symbol,
+            ticker.price,
+            ticker.price_change,
+            ticker.price_change_percent,
+            ticker.high_24h,
+            ticker.low_24h,
+            ticker.volume_24h,
+            curr_time,
+            curr_time
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 写入日K线，同一交易日已存在时覆盖（当日K线在收盘前会持续变化）
+    pub async fn upsert_daily_candles(
+        pool: &Pool<MySql>,
+        symbol: &str,
+        candles: &[Candle],
+    ) -> Result<()> {
+        if candles.is_empty() {
+            return Ok(());
+        }
+        let curr_time = TimeZone::Beijing.get_time();
+        let mut qb = QueryBuilder::<MySql>::new(
+            r#"
+            INSERT INTO price_data (
+                symbol, `timestamp`, open_price, high_price, low_price, close_price, volume,
+                amount, `interval`, created_at
+            )
+            "#,
+        );
+        qb.push_values(candles, |mut b, candle| {
+            b.push_bind(symbol)
+                .push_bind(day_timestamp(candle.date))
+                .push_bind(candle.open)
+                .push_bind(candle.high)
+                .push_bind(candle.low)
+                .push_bind(candle.close)
+                .push_bind(candle.volume)
+                .push_bind(candle.amount)
+                .push_bind(PRICE_INTERVAL_DAY)
+                .push_bind(curr_time);
+        });
+        qb.push(
+            r#"
+            ON DUPLICATE KEY UPDATE open_price = VALUES(open_price),
+                                    high_price = VALUES(high_price),
+                                    low_price = VALUES(low_price),
+                                    close_price = VALUES(close_price),
+                                    volume = VALUES(volume),
+                                    amount = VALUES(amount)
+            "#,
+        );
+        qb.build().execute(pool).await?;
+
+        Ok(())
+    }
+
+    /// 获取交易对某个交易日（北京时间）的收盘价
+    pub async fn get_daily_close(
+        pool: &Pool<MySql>,
+        symbol: &str,
+        date: Date,
+    ) -> Result<Option<Decimal>> {
+        let close = sqlx::query_scalar!(
+            r#"
+            SELECT close_price FROM price_data
+            WHERE symbol = ? AND `timestamp` = ? AND `interval` = ?
+            "#,
+            symbol,
+            day_timestamp(date),
+            PRICE_INTERVAL_DAY
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(close)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::Result;
+use crate::model::PromotionPackage;
+use sqlx::{MySqlConnection, MySqlPool};
+use time::OffsetDateTime;
+
+/// 限时优惠套餐仓库
+pub struct PromotionRepo;
+
+impl PromotionRepo {
+    /// 获取未下架且未结束的限时优惠套餐（含未开始的预告套餐）
+    pub async fn get_active_packages(
+        pool: &MySqlPool,
+        now: OffsetDateTime,
+    ) -> Result<Vec<PromotionPackage>> {
+        let packages = sqlx::query_as!(
+            PromotionPackage,
+            r#"
+            SELECT id, power_package_id, name as "name: String", price, original_price,
+                   currency as "currency: String", description as "description: String",
+                   profit_percentage, duration_days, features as "features: serde_json::Value",
+                   start_time, end_time, stock, sold, is_available as "is_available: bool",
+                   created_at, updated_at
+            FROM promotion_packages
+            WHERE is_available = 1 AND end_time > ?
+            ORDER BY start_time ASC, id ASC
+            "#,
+            now
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(packages)
+    }
+
+    /// 在事务中获取限时优惠套餐
+    pub async fn tx_get_package(
+        tx: &mut MySqlConnection,
+        id: u64,
+    ) -> Result<Option<PromotionPackage>> {
+        let package = sqlx::query_as!(
+            PromotionPackage,
+            r#"
+            SELECT id, power_package_id, name as "name: String", price, original_price,
+                   currency as "currency: String", description as "description: String",
+                   profit_percentage, duration_days, features as "features: serde_json::Value",
+                   start_time, end_time, stock, sold, is_available as "is_available: bool",
+                   created_at, updated_at
+            FROM promotion_packages
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(package)
+    }
+
+    /// 在事务中预占库存：单条条件更新保证并发下不会超卖，
+    /// 套餐不可用、不在售卖时间内或库存不足时返回 false
+    pub async fn tx_reserve_stock(
+        tx: &mut MySqlConnection,
+        id: u64,
+        quantity: u32,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE promotion_packages
+            SET sold = sold + ?
+            WHERE id = ? AND is_available = 1 AND start_time <= ? AND end_time > ?
+              AND (stock = 0 OR sold + ? <= stock)
+            "#,
+            quantity,
+            id,
+            now,
+            now,
+            quantity
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中回补库存（订单取消或过期）
+    pub async fn tx_release_stock(tx: &mut MySqlConnection, id: u64, quantity: u32) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE promotion_packages
+            SET sold = IF(sold >= ?, sold - ?, 0)
+            WHERE id = ?
+            "#,
+            quantity,
+            quantity,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+}
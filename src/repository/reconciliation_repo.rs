@@ -0,0 +1,102 @@
+use crate::error::Result;
+use crate::model::ledger::LedgerDrift;
+use crate::model::reconciliation::ReconciliationReport;
+use crate::utils::time_zone::TimeZone;
+use sqlx::{MySql, Pool, QueryBuilder};
+use time::Date;
+
+pub struct ReconciliationRepo;
+
+impl ReconciliationRepo {
+    /// 写入用户当日对账差异，同一日重复对账时覆盖上次结果
+    pub async fn save_reports(
+        pool: &Pool<MySql>,
+        run_date: Date,
+        user_id: u64,
+        drifts: &[LedgerDrift],
+        account_frozen: bool,
+    ) -> Result<()> {
+        if drifts.is_empty() {
+            return Ok(());
+        }
+        let curr_time = TimeZone::Beijing.get_time();
+        let mut qb = QueryBuilder::<MySql>::new(
+            r#"
+            INSERT INTO reconciliation_reports (
+                run_date, user_id, account, currency, ledger_balance, wallet_balance, drift,
+                account_frozen, created_at
+            )
+            "#,
+        );
+        qb.push_values(drifts, |mut b, drift| {
+            b.push_bind(run_date)
+                .push_bind(user_id)
+                .push_bind(&drift.account)
+                .push_bind(&drift.currency)
+                .push_bind(drift.ledger)
+                .push_bind(drift.wallet)
+                .push_bind(drift.wallet - drift.ledger)
+                .push_bind(account_frozen)
+                .push_bind(curr_time);
+        });
+        qb.push(
+            r#"
+            ON DUPLICATE KEY UPDATE ledger_balance = VALUES(ledger_balance),
+                                    wallet_balance = VALUES(wallet_balance),
+                                    drift = VALUES(drift),
+                                    account_frozen = VALUES(account_frozen),
+                                    created_at = VALUES(created_at)
+            "#,
+        );
+        qb.build().execute(pool).await?;
+
+        Ok(())
+    }
+
+    /// 分页获取对账差异报告，可按对账日期与用户过滤
+    pub async fn get_reports(
+        pool: &Pool<MySql>,
+        run_date: Option<Date>,
+        user_id: Option<u64>,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<ReconciliationReport>, u64)> {
+        let offset = (page - 1) * limit;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as count FROM reconciliation_reports
+            WHERE (? IS NULL OR run_date = ?) AND (? IS NULL OR user_id = ?)
+            "#,
+            run_date,
+            run_date,
+            user_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let reports = sqlx::query_as!(
+            ReconciliationReport,
+            r#"
+            SELECT id, run_date, user_id, account as "account: String",
+                   currency as "currency: String", ledger_balance, wallet_balance, drift,
+                   account_frozen as "account_frozen: bool", created_at
+            FROM reconciliation_reports
+            WHERE (? IS NULL OR run_date = ?) AND (? IS NULL OR user_id = ?)
+            ORDER BY run_date DESC, ABS(drift) DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            run_date,
+            run_date,
+            user_id,
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok((reports, total as u64))
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::Result;
+use crate::model::settlement::{SettlementRun, SettlementRunStatus};
+use crate::utils::time_zone::TimeZone;
+use rust_decimal::Decimal;
+use sqlx::{MySql, MySqlConnection, Pool};
+use time::{Date, OffsetDateTime};
+
+/// 每日收益结算批次仓库
+pub struct SettlementRepo;
+
+impl SettlementRepo {
+    /// 获取交易日的结算批次
+    pub async fn get_run(
+        pool: &Pool<MySql>,
+        business_date: Date,
+    ) -> Result<Option<SettlementRun>> {
+        let run = sqlx::query_as!(
+            SettlementRun,
+            r#"
+            SELECT id, business_date, status as "status: String", close_price, checkpoint_user_id,
+                   users, records, credited, error as "error: String", started_at, finished_at,
+                   updated_at
+            FROM settlement_runs
+            WHERE business_date = ?
+            "#,
+            business_date
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    /// 创建结算中的批次并锁定收盘价，交易日已有批次时不创建并返回 false
+    pub async fn create_run(
+        pool: &Pool<MySql>,
+        business_date: Date,
+        close_price: Decimal,
+    ) -> Result<bool> {
+        let curr_time = TimeZone::Beijing.get_time();
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO settlement_runs (
+                business_date, status, close_price, started_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?)
+            "#,
+            business_date,
+            SettlementRunStatus::Running.to_string(),
+            close_price,
+            curr_time,
+            curr_time
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 接管失败的批次，或 stale_before 之后再无进展（进程中断）的结算中批次，返回是否接管成功
+    pub async fn claim_run(
+        pool: &Pool<MySql>,
+        business_date: Date,
+        stale_before: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE settlement_runs SET status = ?, updated_at = ?
+            WHERE business_date = ? AND (status = ? OR (status = ? AND updated_at < ?))
+            "#,
+            SettlementRunStatus::Running.to_string(),
+            TimeZone::Beijing.get_time(),
+            business_date,
+            SettlementRunStatus::Failed.to_string(),
+            SettlementRunStatus::Running.to_string(),
+            stale_before
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在用户入账的事务中推进断点并累计结算数据，断点已越过该用户时返回 false
+    pub async fn tx_checkpoint(
+        tx: &mut MySqlConnection,
+        business_date: Date,
+        user_id: u64,
+        records: u32,
+        credited: Decimal,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE settlement_runs
+            SET checkpoint_user_id = ?, users = users + 1, records = records + ?,
+                credited = credited + ?, updated_at = ?
+            WHERE business_date = ? AND status = ? AND checkpoint_user_id < ?
+            "#,
+            user_id,
+            records,
+            credited,
+            TimeZone::Beijing.get_time(),
+            business_date,
+            SettlementRunStatus::Running.to_string(),
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 结束批次：completed 记录完成时间，failed 记录失败原因
+    pub async fn finish_run(
+        pool: &Pool<MySql>,
+        business_date: Date,
+        status: SettlementRunStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let curr_time = TimeZone::Beijing.get_time();
+        let finished_at = (status == SettlementRunStatus::Completed).then_some(curr_time);
+        sqlx::query!(
+            r#"
+            UPDATE settlement_runs SET status = ?, error = ?, finished_at = ?, updated_at = ?
+            WHERE business_date = ? AND status = ?
+            "#,
+            status.to_string(),
+            error,
+            finished_at,
+            curr_time,
+            business_date,
+            SettlementRunStatus::Running.to_string()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,15 +1,212 @@
-use crate::model::transactions::{OrderStatus, OrderType};
+use crate::model::ledger::{
+    Direction, JournalEntry, LedgerAccount, LedgerBalance, SystemAccount, Transfer,
+    ACCOUNT_AVAILABLE, ACCOUNT_FROZEN,
+};
+use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
+use crate::repository::{AssetRepo, UserRepo};
 use crate::utils::time_zone::TimeZone;
-use crate::{error::Result, model::transactions::Transactions};
+use crate::{error::Result, model::transactions::Transactions, AppError};
 use sqlx::QueryBuilder;
 use sqlx::{MySqlConnection, MySqlPool};
 use time::OffsetDateTime;
 
+/// 交易记录与复式记账仓库：用户余额只能通过 tx_record / tx_post 随凭证一起变动
 pub struct TransactionsRepo;
 
 impl TransactionsRepo {
+    /// 在事务中记账并生成交易记录。交易的用户、金额与币种取自凭证的 owner 科目净变动，
+    /// 不能单独指定，保证交易记录与实际余额变动一致
+    pub async fn tx_record(
+        tx: &mut MySqlConnection,
+        entry: &JournalEntry,
+        mut builder: TransactionsBuilder,
+    ) -> Result<Transactions> {
+        let (Some(user_id), Some(amount)) = (entry.owner().user_id(), entry.record_amount())
+        else {
+            return Err(AppError::Internal(
+                "Journal entry must belong to a user and move funds".to_string(),
+            ));
+        };
+        let tran = builder
+            .user_id(user_id)
+            .from_currency(amount.currency().to_string())
+            .amount(amount.amount())
+            .build()
+            .map_err(|e| AppError::Internal(format!("build transaction:{}", e)))?;
+        Self::tx_create(tx, &vec![tran.clone()]).await?;
+        Self::tx_post(tx, &tran.transaction_id, entry).await?;
+
+        Ok(tran)
+    }
+
+    /// 在事务中生成不变动余额的交易记录（如以订单减免形式生效的升级抵扣），
+    /// 用户、金额与币种由调用方指定
+    pub async fn tx_record_memo(
+        tx: &mut MySqlConnection,
+        builder: TransactionsBuilder,
+    ) -> Result<Transactions> {
+        let tran = builder
+            .build()
+            .map_err(|e| AppError::Internal(format!("build transaction:{}", e)))?;
+        Self::tx_create(tx, &vec![tran.clone()]).await?;
+
+        Ok(tran)
+    }
+
+    /// 在事务中为已有交易记账（如提现解冻、出账完成）：校验借贷平衡，
+    /// 同步变动用户钱包余额并写入凭证与分录。用户资金因对账差异被冻结时拒绝从可用余额扣款
+    pub async fn tx_post(
+        tx: &mut MySqlConnection,
+        transaction_id: &str,
+        entry: &JournalEntry,
+    ) -> Result<()> {
+        entry
+            .validate()
+            .map_err(|e| AppError::Internal(format!("Invalid journal entry: {}", e)))?;
+        let Some(user_id) = entry.owner().user_id() else {
+            return Err(AppError::Internal(
+                "Journal entry must belong to a user".to_string(),
+            ));
+        };
+        // 提现、兑换、余额支付等都从可用余额扣款，冻结期间统一在此拦截，入账与冻结余额的结转不受影响
+        let debits_available = entry
+            .transfers()
+            .iter()
+            .any(|transfer| transfer.from == LedgerAccount::Available(user_id));
+        if debits_available && UserRepo::tx_is_funds_frozen(tx, user_id).await? {
+            return Err(AppError::Business(
+                "Account funds are frozen pending reconciliation".to_string(),
+            ));
+        }
+        for transfer in entry.transfers() {
+            Self::tx_apply(tx, transfer).await?;
+        }
+
+        let entry_id = sqlx::query!(
+            r#"
+            INSERT INTO ledger_entries (transaction_id, user_id, created_at) VALUES (?, ?, ?)
+            "#,
+            transaction_id,
+            user_id,
+            TimeZone::Beijing.get_time()
+        )
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+
+        let curr_time = TimeZone::Beijing.get_time();
+        let mut qb = QueryBuilder::<sqlx::MySql>::new(
+            r#"
+            INSERT INTO ledger_postings (
+                entry_id, account, user_id, currency, direction, amount, created_at
+            )
+            "#,
+        );
+        qb.push_values(entry.postings(), |mut b, (account, direction, amount)| {
+            b.push_bind(entry_id)
+                .push_bind(account.code())
+                .push_bind(account.user_id())
+                .push_bind(amount.currency().to_string())
+                .push_bind(direction.to_string())
+                .push_bind(amount.amount())
+                .push_bind(curr_time);
+        });
+        qb.build().execute(&mut *tx).await?;
+
+        Ok(())
+    }
+
+    /// 按划转变动用户钱包：借方扣减、贷方增加，系统科目与用户链上钱包不在平台内记余额
+    async fn tx_apply(tx: &mut MySqlConnection, transfer: &Transfer) -> Result<()> {
+        match transfer.from {
+            LedgerAccount::Available(user_id) => {
+                if !AssetRepo::tx_debit(tx, user_id, &transfer.amount).await? {
+                    return Err(AppError::Business("Insufficient balance".to_string()));
+                }
+            }
+            LedgerAccount::Frozen(user_id) => {
+                if !AssetRepo::tx_deduct_frozen(tx, user_id, &transfer.amount).await? {
+                    return Err(AppError::Internal(format!(
+                        "Frozen balance is insufficient for user {}",
+                        user_id
+                    )));
+                }
+            }
+            LedgerAccount::External(_) | LedgerAccount::System(_) => {}
+        }
+        match transfer.to {
+            LedgerAccount::Available(user_id) => {
+                if transfer.from == LedgerAccount::System(SystemAccount::Rewards) {
+                    AssetRepo::tx_credit_earning(tx, user_id, &transfer.amount).await?;
+                } else {
+                    AssetRepo::tx_credit(tx, user_id, &transfer.amount).await?;
+                }
+            }
+            LedgerAccount::Frozen(user_id) => {
+                AssetRepo::tx_credit_frozen(tx, user_id, &transfer.amount).await?;
+            }
+            LedgerAccount::External(_) | LedgerAccount::System(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// 由分录汇总用户各科目余额（贷方 - 借方），用于与 user_assets 核对
+    pub async fn get_ledger_balances(
+        pool: &MySqlPool,
+        user_id: u64,
+    ) -> Result<Vec<LedgerBalance>> {
+        let balances = sqlx::query_as!(
+            LedgerBalance,
+            r#"
+            SELECT user_id as "user_id!: u64", account as "account: String",
+                   currency as "currency: String",
+                   CAST(COALESCE(SUM(CASE WHEN direction = ? THEN amount ELSE -amount END), 0)
+                        AS DECIMAL(20, 8)) as "balance!: rust_decimal::Decimal"
+            FROM ledger_postings
+            WHERE user_id = ?
+            GROUP BY user_id, account, currency
+            "#,
+            Direction::Credit.to_string(),
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(balances)
+    }
+
+    /// 在事务中批量汇总用户 ID 区间内（含两端）各用户可用/冻结科目的账本余额，用于每日对账
+    pub async fn tx_get_ledger_balances_in_range(
+        tx: &mut MySqlConnection,
+        from_user_id: u64,
+        to_user_id: u64,
+    ) -> Result<Vec<LedgerBalance>> {
+        let balances = sqlx::query_as!(
+            LedgerBalance,
+            r#"
+            SELECT user_id as "user_id!: u64", account as "account: String",
+                   currency as "currency: String",
+                   CAST(COALESCE(SUM(CASE WHEN direction = ? THEN amount ELSE -amount END), 0)
+                        AS DECIMAL(20, 8)) as "balance!: rust_decimal::Decimal"
+            FROM ledger_postings
+            WHERE user_id BETWEEN ? AND ? AND account IN (?, ?)
+            GROUP BY user_id, account, currency
+            "#,
+            Direction::Credit.to_string(),
+            from_user_id,
+            to_user_id,
+            ACCOUNT_AVAILABLE,
+            ACCOUNT_FROZEN
+        )
+        .fetch_all(tx)
+        .await?;
+
+        Ok(balances)
+    }
+
     /// 创建新的交易记录
-    pub async fn tx_create(
+    async fn tx_create(
         pool: &mut MySqlConnection,
         transactions: &Vec<Transactions>,
     ) -> Result<u64> {
@@ -60,7 +257,7 @@ impl TransactionsRepo {
                 status as "status: String", blockchain_type as "blockchain_type: String",
                 from_address as "from_address: String", to_address as "to_address: String",
                 description as "description: String", completed_at, created_at, updated_at,
-                metadata
+                metadata as "metadata: Vec<u8>"
             FROM transactions
             WHERE id = ?
             "#,
@@ -88,7 +285,7 @@ impl TransactionsRepo {
                 status as "status: String", blockchain_type as "blockchain_type: String",
                 from_address as "from_address: String", to_address as "to_address: String",
                 description as "description: String", completed_at, created_at, updated_at,
-                metadata
+                metadata as "metadata: Vec<u8>"
             FROM transactions
             WHERE transaction_id = ?
             "#,
@@ -125,6 +322,52 @@ impl TransactionsRepo {
         Ok(result.rows_affected())
     }
 
+    /// 在事务中按交易唯一标识更新交易状态
+    pub async fn tx_update_status_by_transaction_id(
+        tx: &mut MySqlConnection,
+        transaction_id: &str,
+        status: OrderStatus,
+        completed_at: Option<OffsetDateTime>,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET status = ?, completed_at = ?, updated_at = ?
+            WHERE transaction_id = ?
+            "#,
+            status.to_string(),
+            completed_at,
+            TimeZone::Beijing.get_time(),
+            transaction_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 在事务中记录交易的链上哈希
+    pub async fn tx_update_hash_by_transaction_id(
+        tx: &mut MySqlConnection,
+        transaction_id: &str,
+        transaction_hash: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET transaction_hash = ?, updated_at = ?
+            WHERE transaction_id = ?
+            "#,
+            transaction_hash,
+            TimeZone::Beijing.get_time(),
+            transaction_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// 根据用户ID查询交易列表
     pub async fn find_by_user_id(
         pool: &MySqlPool,
@@ -145,7 +388,7 @@ impl TransactionsRepo {
                 status as "status: String", blockchain_type as "blockchain_type: String",
                 from_address as "from_address: String", to_address as "to_address: String",
                 description as "description: String", completed_at, created_at, updated_at,
-                metadata
+                metadata as "metadata: Vec<u8>"
             FROM transactions
             WHERE user_id = ?
             ORDER BY created_at DESC
@@ -182,7 +425,7 @@ impl TransactionsRepo {
                 status as "status: String", blockchain_type as "blockchain_type: String",
                 from_address as "from_address: String", to_address as "to_address: String",
                 description as "description: String", completed_at, created_at, updated_at,
-                metadata
+                metadata as "metadata: Vec<u8>"
             FROM transactions
             WHERE user_id = ? AND types = ?
             ORDER BY created_at DESC
@@ -219,7 +462,7 @@ impl TransactionsRepo {
                 status as "status: String", blockchain_type as "blockchain_type: String",
                 from_address as "from_address: String", to_address as "to_address: String",
                 description as "description: String", completed_at, created_at, updated_at,
-                metadata
+                metadata as "metadata: Vec<u8>"
             FROM transactions
             WHERE status = ?
             ORDER BY created_at DESC
@@ -247,6 +490,23 @@ impl TransactionsRepo {
         Ok(result.count as u64)
     }
 
+    /// 统计用户指定类型的交易数量
+    pub async fn count_by_user_id_and_type(
+        pool: &MySqlPool,
+        user_id: u64,
+        types: OrderType,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            "SELECT COUNT(*) as count FROM transactions WHERE user_id = ? AND types = ?",
+            user_id,
+            types.to_string()
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.count as u64)
+    }
+
     /// 统计指定状态的交易数量
     pub async fn count_by_status(pool: &MySqlPool, status: OrderStatus) -> Result<u64> {
         let result = sqlx::query!(
@@ -4,6 +4,8 @@ use sqlx::{FromRow, MySqlConnection, MySqlPool};
 
 use crate::model::user_security_questions::{PasswordResetTokens, SecurityQuestion};
 use crate::model::Assets;
+use crate::repository::AssetRepo;
+use crate::utils::money::Currency;
 use rust_decimal::Decimal;
 
 // 数据库查询结果结构体
@@ -15,8 +17,6 @@ pub struct UserLoginInfo {
     pub user_level: u8,        // tinyint unsigned
     pub is_kyc_verified: i8,   // tinyint(1)
     pub login_attempts: u8,    // tinyint unsigned
-    pub total_assets: Decimal, // decimal(20,8)
-    pub dg_amount: Decimal,
     pub locked_until: Option<time::OffsetDateTime>,
     pub last_login_at: Option<time::OffsetDateTime>,
     pub created_at: time::OffsetDateTime, // NOT NULL timestamp
@@ -50,7 +50,7 @@ impl UserRepo {
             r#"
             SELECT
                 id, username as "username: String", password_hash as "password_hash: String", user_level,
-                invite_code as "invite_code: String", parent_inviter_id, inviter_id, total_assets, dg_amount,
+                role as "role: String", invite_code as "invite_code: String", parent_inviter_id, inviter_id,
                 is_kyc_verified, has_security_questions, upgrade_progress,
                 is_active, is_locked, login_attempts, locked_until,
                 qr_code_url as "qr_code_url: String", created_at, updated_at, last_login_at
@@ -72,7 +72,7 @@ impl UserRepo {
             r#"
             SELECT
                 id, username as "username: String", password_hash as "password_hash: String", user_level,
-                invite_code as "invite_code: String", parent_inviter_id, inviter_id, total_assets, dg_amount,
+                role as "role: String", invite_code as "invite_code: String", parent_inviter_id, inviter_id,
                 is_kyc_verified, has_security_questions, upgrade_progress,
                 is_active, is_locked, login_attempts, locked_until,
                 qr_code_url as "qr_code_url: String", created_at, updated_at, last_login_at
@@ -158,13 +158,13 @@ impl UserRepo {
             r#"
             INSERT INTO users (
                 username, password_hash, user_level,
-                invite_code, inviter_id, parent_inviter_id, total_assets, dg_amount,
+                invite_code, inviter_id, parent_inviter_id,
                 is_kyc_verified, has_security_questions,
                 is_active, is_locked, login_attempts, qr_code_url,
                 created_at, updated_at
             ) VALUES (
                 ?, ?, ?,
-                ?, ?, ?, ?, ?,
+                ?, ?, ?,
                 ?, ?,
                 ?, ?, ?, ?,
                 NOW(), NOW()
@@ -176,8 +176,6 @@ impl UserRepo {
             user_invite_code,
             inviter_id,
             parent_inviter_id,
-            Decimal::ZERO,
-            Decimal::ZERO,
             false,
             false,
             true,
@@ -192,23 +190,6 @@ impl UserRepo {
         Ok(user_id)
     }
 
-    // 更新帐户资产
-    pub async fn tx_update_assets(
-        pool: &mut MySqlConnection,
-        user_id: u64,
-        assets: &Decimal,
-        de_assets: &Decimal,
-    ) -> Result<()> {
-        sqlx::query!(
-            "UPDATE users SET dg_amount = dg_amount+?, total_assets = total_assets+?, updated_at = NOW() WHERE id = ?",
-            assets, user_id, de_assets
-        )
-        .execute(pool)
-        .await?;
-
-        Ok(())
-    }
-
     // 锁定用户账户
     pub async fn lock_user(
         pool: &MySqlPool,
@@ -242,7 +223,7 @@ impl UserRepo {
         user_id: u64,
         password_hash: &str,
     ) -> Result<()> {
-        sqlx::query("UPDATE users SET password_hash = ?, updated_at = NOW() WHERE id = ?")
+        sqlx::query("UPDATE users SET password_hash = ?, password_changed_at = NOW(), updated_at = NOW() WHERE id = ?")
             .bind(password_hash)
             .bind(user_id)
             .execute(pool)
@@ -585,7 +566,7 @@ impl UserRepo {
         user_id: u64,
         password_hash: &str,
     ) -> Result<()> {
-        sqlx::query("UPDATE users SET password_hash = ?, updated_at = NOW() WHERE id = ?")
+        sqlx::query("UPDATE users SET password_hash = ?, password_changed_at = NOW(), updated_at = NOW() WHERE id = ?")
             .bind(password_hash)
             .bind(user_id)
             .execute(&mut *tx)
@@ -604,27 +585,6 @@ impl UserRepo {
         Ok(())
     }
 
-    // 在事务中更新用户资产
-    pub async fn update_user_assets_in_tx(
-        tx: &mut MySqlConnection,
-        user_id: u64,
-        total_assets_change: Decimal,
-        old_assets: Decimal,
-    ) -> Result<()> {
-        sqlx::query!(
-            r#"
-        UPDATE users SET total_assets = ?, updated_at = NOW()
-        WHERE id = ? AND total_assets = ?"#,
-            total_assets_change,
-            user_id,
-            old_assets
-        )
-        .execute(tx)
-        .await?;
-
-        Ok(())
-    }
-
     // 更新帐户资产
     pub async fn tx_update_lv(
         pool: &mut MySqlConnection,
@@ -642,19 +602,61 @@ impl UserRepo {
         Ok(())
     }
 
-    // 更新用户的安全问题设置标志
+    // 冻结或解除冻结用户资金（对账差异），冻结期间不能从可用余额扣款，返回状态是否发生变化
+    pub async fn set_funds_frozen(pool: &MySqlPool, user_id: u64, frozen: bool) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET funds_frozen = ?, updated_at = NOW() WHERE id = ? AND funds_frozen <> ?",
+            frozen,
+            user_id,
+            frozen
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 在事务中锁定用户行，用于串行化同一用户的并发操作，用户不存在时返回 false
+    pub async fn tx_lock_user(tx: &mut MySqlConnection, user_id: u64) -> Result<bool> {
+        let id = sqlx::query_scalar!("SELECT id FROM users WHERE id = ? FOR UPDATE", user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        Ok(id.is_some())
+    }
+
+    // 在事务中检查用户资金是否被冻结
+    pub async fn tx_is_funds_frozen(tx: &mut MySqlConnection, user_id: u64) -> Result<bool> {
+        let frozen = sqlx::query_scalar!(
+            r#"SELECT funds_frozen as "funds_frozen: bool" FROM users WHERE id = ?"#,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(frozen.unwrap_or(false))
+    }
+
+    // 获取用户等级进度与钱包余额
     pub async fn get_assets(pool: &MySqlPool, user_id: u64) -> Result<Assets> {
-        let asset = sqlx::query_as!(
-            Assets,
-            r#" SELECT upgrade_progress, user_level, dg_amount, dg_amount as daily_balance, total_assets FROM users WHERE id = ? "#,
+        let user = sqlx::query!(
+            r#" SELECT upgrade_progress, user_level FROM users WHERE id = ? "#,
             user_id
         )
         .fetch_optional(pool)
         .await?;
-        let Some(asset) = asset else {
+        let Some(user) = user else {
             return Err(AppError::NotFound("user assets not found".to_string()));
         };
-
-        Ok(asset)
+        let wallet = AssetRepo::get_wallet(pool, user_id).await?;
+        let dg_amount = wallet.available(Currency::Dg).amount();
+
+        Ok(Assets {
+            upgrade_progress: user.upgrade_progress,
+            dg_amount,
+            total_assets: wallet.available(Currency::Usdt).amount(),
+            daily_balance: dg_amount,
+            user_level: user.user_level,
+        })
     }
 }
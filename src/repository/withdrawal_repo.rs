@@ -0,0 +1,371 @@
+use crate::error::{AppError, Result};
+use crate::model::withdrawal::{NewWithdrawal, Withdrawal, WithdrawalReview, WithdrawalStatus};
+use sqlx::{MySql, MySqlConnection, Pool};
+use time::OffsetDateTime;
+
+/// 提现记录仓库
+pub struct WithdrawalRepo;
+
+impl WithdrawalRepo {
+    /// 在事务中创建待审核的提现记录
+    pub async fn tx_create(tx: &mut MySqlConnection, withdrawal: &NewWithdrawal<'_>) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_requests (
+                user_id, withdrawal_id, amount, fee, currency, blockchain_type, destination_address, status
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            withdrawal.user_id,
+            withdrawal.withdrawal_id,
+            withdrawal.amount,
+            withdrawal.fee,
+            withdrawal.currency,
+            withdrawal.blockchain_type,
+            withdrawal.destination_address,
+            WithdrawalStatus::Pending.to_string()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// 根据提现单号获取提现记录
+    pub async fn get_by_withdrawal_id(
+        pool: &Pool<MySql>,
+        withdrawal_id: &str,
+    ) -> Result<Option<Withdrawal>> {
+        let withdrawal = sqlx::query_as!(
+            Withdrawal,
+            r#"
+            SELECT id, user_id, withdrawal_id as "withdrawal_id: String", amount,
+                   currency as "currency: String", blockchain_type as "blockchain_type: String",
+                   destination_address as "destination_address: String", status as "status: String",
+                   fee, transaction_hash as "transaction_hash: String", confirmations,
+                   payout_attempts, next_payout_at, payout_error as "payout_error: String",
+                   payout_submitted_at, reviewer_id,
+                   rejection_reason as "rejection_reason: String", created_at, updated_at, processed_at
+            FROM withdrawal_requests
+            WHERE withdrawal_id = ?
+            "#,
+            withdrawal_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(withdrawal)
+    }
+
+    /// 获取用户的提现记录
+    pub async fn get_by_withdrawal_id_and_user_id(
+        pool: &Pool<MySql>,
+        withdrawal_id: &str,
+        user_id: u64,
+    ) -> Result<Withdrawal> {
+        let withdrawal = Self::get_by_withdrawal_id(pool, withdrawal_id).await?;
+        match withdrawal {
+            Some(withdrawal) if withdrawal.user_id == user_id => Ok(withdrawal),
+            _ => Err(AppError::NotFound("Withdrawal not found".to_string())),
+        }
+    }
+
+    /// 分页获取用户提现记录，可按状态过滤
+    pub async fn get_user_withdrawals(
+        pool: &Pool<MySql>,
+        user_id: u64,
+        status: Option<WithdrawalStatus>,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Withdrawal>, u64)> {
+        let offset = (page - 1) * limit;
+        let status = status.map(|status| status.to_string());
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as count FROM withdrawal_requests
+            WHERE user_id = ? AND (? IS NULL OR status = ?)
+            "#,
+            user_id,
+            status,
+            status
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let withdrawals = sqlx::query_as!(
+            Withdrawal,
+            r#"
+            SELECT id, user_id, withdrawal_id as "withdrawal_id: String", amount,
+                   currency as "currency: String", blockchain_type as "blockchain_type: String",
+                   destination_address as "destination_address: String", status as "status: String",
+                   fee, transaction_hash as "transaction_hash: String", confirmations,
+                   payout_attempts, next_payout_at, payout_error as "payout_error: String",
+                   payout_submitted_at, reviewer_id,
+                   rejection_reason as "rejection_reason: String", created_at, updated_at, processed_at
+            FROM withdrawal_requests
+            WHERE user_id = ? AND (? IS NULL OR status = ?)
+            ORDER BY created_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            user_id,
+            status,
+            status,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok((withdrawals, total as u64))
+    }
+
+    /// 在事务中按状态机流转提现状态，仅当当前状态为 from 时更新，
+    /// 流转到终态时记录处理完成时间；返回是否更新成功
+    pub async fn tx_update_status(
+        tx: &mut MySqlConnection,
+        withdrawal_id: &str,
+        from: WithdrawalStatus,
+        to: WithdrawalStatus,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        if !from.can_transition_to(to) {
+            return Err(AppError::Business(format!(
+                "Withdrawal cannot change from {} to {}",
+                from, to
+            )));
+        }
+        let processed_at = to.is_final().then_some(now);
+        let result = sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests
+            SET status = ?, processed_at = COALESCE(?, processed_at)
+            WHERE withdrawal_id = ? AND status = ?
+            "#,
+            to.to_string(),
+            processed_at,
+            withdrawal_id,
+            from.to_string()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 分页获取待审核（pending）或待人工复核（review）的提现及用户风险信息，按申请时间先后排序
+    pub async fn get_pending_reviews(
+        pool: &Pool<MySql>,
+        status: WithdrawalStatus,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<WithdrawalReview>, u64)> {
+        let offset = (page - 1) * limit;
+        let status = status.to_string();
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as count FROM withdrawal_requests WHERE status = ?
+            "#,
+            status
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let reviews = sqlx::query_as!(
+            WithdrawalReview,
+            r#"
+            SELECT w.withdrawal_id as "withdrawal_id: String", w.user_id, u.username as "username: String",
+                   w.amount, w.fee, w.currency as "currency: String",
+                   w.blockchain_type as "blockchain_type: String",
+                   w.destination_address as "destination_address: String", w.created_at,
+                   u.created_at as user_created_at, u.is_kyc_verified as "is_kyc_verified: bool",
+                   u.password_changed_at
+            FROM withdrawal_requests w
+            JOIN users u ON u.id = w.user_id
+            WHERE w.status = ?
+            ORDER BY w.created_at ASC, w.id ASC
+            LIMIT ? OFFSET ?
+            "#,
+            status,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok((reviews, total as u64))
+    }
+
+    /// 在事务中记录审核人及拒绝原因
+    pub async fn tx_set_review(
+        tx: &mut MySqlConnection,
+        withdrawal_id: &str,
+        reviewer_id: u64,
+        rejection_reason: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests SET reviewer_id = ?, rejection_reason = ? WHERE withdrawal_id = ?
+            "#,
+            reviewer_id,
+            rejection_reason,
+            withdrawal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取待提交出账的提现：已审核通过、尚未广播且已过退避时间
+    pub async fn get_payout_due(
+        pool: &Pool<MySql>,
+        chain: &str,
+        now: OffsetDateTime,
+        limit: u32,
+    ) -> Result<Vec<Withdrawal>> {
+        let withdrawals = sqlx::query_as!(
+            Withdrawal,
+            r#"
+            SELECT id, user_id, withdrawal_id as "withdrawal_id: String", amount,
+                   currency as "currency: String", blockchain_type as "blockchain_type: String",
+                   destination_address as "destination_address: String", status as "status: String",
+                   fee, transaction_hash as "transaction_hash: String", confirmations,
+                   payout_attempts, next_payout_at, payout_error as "payout_error: String",
+                   payout_submitted_at, reviewer_id,
+                   rejection_reason as "rejection_reason: String", created_at, updated_at, processed_at
+            FROM withdrawal_requests
+            WHERE status = ? AND blockchain_type = ? AND transaction_hash IS NULL
+              AND (next_payout_at IS NULL OR next_payout_at <= ?)
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+            WithdrawalStatus::Processing.to_string(),
+            chain,
+            now,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(withdrawals)
+    }
+
+    /// 获取已广播、等待链上确认的提现
+    pub async fn get_payout_unconfirmed(
+        pool: &Pool<MySql>,
+        chain: &str,
+        limit: u32,
+    ) -> Result<Vec<Withdrawal>> {
+        let withdrawals = sqlx::query_as!(
+            Withdrawal,
+            r#"
+            SELECT id, user_id, withdrawal_id as "withdrawal_id: String", amount,
+                   currency as "currency: String", blockchain_type as "blockchain_type: String",
+                   destination_address as "destination_address: String", status as "status: String",
+                   fee, transaction_hash as "transaction_hash: String", confirmations,
+                   payout_attempts, next_payout_at, payout_error as "payout_error: String",
+                   payout_submitted_at, reviewer_id,
+                   rejection_reason as "rejection_reason: String", created_at, updated_at, processed_at
+            FROM withdrawal_requests
+            WHERE status = ? AND blockchain_type = ? AND transaction_hash IS NOT NULL
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+            WithdrawalStatus::Processing.to_string(),
+            chain,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(withdrawals)
+    }
+
+    /// 占用一次出账尝试：尝试次数加一并把下次允许提交的时间推迟到 next_payout_at，
+    /// 仅当尝试次数仍为 attempts 时更新，防止多个实例重复提交；返回是否占用成功
+    pub async fn claim_payout(
+        pool: &Pool<MySql>,
+        withdrawal_id: &str,
+        attempts: u32,
+        next_payout_at: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests
+            SET payout_attempts = payout_attempts + 1, next_payout_at = ?
+            WHERE withdrawal_id = ? AND status = ? AND transaction_hash IS NULL
+              AND payout_attempts = ?
+            "#,
+            next_payout_at,
+            withdrawal_id,
+            WithdrawalStatus::Processing.to_string(),
+            attempts
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中记录出账交易哈希与广播时间
+    pub async fn tx_set_payout_hash(
+        tx: &mut MySqlConnection,
+        withdrawal_id: &str,
+        transaction_hash: &str,
+        submitted_at: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests
+            SET transaction_hash = ?, confirmations = 0, payout_error = NULL, payout_submitted_at = ?
+            WHERE withdrawal_id = ? AND transaction_hash IS NULL
+            "#,
+            transaction_hash,
+            submitted_at,
+            withdrawal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 在事务中记录出账失败原因
+    pub async fn tx_set_payout_error(
+        tx: &mut MySqlConnection,
+        withdrawal_id: &str,
+        error: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests SET payout_error = ? WHERE withdrawal_id = ?
+            "#,
+            error,
+            withdrawal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中更新出账确认数
+    pub async fn tx_update_confirmations(
+        tx: &mut MySqlConnection,
+        withdrawal_id: &str,
+        confirmations: u32,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE withdrawal_requests SET confirmations = ? WHERE withdrawal_id = ?
+            "#,
+            confirmations,
+            withdrawal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,13 +1,274 @@
+use crate::model::blockchain::Blockchain;
+use crate::model::deposit::Deposit;
+use crate::model::exchange::ExchangeQuote;
+use crate::model::transactions::Transactions;
+use crate::model::wallet::Wallet;
+use crate::model::withdrawal::{Withdrawal, WithdrawalReview};
+use crate::utils::address::validate_blockchain_address;
+use crate::utils::money::{Currency, MONEY_ROUNDING, MONEY_SCALE};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use validator::Validate;
 
+/// 钱包单币种余额
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBalanceItem {
+    pub currency: String,
+    pub available: Decimal,
+    pub frozen: Decimal,
+    pub total: Decimal,
+    pub total_earned: Decimal,
+}
+
+impl WalletBalanceItem {
+    pub fn new(wallet: &Wallet, currency: Currency) -> Self {
+        Self {
+            currency: currency.to_string(),
+            available: wallet.available(currency).amount(),
+            frozen: wallet.frozen(currency).amount(),
+            total: wallet.total(currency).amount(),
+            total_earned: wallet.total_earned(currency).amount(),
+        }
+    }
+}
+
+/// 兑换报价申请，fromAmount 含手续费
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeQuoteRequest {
+    #[validate(length(min = 1, max = 20, message = "Currency is required"))]
+    pub from_currency: String,
+    #[validate(length(min = 1, max = 20, message = "Currency is required"))]
+    pub to_currency: String,
+    pub from_amount: Decimal,
+}
+
+/// 按报价兑换
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRequest {
+    #[validate(length(min = 1, max = 64, message = "Quote id is required"))]
+    pub quote_id: String,
+}
+
+/// 兑换报价，有效期内按锁定汇率兑换
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeQuoteItem {
+    pub quote_id: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub from_amount: Decimal,
+    pub fee: Decimal,
+    pub to_amount: Decimal,
+    pub exchange_rate: Decimal,
+    pub market_price: Decimal,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl From<ExchangeQuote> for ExchangeQuoteItem {
+    fn from(quote: ExchangeQuote) -> Self {
+        Self {
+            quote_id: quote.quote_id,
+            from_currency: quote.from_currency,
+            to_currency: quote.to_currency,
+            from_amount: quote.from_amount,
+            fee: quote.fee,
+            to_amount: quote.to_amount,
+            exchange_rate: quote.exchange_rate,
+            market_price: quote.market_price,
+            expires_at: quote.expires_at,
+        }
+    }
+}
+
+/// 兑换记录，由兑换交易记录生成
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionRecordItem {
+    pub transaction_id: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub from_amount: Decimal,
+    pub to_amount: Decimal,
+    pub exchange_rate: Decimal,
+    pub fee: Decimal,
+    pub status: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<Transactions> for ConversionRecordItem {
+    fn from(tran: Transactions) -> Self {
+        // 交易金额为转出币种的净变动（负数，含手续费）
+        let from_amount = tran.amount.abs();
+        let to_amount = ((from_amount - tran.fee) * tran.exchange_rate)
+            .round_dp_with_strategy(MONEY_SCALE, MONEY_ROUNDING);
+        Self {
+            transaction_id: tran.transaction_id,
+            from_currency: tran.from_currency,
+            to_currency: tran.to_currency,
+            from_amount,
+            to_amount,
+            exchange_rate: tran.exchange_rate,
+            fee: tran.fee,
+            status: tran.status,
+            created_at: tran.created_at,
+        }
+    }
+}
+
+/// 支持的链（提现手续费与限额、确认数）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedBlockchainItem {
+    pub code: String,
+    pub name: String,
+    pub fee: Decimal,
+    pub min_amount: Decimal,
+    pub max_amount: Decimal,
+    pub confirmations: u64,
+    pub address_format: String,
+    pub icon: String,
+    pub confirmation_time: String,
+    pub is_available: bool,
+}
+
+impl From<Blockchain> for SupportedBlockchainItem {
+    fn from(chain: Blockchain) -> Self {
+        Self {
+            address_format: chain.address_format.to_string(),
+            code: chain.code,
+            name: chain.name,
+            fee: chain.fee,
+            min_amount: chain.min_amount,
+            max_amount: chain.max_amount,
+            confirmations: chain.confirmations,
+            icon: chain.icon,
+            confirmation_time: chain.confirmation_time,
+            is_available: chain.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepositAddressQuery {
+    #[serde(rename = "blockchainCode", alias = "blockchain")]
+    pub blockchain_code: String,
+}
+
+/// 用户充值地址，转入该地址的 USDT 达到确认数后自动入账
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAddressItem {
+    pub blockchain_code: String,
+    pub address: String,
+}
+
+/// 充值记录
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RechargeRecordItem {
+    pub deposit_id: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub blockchain_code: String,
+    pub address: String,
+    pub from_address: String,
+    pub transaction_hash: String,
+    pub confirmations: u32,
+    /// 充值状态：pending 确认中 / credited 已入账
+    pub status: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub credited_at: Option<OffsetDateTime>,
+}
+
+impl From<Deposit> for RechargeRecordItem {
+    fn from(deposit: Deposit) -> Self {
+        Self {
+            deposit_id: deposit.deposit_id,
+            amount: deposit.amount,
+            currency: deposit.currency,
+            blockchain_code: deposit.chain,
+            address: deposit.address,
+            from_address: deposit.from_address,
+            transaction_hash: deposit.tx_hash,
+            confirmations: deposit.confirmations,
+            status: deposit.status,
+            created_at: deposit.detected_at,
+            credited_at: deposit.credited_at,
+        }
+    }
+}
+
+/// 提现申请，amount 含手续费，实际到账 = amount - fee
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct WithdrawalRequest {
-    pub amount: f64,
+    pub amount: Decimal,
+    #[serde(default = "default_currency")]
     pub currency: String,
+    #[serde(rename = "blockchainCode", alias = "blockchain")]
+    #[validate(length(min = 2, message = "Chain type incorrect"))]
     pub blockchain_code: String,
+    #[validate(custom(function = "validate_blockchain_address"))]
     pub address: String,
 }
 
+fn default_currency() -> String {
+    "USDT".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WithdrawalListQuery {
+    /// 提现状态：pending/processing/completed/failed/cancelled
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalItem {
+    pub withdrawal_id: String,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub actual_amount: Decimal,
+    pub currency: String,
+    pub blockchain_code: String,
+    pub address: String,
+    pub status: String,
+    pub transaction_hash: Option<String>,
+    pub confirmations: Option<u32>,
+    pub rejection_reason: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub processed_at: Option<OffsetDateTime>,
+}
+
+impl From<Withdrawal> for WithdrawalItem {
+    fn from(withdrawal: Withdrawal) -> Self {
+        Self {
+            actual_amount: withdrawal.receive_amount(),
+            withdrawal_id: withdrawal.withdrawal_id,
+            amount: withdrawal.amount,
+            fee: withdrawal.fee,
+            currency: withdrawal.currency,
+            blockchain_code: withdrawal.blockchain_type,
+            address: withdrawal.destination_address,
+            status: withdrawal.status,
+            transaction_hash: withdrawal.transaction_hash,
+            confirmations: withdrawal.confirmations,
+            rejection_reason: withdrawal.rejection_reason,
+            created_at: withdrawal.created_at,
+            processed_at: withdrawal.processed_at,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EarningResponse {
     pub id: i64,
@@ -16,4 +277,71 @@ pub struct EarningResponse {
     pub currency: String,
     pub description: String,
     pub created_at: String,
-}
\ No newline at end of file
+}
+
+/// 待审核提现（管理后台）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalReviewItem {
+    pub withdrawal_id: String,
+    pub user_id: u64,
+    pub username: String,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub currency: String,
+    pub blockchain_code: String,
+    pub address: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    pub account_age_days: i64,
+    pub is_kyc_verified: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub password_changed_at: Option<OffsetDateTime>,
+    /// 风险提示：new_account / kyc_unverified / recent_password_change
+    pub risk_hints: Vec<String>,
+}
+
+impl WithdrawalReviewItem {
+    pub fn new(review: WithdrawalReview, now: OffsetDateTime) -> Self {
+        Self {
+            account_age_days: review.account_age_days(now),
+            risk_hints: review
+                .risk_hints(now)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            withdrawal_id: review.withdrawal_id,
+            user_id: review.user_id,
+            username: review.username,
+            amount: review.amount,
+            fee: review.fee,
+            currency: review.currency,
+            blockchain_code: review.blockchain_type,
+            address: review.destination_address,
+            created_at: review.created_at,
+            is_kyc_verified: review.is_kyc_verified,
+            password_changed_at: review.password_changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct RejectWithdrawalRequest {
+    #[validate(length(min = 1, max = 500, message = "Rejection reason is required"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct BatchApproveWithdrawalRequest {
+    #[serde(rename = "withdrawalIds")]
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 withdrawals per batch"))]
+    pub withdrawal_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReviewResult {
+    pub withdrawal_id: String,
+    pub success: bool,
+    pub message: Option<String>,
+}
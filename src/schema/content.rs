@@ -1,4 +1,7 @@
+use crate::model::PromotionPackage;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ContentResponse {
@@ -23,4 +26,60 @@ pub struct PromotionResponse {
     pub reward_amount: f64,
     pub status: String,
     pub is_participated: bool,
-}
\ No newline at end of file
+}
+
+/// 限时优惠套餐响应数据
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromotionPackageItem {
+    pub id: u64,
+    pub power_id: u64,
+    pub name: String,
+    pub original_price: Option<Decimal>,
+    pub promotion_price: Decimal,
+    /// 折扣百分比，如 25 表示减 25%
+    pub discount: Decimal,
+    pub currency: String,
+    pub description: String,
+    pub features: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub start_time: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub end_time: OffsetDateTime,
+    /// 0 表示不限库存
+    pub stock: u32,
+    pub sold: u32,
+    pub remaining: Option<u32>,
+    pub sold_out: bool,
+    /// 当前是否处于售卖时间内
+    pub on_sale: bool,
+}
+
+impl PromotionPackageItem {
+    pub fn new(package: PromotionPackage, now: OffsetDateTime) -> Self {
+        let discount = match package.original_price {
+            Some(original) if original > package.price => {
+                ((original - package.price) * Decimal::ONE_HUNDRED / original).round_dp(0)
+            }
+            _ => Decimal::ZERO,
+        };
+        Self {
+            id: package.id,
+            power_id: package.power_package_id,
+            remaining: package.remaining(),
+            sold_out: package.is_sold_out(),
+            on_sale: now >= package.start_time && now < package.end_time,
+            name: package.name,
+            original_price: package.original_price,
+            promotion_price: package.price,
+            discount,
+            currency: package.currency,
+            description: package.description,
+            features: package.features.unwrap_or_else(|| serde_json::json!([])),
+            start_time: package.start_time,
+            end_time: package.end_time,
+            stock: package.stock,
+            sold: package.sold,
+        }
+    }
+}
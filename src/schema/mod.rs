@@ -11,6 +11,8 @@ pub mod kyc;
 pub mod content;
 pub mod chat;
 pub mod system_config;
+pub mod reconciliation;
+pub mod settlement;
 pub mod common;
 
 pub use user::*;
@@ -26,4 +28,6 @@ pub use kyc::*;
 pub use content::*;
 pub use chat::*;
 pub use system_config::*;
+pub use reconciliation::*;
+pub use settlement::*;
 pub use common::*;
\ No newline at end of file
@@ -1,4 +1,7 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -9,12 +12,35 @@ pub struct CreateOrderRequest {
     #[serde(alias = "blockchainType")]
     #[validate(length(min = 2, message = "Chain type incorrect"))]
     pub blockchain_type: String,
+    /// 购买数量，默认 1
+    #[serde(default = "default_quantity")]
+    #[validate(range(min = 1, message = "Purchase quantity must be at least 1"))]
+    pub quantity: u32,
+    /// 限时优惠套餐ID，可选，需与 powerId 对应
+    #[serde(rename = "promotionId", default)]
+    pub promotion_id: Option<u64>,
+    /// 优惠码，可选
+    #[serde(rename = "couponCode", default)]
+    #[validate(length(min = 1, max = 64, message = "Coupon code incorrect"))]
+    pub coupon_code: Option<String>,
+}
+
+fn default_quantity() -> u32 {
+    1
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateOrderResponse {
     #[serde(alias = "orderNumber")]
     pub order_number: String,
+    #[serde(alias = "blockchainType")]
+    pub blockchain_type: String,
+    /// 本订单独占的收款地址，资产全额抵扣时为空
+    #[serde(alias = "depositAddress")]
+    pub deposit_address: Option<String>,
+    /// 需向收款地址转账的金额
+    #[serde(alias = "payAmount")]
+    pub pay_amount: Decimal,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -55,3 +81,128 @@ pub struct UpgradeOrderRequest {
     #[validate(length(min = 2, message = "Chain type incorrect"))]
     pub blockchain_type: String,
 }
+
+/// 升级报价查询参数
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpgradeQuoteQuery {
+    #[serde(rename = "oldUserPowerId")]
+    #[validate(range(min = 1, message = "Original computing power package cannot be empty"))]
+    pub old_user_power_id: u64,
+
+    #[serde(rename = "powerId")]
+    #[validate(range(min = 1, message = "Upgrade computing power package cannot be empty"))]
+    pub power_id: u64,
+}
+
+/// 升级报价
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpgradeQuoteResponse {
+    #[serde(rename = "oldUserPowerId")]
+    pub old_user_power_id: u64,
+    #[serde(rename = "powerId")]
+    pub power_id: u64,
+    /// 新算力包价格
+    pub amount: Decimal,
+    /// 旧算力包价格
+    #[serde(rename = "oldAmount")]
+    pub old_amount: Decimal,
+    /// 旧算力包实付金额
+    #[serde(rename = "oldPaidAmount")]
+    pub old_paid_amount: Decimal,
+    /// 旧算力包累计收益（DG）
+    #[serde(rename = "oldEarnings")]
+    pub old_earnings: Decimal,
+    /// 旧算力包累计收益按每日收盘价折合的 USDT
+    #[serde(rename = "oldEarningsValue")]
+    pub old_earnings_value: Decimal,
+    #[serde(rename = "durationDays")]
+    pub duration_days: u32,
+    #[serde(rename = "elapsedDays")]
+    pub elapsed_days: u32,
+    /// 旧算力包剩余价值抵扣
+    pub credit: Decimal,
+    #[serde(rename = "assetPay")]
+    pub asset_pay: Decimal,
+    #[serde(rename = "coinPay")]
+    pub coin_pay: Decimal,
+}
+
+/// 升级订单创建结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpgradeOrderResponse {
+    #[serde(rename = "orderNumber")]
+    pub order_number: String,
+    #[serde(rename = "blockchainType")]
+    pub blockchain_type: String,
+    /// 本订单独占的收款地址，资产全额抵扣时为空
+    #[serde(rename = "depositAddress")]
+    pub deposit_address: Option<String>,
+    /// 需向收款地址转账的金额
+    #[serde(rename = "payAmount")]
+    pub pay_amount: Decimal,
+    /// 旧算力包剩余价值抵扣
+    #[serde(rename = "upgradeCredit")]
+    pub upgrade_credit: Decimal,
+}
+
+/// 订单列表筛选参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderListQuery {
+    /// 订单状态：pending/paid/cancelled/upgrade/expired
+    pub status: Option<String>,
+    #[serde(rename = "powerId")]
+    pub power_id: Option<u64>,
+    /// 开始日期（包含），YYYY-MM-DD
+    #[serde(rename = "startDate")]
+    pub start_date: Option<NaiveDate>,
+    /// 结束日期（包含），YYYY-MM-DD
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+}
+
+/// 订单列表项
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderItem {
+    #[serde(rename = "orderNumber")]
+    pub order_number: String,
+    #[serde(rename = "powerId")]
+    pub power_id: u64,
+    /// 按用户语言本地化的算力包标题
+    #[serde(rename = "powerName")]
+    pub power_name: String,
+    pub lv: u16,
+    pub quantity: u32,
+    pub amount: Decimal,
+    /// 优惠券减免金额
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: Decimal,
+    #[serde(rename = "assetPay")]
+    pub asset_pay: Decimal,
+    #[serde(rename = "coinPay")]
+    pub coin_pay: Decimal,
+    #[serde(rename = "blockchainType")]
+    pub blockchain_type: String,
+    #[serde(rename = "blockchainAddress")]
+    pub blockchain_address: String,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: Option<String>,
+    pub confirmations: u32,
+    pub status: String,
+    #[serde(rename = "expiredAt", with = "time::serde::rfc3339")]
+    pub expired_at: OffsetDateTime,
+    #[serde(rename = "createdAt", with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// 订单链上支付确认结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaymentConfirmationResponse {
+    #[serde(rename = "orderNumber")]
+    pub order_number: String,
+    pub paid: bool,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: Option<String>,
+    pub confirmations: u32,
+    #[serde(rename = "requiredConfirmations")]
+    pub required_confirmations: u64,
+}
@@ -36,6 +36,7 @@ pub struct PowerPackageItem {
     pub lv: u16,                     // varchar(255) NOT NULL
     pub daily_yield_percentage: f64, // tinyint unsigned NOT NULL
     pub amount: f64,
+    pub duration_days: u32,
     pub description: String,
     pub is_upgrade: bool,
     pub status: i8,
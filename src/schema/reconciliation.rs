@@ -0,0 +1,47 @@
+use crate::model::reconciliation::ReconciliationReport;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReportQuery {
+    /// 对账日期，格式 YYYY-MM-DD
+    pub date: Option<String>,
+    pub user_id: Option<u64>,
+}
+
+/// 对账差异报告
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReportItem {
+    pub id: u64,
+    pub run_date: String,
+    pub user_id: u64,
+    pub account: String,
+    pub currency: String,
+    pub ledger_balance: Decimal,
+    pub wallet_balance: Decimal,
+    /// 差异 = 钱包余额 - 账本余额
+    pub drift: Decimal,
+    pub account_frozen: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<ReconciliationReport> for ReconciliationReportItem {
+    fn from(report: ReconciliationReport) -> Self {
+        Self {
+            id: report.id,
+            run_date: report.run_date.to_string(),
+            user_id: report.user_id,
+            account: report.account,
+            currency: report.currency,
+            ledger_balance: report.ledger_balance,
+            wallet_balance: report.wallet_balance,
+            drift: report.drift,
+            account_frozen: report.account_frozen,
+            created_at: report.created_at,
+        }
+    }
+}
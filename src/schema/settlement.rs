@@ -0,0 +1,46 @@
+use crate::model::settlement::SettlementReport;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// 补结算请求
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementBackfillRequest {
+    /// 起始交易日，格式 YYYY-MM-DD
+    pub start_date: String,
+    /// 结束交易日（含），格式 YYYY-MM-DD
+    pub end_date: String,
+    /// 试运行：只返回预计入账数据，不写入任何记录
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 单个交易日的结算结果
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementReportItem {
+    pub business_date: String,
+    /// running / completed / failed，试运行且尚未结算时为空
+    pub status: Option<String>,
+    /// 结算使用的 DG 收盘价（USDT）
+    pub close_price: Option<Decimal>,
+    pub users: u32,
+    pub records: u32,
+    /// 入账（试运行时为预计入账）DG 总额
+    pub credited: Decimal,
+    pub message: Option<String>,
+}
+
+impl From<SettlementReport> for SettlementReportItem {
+    fn from(report: SettlementReport) -> Self {
+        Self {
+            business_date: report.business_date.to_string(),
+            status: report.status,
+            close_price: report.close_price,
+            users: report.users,
+            records: report.records,
+            credited: report.credited,
+            message: report.message,
+        }
+    }
+}
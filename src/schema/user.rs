@@ -1,3 +1,5 @@
+use crate::model::wallet::Wallet;
+use crate::utils::money::Currency;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -56,13 +58,24 @@ pub struct UserInfoRes {
     pub invite_code: String,
 }
 
-impl From<crate::model::User> for UserInfoRes {
-    fn from(user: crate::model::User) -> Self {
+impl UserInfoRes {
+    /// total_assets 为 USDT 可用余额，dg_amount 为 DG 可用余额
+    pub fn new(user: crate::model::User, wallet: &Wallet) -> Self {
         let qr = user.qr_code_url;
         Self {
             username: user.username,
-            total_assets: user.total_assets.to_string().parse::<f64>().unwrap_or(0.0),
-            dg_amount: user.dg_amount.to_string().parse::<f64>().unwrap_or(0.0),
+            total_assets: wallet
+                .available(Currency::Usdt)
+                .amount()
+                .to_string()
+                .parse::<f64>()
+                .unwrap_or(0.0),
+            dg_amount: wallet
+                .available(Currency::Dg)
+                .amount()
+                .to_string()
+                .parse::<f64>()
+                .unwrap_or(0.0),
             user_level: user.user_level as i32,
             is_kyc_verified: user.is_kyc_verified == 1,
             is_logged_in: true, // 能到达这里说明已登录
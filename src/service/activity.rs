@@ -1,5 +1,7 @@
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
 use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
-use crate::repository::{TransactionsRepo, UserRepo};
+use crate::repository::TransactionsRepo;
+use crate::utils::money::{Currency, Money};
 use crate::utils::time_zone::TimeZone;
 use crate::{error::Result, state::AppState, AppError};
 use rust_decimal::Decimal;
@@ -30,24 +32,23 @@ impl ActivityService {
         if bonus.amount <= 0 {
             return Err(AppError::NotFound("Newcomer benefit amount setting error".to_string()));
         }
-        let amount = Decimal::from(bonus.amount);
+        // 未配置或无法识别的单位按 DG 发放
+        let currency = bonus.unit.to_uppercase().parse().unwrap_or(Currency::Dg);
+        let amount = Money::new(Decimal::from(bonus.amount), currency);
         let curr_time = TimeZone::Beijing.get_time();
-        let tran = TransactionsBuilder::default()
-            .user_id(user_id)
+        let entry = JournalEntry::new(LedgerAccount::Available(user_id)).transfer(
+            LedgerAccount::System(SystemAccount::Rewards),
+            LedgerAccount::Available(user_id),
+            amount,
+        );
+        let mut builder = TransactionsBuilder::default();
+        builder
             .types(OrderType::Welcome.to_string())
-            .amount(amount)
             .status(OrderStatus::Completed.to_string())
             .created_at(curr_time)
-            .updated_at(curr_time)
-            .build()
-            .map_err(|e| AppError::Internal(format!("build transaction:{}", e.to_string())))?;
+            .updated_at(curr_time);
         let mut tx = self.db.begin().await?;
-        let res = UserRepo::tx_update_assets(&mut *tx, user_id, &amount, &Decimal::ZERO).await;
-        if let Err(err) = res {
-            tx.rollback().await?;
-            return Err(AppError::Internal(format!("Failed to update user assets: {}", err)));
-        }
-        let res = TransactionsRepo::tx_create(&mut *tx, &vec![tran]).await;
+        let res = TransactionsRepo::tx_record(&mut tx, &entry, builder).await;
         if let Err(err) = res {
             tx.rollback().await?;
             return Err(AppError::Internal(format!("Failed to insert benefit claim record: {}", err)));
@@ -0,0 +1,61 @@
+use crate::model::blockchain::Blockchain;
+use crate::model::system_config::SystemConfigType;
+use crate::repository::SystemConfigRepo;
+use crate::{error::Result, state::AppState, AppError};
+
+/// 支持的链注册表：从 system_configs.blockchain 读取，未配置时使用默认链
+pub struct BlockchainService {
+    db: sqlx::MySqlPool,
+    defaults: Vec<Blockchain>,
+}
+
+impl BlockchainService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            defaults: Blockchain::defaults(&state.config.chain),
+        }
+    }
+
+    /// 获取所有已配置的链，包括停用的链
+    pub async fn get_blockchains(&self) -> Result<Vec<Blockchain>> {
+        let config = SystemConfigRepo::get_config_by_key(
+            &self.db,
+            &SystemConfigType::Blockchain.to_string(),
+        )
+        .await;
+        match config {
+            Ok(config) => {
+                let chains: Vec<Blockchain> = serde_json::from_str(&config.config_value)?;
+                chains.iter().try_for_each(Blockchain::validate).map_err(|e| {
+                    AppError::Internal(format!("Invalid blockchain config: {}", e))
+                })?;
+                Ok(chains)
+            }
+            Err(AppError::NotFound(_)) => Ok(self.defaults.clone()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 获取链配置，用于确认已发起的支付、充值与出账，停用的链同样返回
+    pub async fn get_blockchain(&self, code: &str) -> Result<Blockchain> {
+        self.get_blockchains()
+            .await?
+            .into_iter()
+            .find(|chain| chain.code == code)
+            .ok_or_else(|| AppError::Validation(format!("Unsupported blockchain: {}", code)))
+    }
+
+    /// 获取启用中的链配置，用于发起新订单、分配充值地址与提现
+    pub async fn get_enabled_blockchain(&self, code: &str) -> Result<Blockchain> {
+        let chain = self.get_blockchain(code).await?;
+        if !chain.enabled {
+            return Err(AppError::Business(format!(
+                "{} is currently unavailable",
+                chain.name
+            )));
+        }
+
+        Ok(chain)
+    }
+}
@@ -0,0 +1,353 @@
+use crate::chain::{ChainWatcher, ChainWatchers};
+use crate::model::deposit::{Deposit, DepositScanSummary, NewDeposit};
+use crate::model::ledger::{JournalEntry, LedgerAccount};
+use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
+use crate::repository::{DepositAddressRepo, DepositRepo, OrderRepo, TransactionsRepo};
+use crate::service::blockchain::BlockchainService;
+use crate::service::deposit_address::DepositAddressService;
+use crate::utils::money::{Currency, Money};
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use time::Duration;
+
+/// 每批扫描的充值地址数
+const DEPOSIT_SCAN_BATCH_SIZE: u32 = 500;
+/// 每轮确认处理的最大充值数
+const DEPOSIT_CONFIRM_BATCH_SIZE: u32 = 200;
+
+/// 用户充值服务：为用户绑定长期充值地址，后台扫描地址的链上入账，
+/// 达到确认数后以 recharge 交易记入用户 USDT 可用余额。
+/// 订单关闭后释放的收款地址在冷却期内同样扫描，迟到的订单支付按充值入账
+pub struct DepositService {
+    db: sqlx::MySqlPool,
+    watchers: Arc<ChainWatchers>,
+    lookback: Duration,
+    cooldown: Duration,
+    max_confirm_checks: u32,
+    blockchains: BlockchainService,
+    address_service: DepositAddressService,
+}
+
+impl DepositService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            watchers: state.chain_watchers.clone(),
+            lookback: Duration::hours(state.config.chain.deposit_lookback_hours),
+            cooldown: Duration::hours(state.config.chain.address_cooldown_hours),
+            max_confirm_checks: state.config.chain.deposit_max_confirm_checks,
+            blockchains: BlockchainService::new(state),
+            address_service: DepositAddressService::new(state),
+        }
+    }
+
+    /// 获取用户在指定链上的充值地址，首次获取时绑定
+    pub async fn get_or_assign_address(&self, user_id: u64, chain: &str) -> Result<String> {
+        self.blockchains.get_enabled_blockchain(chain).await?;
+        if self.watchers.get(chain).is_err() {
+            return Err(AppError::Business(format!(
+                "Deposits on {} are not available",
+                chain
+            )));
+        }
+        let mut tx = self.db.begin().await?;
+        let address = match self.address_service.tx_assign_user(&mut tx, chain, user_id).await {
+            Ok(address) => address,
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        };
+        tx.commit().await?;
+
+        Ok(address)
+    }
+
+    /// 扫描所有已配置且已接入的链上的用户充值地址，记录新充值并为达到确认数的充值入账。
+    /// 停用的链仍会扫描，避免停用前已绑定地址收到的转账无法入账
+    pub async fn scan_deposits(&self) -> Result<DepositScanSummary> {
+        let mut summary = DepositScanSummary::default();
+        for blockchain in self.blockchains.get_blockchains().await? {
+            let Ok(watcher) = self.watchers.get(&blockchain.code) else {
+                continue;
+            };
+            summary.detected += self.detect_deposits(watcher.as_ref()).await?;
+            let late_payments = self.detect_late_payments(watcher.as_ref()).await?;
+            summary.detected += late_payments;
+            summary.late_payments += late_payments;
+            summary.credited += self
+                .confirm_deposits(watcher.as_ref(), blockchain.confirmations)
+                .await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// 查询回溯时间内各充值地址收到的转账，返回新记录的充值数
+    async fn detect_deposits(&self, watcher: &dyn ChainWatcher) -> Result<usize> {
+        let since = TimeZone::Beijing.get_time() - self.lookback;
+        let currency = Currency::Usdt.to_string();
+        let mut detected = 0;
+        let mut after_id = 0;
+        loop {
+            let addresses = DepositAddressRepo::get_user_addresses(
+                &self.db,
+                watcher.chain(),
+                after_id,
+                DEPOSIT_SCAN_BATCH_SIZE,
+            )
+            .await?;
+            let Some(last) = addresses.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for address in addresses {
+                let Some(user_id) = address.user_id else {
+                    continue;
+                };
+                let transfers = match watcher.incoming_transfers(&address.address, since).await {
+                    Ok(transfers) => transfers,
+                    Err(err) => {
+                        tracing::error!(
+                            "Failed to scan {} deposit address {}: {}",
+                            watcher.chain(),
+                            address.address,
+                            err
+                        );
+                        continue;
+                    }
+                };
+                for transfer in transfers.into_iter().filter(|t| t.amount > Decimal::ZERO) {
+                    let deposit = NewDeposit {
+                        user_id,
+                        order_id: None,
+                        chain: watcher.chain(),
+                        address: &address.address,
+                        tx_hash: &transfer.tx_hash,
+                        from_address: &transfer.from_address,
+                        amount: transfer.amount,
+                        currency: &currency,
+                    };
+                    if DepositRepo::insert_detected(&self.db, &deposit).await?
+                    {
+                        detected += 1;
+                        tracing::info!(
+                            "Detected {} deposit {} of {} USDT for user {}",
+                            watcher.chain(),
+                            transfer.tx_hash,
+                            transfer.amount,
+                            user_id
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(detected)
+    }
+
+    /// 扫描订单关闭（支付、取消或过期）后仍在冷却期内的收款地址，订单关闭后才到账、
+    /// 少付或多付等未被已支付订单认领的转账记为下单用户的充值，返回新记录的迟到支付数
+    async fn detect_late_payments(&self, watcher: &dyn ChainWatcher) -> Result<usize> {
+        let now = TimeZone::Beijing.get_time();
+        let currency = Currency::Usdt.to_string();
+        let mut detected = 0;
+        let mut after_id = 0;
+        loop {
+            let addresses = DepositAddressRepo::get_released_order_addresses(
+                &self.db,
+                watcher.chain(),
+                now - self.cooldown,
+                after_id,
+                DEPOSIT_SCAN_BATCH_SIZE,
+            )
+            .await?;
+            let Some(last) = addresses.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for address in addresses {
+                let (Some(user_id), Some(order_id)) = (address.user_id, address.order_id) else {
+                    continue;
+                };
+                let since = address.allocated_at.unwrap_or(now - self.cooldown);
+                let transfers = match watcher.incoming_transfers(&address.address, since).await {
+                    Ok(transfers) => transfers,
+                    Err(err) => {
+                        tracing::error!(
+                            "Failed to scan {} released address {}: {}",
+                            watcher.chain(),
+                            address.address,
+                            err
+                        );
+                        continue;
+                    }
+                };
+                for transfer in transfers.into_iter().filter(|t| t.amount > Decimal::ZERO) {
+                    if OrderRepo::is_paid_transaction_hash(&self.db, &transfer.tx_hash).await? {
+                        continue;
+                    }
+                    let deposit = NewDeposit {
+                        user_id,
+                        order_id: Some(&order_id),
+                        chain: watcher.chain(),
+                        address: &address.address,
+                        tx_hash: &transfer.tx_hash,
+                        from_address: &transfer.from_address,
+                        amount: transfer.amount,
+                        currency: &currency,
+                    };
+                    if DepositRepo::insert_detected(&self.db, &deposit).await?
+                    {
+                        detected += 1;
+                        tracing::warn!(
+                            "Detected late {} payment {} of {} USDT for closed order {}, \
+                             crediting user {}",
+                            watcher.chain(),
+                            transfer.tx_hash,
+                            transfer.amount,
+                            order_id,
+                            user_id
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(detected)
+    }
+
+    /// 检查确认中充值的确认数，达到要求后入账，返回本轮入账的充值数
+    async fn confirm_deposits(&self, watcher: &dyn ChainWatcher, required: u64) -> Result<usize> {
+        let deposits =
+            DepositRepo::get_pending(&self.db, watcher.chain(), DEPOSIT_CONFIRM_BATCH_SIZE).await?;
+        let mut credited = 0;
+        for deposit in deposits {
+            let confirmations = match watcher.confirmations(&deposit.tx_hash).await {
+                Ok(confirmations) => u32::try_from(confirmations).unwrap_or(u32::MAX),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to query confirmations of deposit {}: {}",
+                        deposit.deposit_id,
+                        err
+                    );
+                    self.record_check(&deposit, deposit.confirmations).await?;
+                    continue;
+                }
+            };
+            if (confirmations as u64) < required {
+                self.record_check(&deposit, confirmations).await?;
+                continue;
+            }
+
+            match self.credit_deposit(&deposit, confirmations).await {
+                Ok(true) => credited += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::error!("Failed to credit deposit {}: {}", deposit.deposit_id, err);
+                }
+            }
+        }
+
+        Ok(credited)
+    }
+
+    /// 记录一次未能入账的确认检查，检查次数达到上限的充值标记为过期，不再占用每轮的确认批次
+    async fn record_check(&self, deposit: &Deposit, confirmations: u32) -> Result<()> {
+        let now = TimeZone::Beijing.get_time();
+        if deposit.check_attempts + 1 < self.max_confirm_checks {
+            return DepositRepo::mark_checked(&self.db, &deposit.deposit_id, confirmations, now)
+                .await;
+        }
+        if DepositRepo::expire(&self.db, &deposit.deposit_id, confirmations, now).await? {
+            tracing::warn!(
+                "{} deposit {} of {} {} for user {} expired after {} confirmation checks, \
+                 manual review required",
+                deposit.chain,
+                deposit.deposit_id,
+                deposit.amount,
+                deposit.currency,
+                deposit.user_id,
+                deposit.check_attempts + 1
+            );
+        }
+
+        Ok(())
+    }
+
+    /// 在同一事务中标记充值已入账并记入用户可用余额，已入账的充值返回 false
+    async fn credit_deposit(&self, deposit: &Deposit, confirmations: u32) -> Result<bool> {
+        let currency = deposit.currency.parse::<Currency>().map_err(|_| {
+            AppError::Internal(format!(
+                "Unsupported currency {} on deposit {}",
+                deposit.currency, deposit.deposit_id
+            ))
+        })?;
+        let now = TimeZone::Beijing.get_time();
+        let mut tx = self.db.begin().await?;
+        if !DepositRepo::tx_mark_credited(&mut tx, &deposit.deposit_id, confirmations, now).await? {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        let entry = JournalEntry::new(LedgerAccount::Available(deposit.user_id)).transfer(
+            LedgerAccount::External(deposit.user_id),
+            LedgerAccount::Available(deposit.user_id),
+            Money::new(deposit.amount, currency),
+        );
+        let mut builder = TransactionsBuilder::default();
+        builder
+            .transaction_id(deposit.deposit_id.clone())
+            .types(OrderType::Recharge.to_string())
+            .status(OrderStatus::Completed.to_string())
+            .blockchain_type(deposit.chain.clone())
+            .from_address(deposit.from_address.clone())
+            .to_address(deposit.address.clone())
+            .description(match &deposit.order_id {
+                Some(order_id) => format!(
+                    "{} late payment for order {}, tx {}",
+                    deposit.chain, order_id, deposit.tx_hash
+                ),
+                None => format!("{} deposit, tx {}", deposit.chain, deposit.tx_hash),
+            })
+            .created_at(now)
+            .updated_at(now)
+            .completed_at(Some(now));
+        let res = TransactionsRepo::tx_record(&mut tx, &entry, builder).await;
+        if let Err(err) = res {
+            tx.rollback().await?;
+            return Err(err);
+        }
+        TransactionsRepo::tx_update_hash_by_transaction_id(
+            &mut tx,
+            &deposit.deposit_id,
+            &deposit.tx_hash,
+        )
+        .await?;
+        tx.commit().await?;
+        tracing::info!(
+            "Credited deposit {} of {} {} to user {} ({} confirmations)",
+            deposit.deposit_id,
+            deposit.amount,
+            deposit.currency,
+            deposit.user_id,
+            confirmations
+        );
+
+        Ok(true)
+    }
+
+    /// 分页获取用户充值记录
+    pub async fn get_user_deposits(
+        &self,
+        user_id: u64,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Deposit>, u64)> {
+        DepositRepo::get_user_deposits(&self.db, user_id, page, limit).await
+    }
+}
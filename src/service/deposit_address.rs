@@ -0,0 +1,100 @@
+use crate::chain::address::AddressProviders;
+use crate::repository::{DepositAddressRepo, UserRepo};
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use sqlx::MySqlConnection;
+use std::sync::Arc;
+use time::Duration;
+
+/// 收款地址分配：订单收款地址与用户充值地址都优先复用地址池中的空闲地址，
+/// 地址池耗尽时通过派生器生成新地址
+pub struct DepositAddressService {
+    providers: Arc<AddressProviders>,
+    cooldown: Duration,
+}
+
+impl DepositAddressService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            providers: state.address_providers.clone(),
+            cooldown: Duration::hours(state.config.chain.address_cooldown_hours),
+        }
+    }
+
+    /// 在事务中为订单分配独占的收款地址，事务回滚时分配一并撤销
+    pub async fn tx_allocate(
+        &self,
+        tx: &mut MySqlConnection,
+        chain: &str,
+        user_id: u64,
+        order_id: &str,
+    ) -> Result<String> {
+        let now = TimeZone::Beijing.get_time();
+        // 释放后的地址需经过冷却期，避免旧订单的迟到转账被记到新订单上
+        if let Some(address) =
+            DepositAddressRepo::tx_lock_free_address(tx, chain, now - self.cooldown).await?
+        {
+            DepositAddressRepo::tx_allocate(tx, address.id, user_id, order_id, now).await?;
+            return Ok(address.address);
+        }
+
+        let Some(provider) = self.providers.get(chain) else {
+            return Err(AppError::Business(format!(
+                "No deposit address available for {}",
+                chain
+            )));
+        };
+        let index = DepositAddressRepo::tx_next_derivation_index(tx, chain).await?;
+        let address = provider.derive_address(index).await?;
+        DepositAddressRepo::tx_create_allocated(tx, chain, &address, index, user_id, order_id, now)
+            .await?;
+        tracing::info!("Derived {} deposit address #{} for order {}", chain, index, order_id);
+
+        Ok(address)
+    }
+
+    /// 在事务中释放订单占用的收款地址（订单支付完成、取消或过期）
+    pub async fn tx_release(&self, tx: &mut MySqlConnection, order_id: &str) -> Result<()> {
+        DepositAddressRepo::tx_release_by_order(tx, order_id, TimeZone::Beijing.get_time())
+            .await?;
+
+        Ok(())
+    }
+
+    /// 在事务中获取用户的长期充值地址，尚未绑定时从地址池分配或派生一个新地址
+    pub async fn tx_assign_user(
+        &self,
+        tx: &mut MySqlConnection,
+        chain: &str,
+        user_id: u64,
+    ) -> Result<String> {
+        // 锁定用户行，避免并发请求为同一用户绑定多个地址
+        if !UserRepo::tx_lock_user(tx, user_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        if let Some(address) = DepositAddressRepo::tx_get_user_address(tx, chain, user_id).await? {
+            return Ok(address);
+        }
+
+        let now = TimeZone::Beijing.get_time();
+        if let Some(address) =
+            DepositAddressRepo::tx_lock_free_address(tx, chain, now - self.cooldown).await?
+        {
+            DepositAddressRepo::tx_bind_user(tx, address.id, user_id, now).await?;
+            return Ok(address.address);
+        }
+
+        let Some(provider) = self.providers.get(chain) else {
+            return Err(AppError::Business(format!(
+                "No deposit address available for {}",
+                chain
+            )));
+        };
+        let index = DepositAddressRepo::tx_next_derivation_index(tx, chain).await?;
+        let address = provider.derive_address(index).await?;
+        DepositAddressRepo::tx_create_user_bound(tx, chain, &address, index, user_id, now).await?;
+        tracing::info!("Derived {} deposit address #{} for user {}", chain, index, user_id);
+
+        Ok(address)
+    }
+}
@@ -0,0 +1,187 @@
+use crate::model::exchange::{ExchangeConfig, ExchangeQuote, DG_USDT_SYMBOL};
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
+use crate::model::system_config::SystemConfigType;
+use crate::model::transactions::{OrderStatus, OrderType, Transactions, TransactionsBuilder};
+use crate::repository::{ExchangeRepo, PriceRepo, SystemConfigRepo, TransactionsRepo};
+use crate::utils::gen::generate_no;
+use crate::utils::money::{Currency, Money};
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use time::Duration;
+
+/// 币种兑换服务：按实时价格加点差锁定报价，凭报价在同一事务中扣减转出币种、增加转入币种
+pub struct ExchangeService {
+    db: sqlx::MySqlPool,
+}
+
+impl ExchangeService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+        }
+    }
+
+    /// 获取兑换配置，未配置时使用默认点差与手续费
+    pub async fn get_config(&self) -> Result<ExchangeConfig> {
+        let config = SystemConfigRepo::get_config_by_key(
+            &self.db,
+            &SystemConfigType::Exchange.to_string(),
+        )
+        .await;
+        match config {
+            Ok(config) => {
+                let config: ExchangeConfig = serde_json::from_str(&config.config_value)?;
+                config.validate().map_err(|e| {
+                    AppError::Internal(format!("Invalid exchange config: {}", e))
+                })?;
+                Ok(config)
+            }
+            Err(AppError::NotFound(_)) => Ok(ExchangeConfig::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 生成兑换报价，汇率在有效期内锁定
+    pub async fn quote(
+        &self,
+        user_id: u64,
+        from_currency: &str,
+        to_currency: &str,
+        from_amount: Decimal,
+    ) -> Result<ExchangeQuote> {
+        let parse = |code: &str| {
+            code.parse::<Currency>()
+                .map_err(|_| AppError::Validation(format!("Unsupported currency: {}", code)))
+        };
+        let from = Money::new(from_amount, parse(from_currency)?);
+        let to = parse(to_currency)?;
+
+        let config = self.get_config().await?;
+        let now = TimeZone::Beijing.get_time();
+        let Some(price) = PriceRepo::get_real_time_price(&self.db, DG_USDT_SYMBOL).await? else {
+            return Err(AppError::Business("DG price is unavailable".to_string()));
+        };
+        if now - price.last_updated_at > Duration::seconds(config.max_price_age_seconds) {
+            return Err(AppError::Business(
+                "DG price is outdated, please try again later".to_string(),
+            ));
+        }
+        let pricing = config
+            .price(from, to, price.current_price)
+            .map_err(AppError::Validation)?;
+
+        let quote = ExchangeQuote {
+            id: 0,
+            quote_id: generate_no("Q"),
+            user_id,
+            from_currency: from.currency().to_string(),
+            to_currency: to.to_string(),
+            from_amount: from.amount(),
+            fee: pricing.fee.amount(),
+            to_amount: pricing.to_amount.amount(),
+            exchange_rate: pricing.rate,
+            market_price: price.current_price,
+            expires_at: now + Duration::seconds(config.quote_ttl_seconds),
+            transaction_id: None,
+            created_at: now,
+        };
+        let id = ExchangeRepo::create_quote(&self.db, &quote).await?;
+
+        Ok(ExchangeQuote { id, ..quote })
+    }
+
+    /// 按锁定的报价兑换：转出币种扣减（含手续费）与转入币种增加在同一凭证中完成
+    pub async fn exchange(&self, user_id: u64, quote_id: &str) -> Result<Transactions> {
+        let mut tx = self.db.begin().await?;
+        let Some(quote) = ExchangeRepo::tx_lock_quote(&mut tx, quote_id, user_id).await? else {
+            tx.rollback().await?;
+            return Err(AppError::NotFound("Exchange quote not found".to_string()));
+        };
+        if quote.transaction_id.is_some() {
+            tx.rollback().await?;
+            return Err(AppError::Conflict(
+                "Exchange quote has already been used".to_string(),
+            ));
+        }
+        let now = TimeZone::Beijing.get_time();
+        if quote.expires_at <= now {
+            tx.rollback().await?;
+            return Err(AppError::Business(
+                "Exchange quote has expired, please request a new quote".to_string(),
+            ));
+        }
+
+        let parse = |code: &str| {
+            code.parse::<Currency>().map_err(|_| {
+                AppError::Internal(format!("Unsupported currency {} on quote {}", code, quote_id))
+            })
+        };
+        let from_currency = parse(&quote.from_currency)?;
+        let to_currency = parse(&quote.to_currency)?;
+        let fee = Money::new(quote.fee, from_currency);
+        let net = Money::new(quote.from_amount, from_currency) - fee;
+        let available = LedgerAccount::Available(user_id);
+        let exchange = LedgerAccount::System(SystemAccount::Exchange);
+        let entry = JournalEntry::new(available)
+            .transfer(available, exchange, net)
+            .transfer(available, LedgerAccount::System(SystemAccount::Fees), fee)
+            .transfer(exchange, available, Money::new(quote.to_amount, to_currency));
+
+        let mut builder = TransactionsBuilder::default();
+        builder
+            .types(OrderType::Exchange.to_string())
+            .to_currency(quote.to_currency.clone())
+            .fee(quote.fee)
+            .exchange_rate(quote.exchange_rate)
+            .status(OrderStatus::Completed.to_string())
+            .description(format!(
+                "exchange {} {} to {} {}",
+                quote.from_amount.normalize(),
+                quote.from_currency,
+                quote.to_amount.normalize(),
+                quote.to_currency
+            ))
+            .created_at(now)
+            .updated_at(now)
+            .completed_at(Some(now));
+        let tran = match TransactionsRepo::tx_record(&mut tx, &entry, builder).await {
+            Ok(tran) => tran,
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        };
+        if !ExchangeRepo::tx_use_quote(&mut tx, quote_id, &tran.transaction_id, now).await? {
+            tx.rollback().await?;
+            return Err(AppError::Conflict(
+                "Exchange quote is no longer valid".to_string(),
+            ));
+        }
+        tx.commit().await?;
+
+        Ok(tran)
+    }
+
+    /// 分页获取用户兑换记录
+    pub async fn get_conversion_records(
+        &self,
+        user_id: u64,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Transactions>, u64)> {
+        let total =
+            TransactionsRepo::count_by_user_id_and_type(&self.db, user_id, OrderType::Exchange)
+                .await?;
+        let records = TransactionsRepo::find_by_user_id_and_type(
+            &self.db,
+            user_id,
+            OrderType::Exchange,
+            Some(limit),
+            Some((page - 1) * limit),
+        )
+        .await?;
+
+        Ok((records, total))
+    }
+}
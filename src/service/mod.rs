@@ -13,6 +13,17 @@ pub mod content;
 pub mod chat;
 pub mod system_config;
 pub mod activity;
+pub mod payment;
+pub mod deposit_address;
+pub mod deposit;
+pub mod withdrawal;
+pub mod payout;
+pub mod wallet;
+pub mod reconciliation;
+pub mod exchange;
+pub mod blockchain;
+pub mod price;
+pub mod settlement;
 
 pub use auth::*;
 pub use user::*;
@@ -1,26 +1,47 @@
-use crate::model::system_config::{ConfigLevel, SystemConfigType};
+use crate::model::system_config::{
+    ConfigLevel, PurchaseLimit, SystemConfigType, DEFAULT_MAX_QUANTITY_PER_ORDER,
+};
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
 use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
+use crate::model::power::convert_power_package_to_package_item;
 use crate::model::{
-    PowerPackage, User, ORDER_STATUS_CANCELLED, ORDER_STATUS_PAID, ORDER_STATUS_PENDING,
-    USER_POWER_RECORD_STATUS_ACTIVE,
+    order_status_name, Order, OrderFilter, PowerPackage, User, UserPower, ORDER_STATUS_CANCELLED, ORDER_STATUS_EXPIRED, ORDER_STATUS_PAID,
+    ORDER_STATUS_PENDING, POWER_PACKAGE_STATUS_NO_UPGRADE, USER_POWER_RECORD_STATUS_ACTIVE,
+    USER_POWER_RECORD_STATUS_CANCELED,
 };
 use crate::repository::power_repo::PowerRepo;
-use crate::repository::{InviteRepo, SystemConfigRepo, TransactionsRepo, UserRepo};
+use crate::repository::{
+    AssetRepo, CouponRepo, InviteRepo, PromotionRepo, SystemConfigRepo, TransactionsRepo, UserRepo,
+};
+use crate::schema::order::OrderItem;
+use crate::service::blockchain::BlockchainService;
+use crate::service::deposit_address::DepositAddressService;
 use crate::utils::gen::generate_no;
+use crate::utils::money::{Currency, Money};
 use crate::utils::time_zone::TimeZone;
 use crate::{error::Result, repository::OrderRepo, state::AppState, AppError};
-use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use sqlx::MySqlConnection;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use time::{Duration, OffsetDateTime};
 
 pub struct OrderService {
     db: sqlx::MySqlPool,
+    blockchains: BlockchainService,
+    deposit_addresses: DepositAddressService,
+    expire_after: Duration,
+    expire_batch_size: u32,
 }
 
 impl OrderService {
     pub fn new(state: &AppState) -> Self {
         Self {
             db: (*state.db).clone(),
+            blockchains: BlockchainService::new(state),
+            deposit_addresses: DepositAddressService::new(state),
+            expire_after: Duration::minutes(state.config.order.expire_minutes),
+            expire_batch_size: state.config.order.expire_batch_size,
         }
     }
 
@@ -29,16 +50,43 @@ impl OrderService {
         &self,
         user: &User,
         power: &PowerPackage,
+        quantity: u32,
+        promotion_id: Option<u64>,
+        coupon_code: Option<&str>,
         chain_type: &str,
-        addr: &str,
-    ) -> Result<(u64, String)> {
+    ) -> Result<CreatedOrder> {
+        self.blockchains.get_enabled_blockchain(chain_type).await?;
+        let limit = self.get_purchase_limit(power.id).await?;
         let mut tx = self.db.begin().await?;
+        if let Err(err) = Self::check_purchase_limit(&mut tx, &limit, user.id, quantity).await {
+            tx.rollback().await?;
+            return Err(err);
+        }
         let order = self
-            .save_order(&mut tx, power, user.total_assets, user.id, chain_type, addr)
+            .save_order(
+                &mut tx,
+                power,
+                quantity,
+                user.id,
+                &OrderPricing {
+                    promotion_id,
+                    coupon_code,
+                    upgrade: None,
+                    chain_type,
+                },
+            )
             .await;
-        let Ok(order) = order else {
-            tx.rollback().await?;
-            return Err(AppError::Internal("Order creation failed".to_string()));
+        let order = match order {
+            Ok(order) => order,
+            // 优惠券、限时套餐等业务校验失败直接返回给用户
+            Err(err @ (AppError::Validation(_) | AppError::Business(_) | AppError::NotFound(_))) => {
+                tx.rollback().await?;
+                return Err(err);
+            }
+            Err(_) => {
+                tx.rollback().await?;
+                return Err(AppError::Internal("Order creation failed".to_string()));
+            }
         };
         // 提交事务
         match tx.commit().await {
@@ -50,44 +98,98 @@ impl OrderService {
         }
     }
 
-    /// 订单升级（使用事务）
+    /// 计算升级报价：校验升级条件，折算旧算力包剩余价值并预估新订单的支付拆分
+    pub async fn quote_upgrade(
+        &self,
+        user: &User,
+        old_user_power_id: u64,
+        power_id: u64,
+    ) -> Result<UpgradeQuote> {
+        let old_power =
+            PowerRepo::get_power_record_by_id_and_user(&self.db, old_user_power_id, user.id)
+                .await?;
+        let wallet = AssetRepo::get_wallet(&self.db, user.id).await?;
+        let mut conn = self.db.acquire().await?;
+
+        self.build_upgrade_quote(&mut conn, old_power, power_id, wallet.available(Currency::Usdt))
+            .await
+    }
+
+    /// 订单升级（使用事务）：锁定旧算力后重新计算报价，旧算力标记为已升级，
+    /// 剩余价值作为订单级抵扣记录在新订单上并生成升级抵扣交易，不计入账户余额；新订单取消或过期时旧算力恢复生效
     pub async fn upgrade_order(
         &self,
         user: &User,
-        power: &PowerPackage,
-        total_assets: Decimal,
+        old_user_power_id: u64,
+        power_id: u64,
         chain_type: &str,
-        addr: &str,
-        old_id: u64,
-    ) -> Result<(u64, String)> {
+    ) -> Result<(CreatedOrder, UpgradeQuote)> {
+        self.blockchains.get_enabled_blockchain(chain_type).await?;
         let mut tx = self.db.begin().await?;
-        let res = PowerRepo::upgrade_user_power_record(&mut tx, user.id, old_id).await;
-        if let Err(err) = res {
-            tx.rollback().await?;
-            return Err(AppError::Internal(format!(
-                "Failed to set original power status during upgrade: {}",
-                err
-            )));
+        let quote = match self.lock_upgrade_quote(&mut tx, user.id, old_user_power_id, power_id).await {
+            Ok(quote) => quote,
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(err);
+            }
         };
+
         let order = self
-            .save_order(&mut tx, power, total_assets, user.id, chain_type, addr)
+            .save_order(
+                &mut tx,
+                &quote.package,
+                1,
+                user.id,
+                &OrderPricing {
+                    promotion_id: None,
+                    coupon_code: None,
+                    upgrade: Some(&quote),
+                    chain_type,
+                },
+            )
             .await;
 
         let order = match order {
             Ok(order) => order,
-            Err(err) => {
+            // 业务校验失败直接返回给用户
+            Err(err @ (AppError::Validation(_) | AppError::Business(_) | AppError::NotFound(_))) => {
                 tx.rollback().await?;
-                return Err(AppError::Internal(format!(
-                    "Order creation failed: {}",
-                    err
-                )));
+                return Err(err);
+            }
+            Err(_) => {
+                tx.rollback().await?;
+                return Err(AppError::Internal("Order creation failed".to_string()));
             }
         };
 
-        //删除之前的算力
+        // 升级抵扣单独记一笔交易，交易ID与新订单号相同，随订单支付完成或关闭取消
+        if quote.credit.is_positive() {
+            let current_time = TimeZone::Beijing.get_time();
+            let paid = order.coin_pay.is_zero();
+            let mut builder = TransactionsBuilder::default();
+            builder
+                .transaction_id(order.order_number.clone())
+                .user_id(user.id)
+                .types(OrderType::UpgradeCredit.to_string())
+                .from_currency(quote.credit.currency().to_string())
+                .amount(quote.credit.amount())
+                .status(if paid { OrderStatus::Completed } else { OrderStatus::Pending }.to_string())
+                .description(format!(
+                    "order {} upgrade credit from power {}",
+                    order.order_number, quote.old_power.id
+                ))
+                .created_at(current_time)
+                .updated_at(current_time)
+                .completed_at(paid.then_some(current_time));
+            if let Err(err) = TransactionsRepo::tx_record_memo(&mut tx, builder).await {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        }
+
         // 提交事务
         match tx.commit().await {
-            Ok(_) => Ok(order), // 提交成功
+            Ok(_) => Ok((order, quote)), // 提交成功
             Err(e) => Err(AppError::Internal(format!(
                 "Transaction commit failed: {}",
                 e
@@ -95,61 +197,225 @@ impl OrderService {
         }
     }
 
-    pub async fn save_order(
+    /// 锁定旧算力与账户余额，按锁定后的数据重新计算报价，并将旧算力标记为已升级
+    async fn lock_upgrade_quote(
         &self,
         tx: &mut MySqlConnection,
-        power: &PowerPackage,
-        total_assets: Decimal,
         user_id: u64,
-        chain_type: &str,
-        addr: &str,
-    ) -> Result<(u64, String)> {
-        // 计算订单总金额
-        let Some(package_amount) = power.amount.to_f64() else {
-            return Err(AppError::Internal(format!(
-                "Amount conversion failed for power ID {}",
-                power.id
-            )));
+        old_user_power_id: u64,
+        power_id: u64,
+    ) -> Result<UpgradeQuote> {
+        let old_power = PowerRepo::tx_lock_user_power(tx, old_user_power_id, user_id).await?;
+        let available = AssetRepo::tx_lock_available(tx, user_id, Currency::Usdt).await?;
+        let quote = self
+            .build_upgrade_quote(tx, old_power, power_id, available)
+            .await?;
+        if !PowerRepo::upgrade_user_power_record(tx, user_id, quote.old_power.id).await? {
+            return Err(AppError::Conflict(
+                "Computing power package is already being upgraded".to_string(),
+            ));
+        }
+
+        Ok(quote)
+    }
+
+    /// 校验升级条件并折算旧算力包剩余价值：按实付单价自算力生效时起折旧，累计收益按每日收盘价折合 USDT
+    async fn build_upgrade_quote(
+        &self,
+        conn: &mut MySqlConnection,
+        old_power: UserPower,
+        power_id: u64,
+        available: Money,
+    ) -> Result<UpgradeQuote> {
+        if old_power.status != USER_POWER_RECORD_STATUS_ACTIVE {
+            return Err(AppError::OrderNotPaid(
+                "Computing power package not paid".to_string(),
+            ));
+        }
+        let Some(package) = PowerRepo::get_power_record_by_id(&self.db, power_id).await? else {
+            return Err(AppError::NotFound(
+                "Upgrade computing power package not found".to_string(),
+            ));
+        };
+        if old_power.lv as u16 >= package.lv {
+            return Err(AppError::Validation(
+                "Upgrade computing power package has the same level as current package, cannot upgrade".to_string(),
+            ));
+        }
+        if package.is_upgrade == POWER_PACKAGE_STATUS_NO_UPGRADE {
+            return Err(AppError::NotFound(
+                "Computing power package upgrade channel not enabled".to_string(),
+            ));
+        }
+        let Some(old_package) =
+            PowerRepo::get_power_record_by_id(&self.db, old_power.power_package_id).await?
+        else {
+            return Err(AppError::NotFound(
+                "Computing power package not found".to_string(),
+            ));
+        };
+
+        // 赠送等无支付订单的算力没有实付金额，不产生抵扣
+        let paid = OrderRepo::tx_get_paid_unit_price(conn, &old_power.order_id)
+            .await?
+            .map_or(Money::zero(Currency::Usdt), Money::usdt);
+        let earned = Money::usdt(PowerRepo::tx_get_earnings_value(conn, old_power.id).await?);
+        let (credit, elapsed_days) = upgrade_credit(
+            paid,
+            earned,
+            old_package.duration_days,
+            old_power.start_time.unwrap_or(old_power.created_at),
+            TimeZone::Beijing.get_time(),
+        );
+        let (asset_pay, coin_pay) = split_payment(
+            available,
+            Money::usdt(package.amount).saturating_sub(credit),
+        );
+
+        Ok(UpgradeQuote {
+            old_power,
+            package,
+            duration_days: old_package.duration_days,
+            elapsed_days,
+            paid,
+            earned,
+            credit,
+            asset_pay,
+            coin_pay,
+        })
+    }
+
+    /// 获取算力包的购买限制，未配置时使用默认单笔上限
+    async fn get_purchase_limit(&self, power_id: u64) -> Result<PurchaseLimit> {
+        let default_limit = PurchaseLimit {
+            power_id,
+            max_per_order: DEFAULT_MAX_QUANTITY_PER_ORDER,
+            max_per_user: 0,
+        };
+        let config = SystemConfigRepo::get_config_by_key(
+            &self.db,
+            &SystemConfigType::PurchaseLimit.to_string(),
+        )
+        .await;
+        let config = match config {
+            Ok(config) => config,
+            Err(AppError::NotFound(_)) => return Ok(default_limit),
+            Err(err) => return Err(err),
         };
-        let total_amount = Decimal::new((package_amount * 1.0f64 * 100.0).round() as i64, 2);
-        let mut asset_pay = Decimal::new(0, 2);
-        let mut coin_pay = Decimal::new(0, 2);
-        let mut new_total_assets = Decimal::new(0, 2);
-        let zero = Decimal::new(0, 0);
-        if total_assets > zero {
-            if total_assets >= total_amount {
-                asset_pay = total_amount;
-                new_total_assets = total_assets - total_amount;
-            } else {
-                asset_pay = total_assets;
-                coin_pay = total_amount - total_assets;
-                new_total_assets = zero;
+        let limits: Vec<PurchaseLimit> = serde_json::from_str(&config.config_value)?;
+        limits
+            .iter()
+            .try_for_each(PurchaseLimit::validate)
+            .map_err(|e| AppError::Internal(format!("Invalid purchase limit config: {}", e)))?;
+
+        Ok(limits
+            .into_iter()
+            .find(|limit| limit.power_id == power_id)
+            .unwrap_or(default_limit))
+    }
+
+    /// 校验购买数量是否超过单笔上限与用户累计持有上限，有持有上限时锁定用户行直至事务结束
+    async fn check_purchase_limit(
+        tx: &mut MySqlConnection,
+        limit: &PurchaseLimit,
+        user_id: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        if quantity == 0 || quantity > limit.max_per_order {
+            return Err(AppError::Validation(format!(
+                "Purchase quantity must be between 1 and {}",
+                limit.max_per_order
+            )));
+        }
+        if limit.max_per_user > 0 {
+            // 锁定用户行，串行化同一用户的并发下单，避免各自计数后同时超过持有上限
+            if !UserRepo::tx_lock_user(tx, user_id).await? {
+                return Err(AppError::NotFound("User not found".to_string()));
+            }
+            let held = PowerRepo::count_user_power_by_package(tx, user_id, limit.power_id).await?;
+            if held + quantity as i64 > limit.max_per_user as i64 {
+                return Err(AppError::Business(format!(
+                    "Purchase limit exceeded: at most {} units of this package per user, {} already held",
+                    limit.max_per_user, held
+                )));
             }
-        } else {
-            coin_pay = total_amount
         }
 
-        // 开始事务
+        Ok(())
+    }
+
+    pub async fn save_order(
+        &self,
+        tx: &mut MySqlConnection,
+        power: &PowerPackage,
+        quantity: u32,
+        user_id: u64,
+        pricing: &OrderPricing<'_>,
+    ) -> Result<CreatedOrder> {
+        let OrderPricing {
+            promotion_id,
+            coupon_code,
+            upgrade,
+            chain_type,
+        } = *pricing;
+        let current_time = TimeZone::Beijing.get_time();
+        // 限时优惠套餐按优惠价计价并预占库存
+        let unit_price = match promotion_id {
+            Some(promotion_id) => {
+                Self::reserve_promotion(tx, promotion_id, power.id, quantity, current_time).await?
+            }
+            None => Money::usdt(power.amount),
+        };
+        // 计算订单总金额：先扣减优惠券与升级抵扣，再优先使用账户资产抵扣，不足部分链上支付
+        let total_amount = unit_price * quantity;
+        let (coupon_id, discount) = match coupon_code {
+            Some(code) => {
+                let (coupon_id, discount) =
+                    Self::apply_coupon(tx, code, user_id, power.id, total_amount).await?;
+                (Some(coupon_id), discount)
+            }
+            None => (None, Money::zero(Currency::Usdt)),
+        };
+        let upgrade_credit = upgrade.map_or(Money::zero(Currency::Usdt), |quote| quote.credit);
+        let payable = (total_amount - discount).saturating_sub(upgrade_credit);
+        let available = AssetRepo::tx_lock_available(tx, user_id, Currency::Usdt).await?;
+        let (asset_pay, coin_pay) = split_payment(available, payable);
 
-        let state = if coin_pay == zero {
+        let state = if coin_pay.is_zero() {
             ORDER_STATUS_PAID
         } else {
             ORDER_STATUS_PENDING
         };
-        // 创建订单
+        // 需要链上支付的订单分配独占收款地址，资产全额抵扣的订单无需收款地址
         let order_no = generate_no("O");
+        let deposit_address = if coin_pay.is_positive() {
+            Some(
+                self.deposit_addresses
+                    .tx_allocate(tx, chain_type, user_id, &order_no)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        // 创建订单
         let order_id = match OrderRepo::create_order_in_tx(
             tx,
             user_id,
             &order_no,
             power.id,
-            1,
-            total_amount,
-            asset_pay,
-            coin_pay,
+            promotion_id,
+            quantity,
+            total_amount.amount(),
+            asset_pay.amount(),
+            coin_pay.amount(),
+            coupon_id,
+            discount.amount(),
+            upgrade.map(|quote| quote.old_power.id),
+            upgrade_credit.amount(),
             chain_type,
-            addr,
+            deposit_address.as_deref().unwrap_or_default(),
             state,
+            current_time + self.expire_after,
         )
         .await
         {
@@ -157,50 +423,163 @@ impl OrderService {
             Err(e) => return Err(e),
         };
 
+        if let Some(coupon_id) = coupon_id {
+            CouponRepo::tx_create_usage(tx, coupon_id, user_id, &order_no, discount.amount())
+                .await?;
+        }
+
         // 为用户创建算力记录（每个购买数量创建一条记录）
         let power_amount = power.amount;
-        let current_time = TimeZone::Beijing.get_time();
-        match PowerRepo::create_user_power_record(
-            tx,
-            user_id,
-            power.id,
-            &order_no,
-            0, // 0 表示购买
-            &power_amount,
-            state as i16,
-            power.lv,
-            &power.daily_yield_percentage,
-        )
-        .await
-        {
-            Ok(_) => {} // 继续下一个
-            Err(e) => return Err(e),
+        for _ in 0..quantity {
+            match PowerRepo::create_user_power_record(
+                tx,
+                user_id,
+                power.id,
+                &order_no,
+                0, // 0 表示购买
+                &power_amount,
+                state as i16,
+                power.lv,
+                &power.daily_yield_percentage,
+            )
+            .await
+            {
+                Ok(_) => {} // 继续下一个
+                Err(e) => return Err(e),
+            }
         }
 
-        if total_assets != new_total_assets {
-            // 总资产变化
-            let res =
-                UserRepo::update_user_assets_in_tx(tx, user_id, new_total_assets, total_assets)
-                    .await;
-            if let Err(err) = res {
-                return Err(err);
-            }
-            let tran = TransactionsBuilder::default()
-                .user_id(user_id)
+        if asset_pay.is_positive() {
+            // 钱包可用余额抵扣的部分记入算力销售收入
+            let entry = JournalEntry::new(LedgerAccount::Available(user_id)).transfer(
+                LedgerAccount::Available(user_id),
+                LedgerAccount::System(SystemAccount::Sales),
+                asset_pay,
+            );
+            let mut builder = TransactionsBuilder::default();
+            builder
                 .types(OrderType::Purchase.to_string())
-                .amount(Decimal::ZERO - asset_pay)
                 .status(OrderStatus::Completed.to_string())
                 .created_at(current_time)
-                .updated_at(current_time)
-                .build()
-                .map_err(|e| AppError::Internal(format!("build transaction:{}", e.to_string())))?;
-            let res = TransactionsRepo::tx_create(tx, &vec![tran]).await;
-            if let Err(err) = res {
-                return Err(err);
+                .updated_at(current_time);
+            TransactionsRepo::tx_record(tx, &entry, builder).await?;
+        }
+
+        Ok(CreatedOrder {
+            id: order_id,
+            order_number: order_no,
+            deposit_address,
+            coin_pay,
+        })
+    }
+
+    /// 校验限时优惠套餐并预占库存，返回优惠单价
+    async fn reserve_promotion(
+        tx: &mut MySqlConnection,
+        promotion_id: u64,
+        power_package_id: u64,
+        quantity: u32,
+        now: OffsetDateTime,
+    ) -> Result<Money> {
+        let Some(promotion) = PromotionRepo::tx_get_package(tx, promotion_id).await? else {
+            return Err(AppError::NotFound("Promotion package not found".to_string()));
+        };
+        if promotion.power_package_id != power_package_id {
+            return Err(AppError::Validation(
+                "Promotion package does not match the computing power package".to_string(),
+            ));
+        }
+        promotion.check_purchasable(now, quantity)?;
+        // 读取与扣减之间可能被其他买家抢先，以条件更新的结果为准
+        if !PromotionRepo::tx_reserve_stock(tx, promotion_id, quantity, now).await? {
+            return Err(AppError::Business(format!(
+                "Promotion {} is sold out",
+                promotion.name
+            )));
+        }
+
+        Ok(Money::usdt(promotion.price))
+    }
+
+    /// 校验优惠码并计算订单可减免金额，返回优惠券 ID 与减免金额
+    async fn apply_coupon(
+        tx: &mut MySqlConnection,
+        code: &str,
+        user_id: u64,
+        power_package_id: u64,
+        total_amount: Money,
+    ) -> Result<(u64, Money)> {
+        let Some(coupon) = CouponRepo::tx_find_by_code_for_update(tx, code).await? else {
+            return Err(AppError::Validation(format!("Invalid coupon code: {}", code)));
+        };
+        if !CouponRepo::tx_is_package_allowed(tx, coupon.id, power_package_id).await? {
+            return Err(AppError::Business(format!(
+                "Coupon {} is not applicable to this package",
+                coupon.code
+            )));
+        }
+        if coupon.per_user_limit > 0 {
+            let used = CouponRepo::tx_count_user_usages(tx, coupon.id, user_id).await?;
+            if used >= coupon.per_user_limit as i64 {
+                return Err(AppError::Business(format!(
+                    "Coupon {} has reached its usage limit for this user",
+                    coupon.code
+                )));
+            }
+        }
+        let discount = coupon.discount_for(total_amount, TimeZone::Beijing.get_time())?;
+
+        Ok((coupon.id, discount))
+    }
+
+    /// 获取用户订单列表，算力包标题按用户语言本地化
+    pub async fn get_user_orders(
+        &self,
+        user_id: u64,
+        filter: &OrderFilter,
+        page: u32,
+        limit: u32,
+        lang: &str,
+    ) -> Result<(Vec<OrderItem>, u64)> {
+        let (orders, total) =
+            OrderRepo::get_user_orders(&self.db, user_id, filter, page, limit).await?;
+
+        // 同一页内的算力包只查询一次
+        let mut packages: HashMap<u64, (String, u16)> = HashMap::new();
+        let mut items = Vec::with_capacity(orders.len());
+        for order in orders {
+            if let Entry::Vacant(entry) = packages.entry(order.power_package_id) {
+                let package = PowerRepo::get_power_record_by_id(&self.db, order.power_package_id)
+                    .await?
+                    .map(|package| {
+                        let item = convert_power_package_to_package_item(package, lang);
+                        (item.title, item.lv)
+                    })
+                    .unwrap_or_default();
+                entry.insert(package);
             }
+            let (power_name, lv) = packages[&order.power_package_id].clone();
+            items.push(OrderItem {
+                order_number: order.order_id,
+                power_id: order.power_package_id,
+                power_name,
+                lv,
+                quantity: order.quantity,
+                amount: order.amount,
+                discount_amount: order.discount_amount,
+                asset_pay: order.asset_pay,
+                coin_pay: order.coin_pay,
+                blockchain_type: order.blockchain_type,
+                blockchain_address: order.blockchain_address,
+                transaction_hash: order.transaction_hash,
+                confirmations: order.confirmations,
+                status: order_status_name(order.status).to_string(),
+                expired_at: order.expired_at,
+                created_at: order.created_at,
+            });
         }
 
-        Ok((order_id, order_no))
+        Ok((items, total))
     }
 
     /// 取消订单
@@ -214,66 +593,152 @@ impl OrderService {
                 "Only pending orders can be cancelled".to_string(),
             ));
         }
-        let user = UserRepo::find_by_id(&self.db, order.user_id).await?;
+        // 已匹配到链上支付、等待确认的订单不能取消
+        if order.transaction_hash.is_some() {
+            return Err(AppError::Business(
+                "Payment of this order is being confirmed".to_string(),
+            ));
+        }
+        if !self.close_pending_order(&order, ORDER_STATUS_CANCELLED).await? {
+            return Err(AppError::Business(
+                "Only pending orders can be cancelled".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 过期超时未支付的订单，返回本轮过期的订单数
+    pub async fn expire_pending_orders(&self) -> Result<usize> {
+        let now = TimeZone::Beijing.get_time();
+        let orders =
+            OrderRepo::get_expired_pending_orders(&self.db, now, self.expire_batch_size).await?;
+        let mut expired = 0;
+        for order in orders {
+            match self.close_pending_order(&order, ORDER_STATUS_EXPIRED).await {
+                Ok(true) => expired += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::error!("Failed to expire order {}: {}", order.order_id, err);
+                }
+            }
+        }
+
+        Ok(expired)
+    }
+
+    /// 关闭待支付订单（取消/过期）：更新订单与算力状态，释放收款地址、回补限时套餐库存、释放优惠券、
+    /// 恢复升级前的旧算力并退还资产抵扣部分
+    /// 订单在此期间已被支付或关闭时返回 false
+    async fn close_pending_order(&self, order: &Order, order_status: i8) -> Result<bool> {
         let mut tx = self.db.begin().await?;
-        let update = OrderRepo::tx_update_order(
+        let update = OrderRepo::tx_close_pending_order(
             &mut tx,
-            &order,
-            ORDER_STATUS_CANCELLED,
-            ORDER_STATUS_CANCELLED,
+            &order.order_id,
+            order_status,
+            USER_POWER_RECORD_STATUS_CANCELED,
         )
         .await;
-        if let Err(err) = update {
+        match update {
+            Ok(true) => {}
+            Ok(false) => {
+                tx.rollback().await?;
+                return Ok(false);
+            }
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(AppError::Internal(format!(
+                    "Failed to update order status: {}",
+                    err
+                )));
+            }
+        }
+        if let Err(err) = self.deposit_addresses.tx_release(&mut tx, &order.order_id).await {
             tx.rollback().await?;
-            return Err(AppError::Internal(format!(
-                "Failed to update order status: {}",
-                err.to_string()
-            )));
+            return Err(err);
         }
-        if order.asset_pay > Decimal::new(0, 2) {
-            let asset = UserRepo::update_user_assets_in_tx(
+        if let Some(promotion_id) = order.promotion_package_id {
+            let res = PromotionRepo::tx_release_stock(&mut tx, promotion_id, order.quantity).await;
+            if let Err(err) = res {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        }
+        if order.coupon_id.is_some() {
+            if let Err(err) = CouponRepo::tx_release_by_order(&mut tx, &order.order_id).await {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        }
+        // 升级抵扣只是订单上的减免，不退还；旧算力恢复生效，抵扣交易取消
+        if let Some(power_id) = order.upgrade_power_id {
+            match PowerRepo::tx_restore_upgraded_power(&mut tx, order.user_id, power_id).await {
+                Ok(true) => {}
+                Ok(false) => tracing::warn!(
+                    "Upgraded power {} of order {} is no longer pending upgrade",
+                    power_id,
+                    order.order_id
+                ),
+                Err(err) => {
+                    tx.rollback().await?;
+                    return Err(err);
+                }
+            }
+            let res = TransactionsRepo::tx_update_status_by_transaction_id(
                 &mut tx,
-                order.user_id,
-                user.total_assets + order.asset_pay,
-                user.total_assets,
+                &order.order_id,
+                OrderStatus::Cancelled,
+                None,
             )
             .await;
-            if let Err(err) = asset {
+            if let Err(err) = res {
                 tx.rollback().await?;
-                return Err(AppError::Internal(format!(
-                    "Failed to update user assets while updating order status: {}",
-                    err.to_string()
-                )));
+                return Err(err);
             }
+        }
+        let refund = Money::usdt(order.asset_pay);
+        if refund.is_positive() {
             let current_time = TimeZone::Beijing.get_time();
-            let tran = TransactionsBuilder::default()
-                .user_id(order.user_id)
+            let entry = JournalEntry::new(LedgerAccount::Available(order.user_id)).transfer(
+                LedgerAccount::System(SystemAccount::Sales),
+                LedgerAccount::Available(order.user_id),
+                refund,
+            );
+            let mut builder = TransactionsBuilder::default();
+            builder
                 .types(OrderType::CancelPurchase.to_string())
-                .amount(order.asset_pay)
                 .status(OrderStatus::Completed.to_string())
+                .description(if order_status == ORDER_STATUS_EXPIRED {
+                    format!("order {} expired, asset refunded", order.order_id)
+                } else {
+                    format!("order {} cancelled, asset refunded", order.order_id)
+                })
                 .created_at(current_time)
-                .updated_at(current_time)
-                .build()
-                .map_err(|e| AppError::Internal(format!("build transaction:{}", e.to_string())))?;
-            let res = TransactionsRepo::tx_create(&mut tx, &vec![tran]).await;
+                .updated_at(current_time);
+            let res = TransactionsRepo::tx_record(&mut tx, &entry, builder).await;
             if let Err(err) = res {
                 tx.rollback().await?;
-                return Err(err);
+                return Err(AppError::Internal(format!(
+                    "Failed to update user assets while updating order status: {}",
+                    err
+                )));
             }
         }
 
         // 提交事务
         tx.commit().await?;
 
-        Ok(())
+        Ok(true)
     }
 
-    /// 订单支付完成
-    pub async fn paid_order(&self, user_id: u64, order_id: &str) -> Result<()> {
-        // 获取订单信息
-        let order = OrderRepo::get_order_by_id_and_user_id(&self.db, order_id, user_id).await?;
-
-        // 验证订单状态（只有待支付的订单才能付款）
+    /// 链上支付确认后结算订单：标记已支付、激活算力、升级等级并发放邀请分成
+    pub async fn settle_paid_order(
+        &self,
+        order: &Order,
+        transaction_hash: &str,
+        confirmations: u32,
+    ) -> Result<()> {
+        // 验证订单状态（只有待支付的订单才能结算）
         if order.status != ORDER_STATUS_PENDING {
             return Err(AppError::Business(
                 "Only pending orders can be paid".to_string(),
@@ -296,26 +761,50 @@ impl OrderService {
         }
 
         let mut tx = self.db.begin().await?;
-        let res = UserRepo::tx_update_lv(&mut tx, user.id, curr_lv, &order.coin_pay).await;
-        if let Err(err) = res {
+        let paid =
+            OrderRepo::tx_mark_order_paid(&mut tx, &order.order_id, transaction_hash, confirmations)
+                .await;
+        match paid {
+            Ok(true) => {}
+            Ok(false) => {
+                tx.rollback().await?;
+                return Err(AppError::Conflict(format!(
+                    "Order {} has already been settled",
+                    order.order_id
+                )));
+            }
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(AppError::Internal(format!(
+                    "Failed to update order status: {}",
+                    err
+                )));
+            }
+        }
+
+        if let Err(err) = self.deposit_addresses.tx_release(&mut tx, &order.order_id).await {
             tx.rollback().await?;
-            return Err(AppError::Internal(format!(
-                "Failed to update user level information: {}",
-                err.to_string()
-            )));
+            return Err(err);
+        }
+        if order.upgrade_power_id.is_some() {
+            let res = TransactionsRepo::tx_update_status_by_transaction_id(
+                &mut tx,
+                &order.order_id,
+                OrderStatus::Completed,
+                Some(TimeZone::Beijing.get_time()),
+            )
+            .await;
+            if let Err(err) = res {
+                tx.rollback().await?;
+                return Err(err);
+            }
         }
 
-        let update = OrderRepo::tx_update_order(
-            &mut tx,
-            &order,
-            ORDER_STATUS_PAID,
-            USER_POWER_RECORD_STATUS_ACTIVE as i8,
-        )
-        .await;
-        if let Err(err) = update {
+        let res = UserRepo::tx_update_lv(&mut tx, user.id, curr_lv, &order.coin_pay).await;
+        if let Err(err) = res {
             tx.rollback().await?;
             return Err(AppError::Internal(format!(
-                "Failed to update order status: {}",
+                "Failed to update user level information: {}",
                 err.to_string()
             )));
         }
@@ -324,35 +813,46 @@ impl OrderService {
             &mut tx,
             user.inviter_id,
             user.parent_inviter_id,
-            &order.coin_pay,
+            Money::usdt(order.coin_pay),
         )
         .await;
-        let mut trans = match invite {
-            Ok(invite) => invite,
+        match invite {
+            Ok(_) => {}
             Err(err) => {
+                tx.rollback().await?;
                 return Err(AppError::Internal(format!(
                     "Recharge sharing failed: {}",
                     err.to_string()
                 )));
             }
         };
-        if order.coin_pay > Decimal::new(0, 2) {
+        let coin_pay = Money::usdt(order.coin_pay);
+        if coin_pay.is_positive() {
+            // 链上支付的部分由用户链上钱包直接记入算力销售收入，不经过平台余额
             let current_time = TimeZone::Beijing.get_time();
-            let tran = TransactionsBuilder::default()
-                .user_id(order.user_id)
+            let entry = JournalEntry::new(LedgerAccount::External(order.user_id)).transfer(
+                LedgerAccount::External(order.user_id),
+                LedgerAccount::System(SystemAccount::Sales),
+                coin_pay,
+            );
+            let mut builder = TransactionsBuilder::default();
+            builder
                 .types(OrderType::Purchase.to_string())
                 .status(OrderStatus::Completed.to_string())
+                .blockchain_type(order.blockchain_type.clone())
+                .to_address(order.blockchain_address.clone())
+                .description(format!(
+                    "order {} paid on-chain, tx {}",
+                    order.order_id, transaction_hash
+                ))
                 .created_at(current_time)
                 .updated_at(current_time)
-                .amount(order.coin_pay)
-                .build()
-                .map_err(|e| AppError::Internal(format!("build transaction:{}", e.to_string())))?;
-            trans.push(tran);
-        }
-        let res = TransactionsRepo::tx_create(&mut tx, &trans).await;
-        if let Err(err) = res {
-            tx.rollback().await?;
-            return Err(err);
+                .completed_at(Some(current_time));
+            let res = TransactionsRepo::tx_record(&mut tx, &entry, builder).await;
+            if let Err(err) = res {
+                tx.rollback().await?;
+                return Err(err);
+            }
         }
 
         // 提交事务
@@ -360,3 +860,147 @@ impl OrderService {
         Ok(())
     }
 }
+
+/// 新建订单结果
+pub struct CreatedOrder {
+    pub id: u64,
+    pub order_number: String,
+    /// 链上支付的收款地址，资产全额抵扣时为 None
+    pub deposit_address: Option<String>,
+    /// 需链上支付的金额
+    pub coin_pay: Money,
+}
+
+/// 下单的计价与支付方式
+#[derive(Clone, Copy)]
+pub struct OrderPricing<'a> {
+    /// 限时优惠套餐ID，按优惠价计价
+    pub promotion_id: Option<u64>,
+    /// 优惠券码
+    pub coupon_code: Option<&'a str>,
+    /// 升级报价，剩余价值作为订单抵扣
+    pub upgrade: Option<&'a UpgradeQuote>,
+    /// 链上支付使用的链类型
+    pub chain_type: &'a str,
+}
+
+/// 升级报价
+pub struct UpgradeQuote {
+    pub old_power: UserPower,
+    pub package: PowerPackage,
+    /// 旧算力包有效期天数
+    pub duration_days: u32,
+    /// 旧算力已使用天数
+    pub elapsed_days: u32,
+    /// 旧算力实付单价
+    pub paid: Money,
+    /// 旧算力累计收益按每日收盘价折合的 USDT
+    pub earned: Money,
+    /// 旧算力包剩余价值（升级抵扣）
+    pub credit: Money,
+    pub asset_pay: Money,
+    pub coin_pay: Money,
+}
+
+/// 订单支付拆分：优先使用账户资产抵扣，不足部分链上支付，返回 (资产抵扣, 链上支付)
+pub fn split_payment(total_assets: Money, total_amount: Money) -> (Money, Money) {
+    let asset_pay = if total_assets.is_positive() {
+        total_assets.min(total_amount)
+    } else {
+        Money::zero(Currency::Usdt)
+    };
+
+    (asset_pay, total_amount - asset_pay)
+}
+
+/// 计算旧算力包的升级抵扣金额，返回 (抵扣金额, 已使用天数)
+///
+/// 已消耗价值取按天折旧（实付金额 × 自生效起已使用天数 / 有效期）与累计收益（USDT）两者中的较大值，
+/// 抵扣金额 = 实付金额 - 已消耗价值，最低为 0。
+pub fn upgrade_credit(
+    paid: Money,
+    earned: Money,
+    duration_days: u32,
+    started_at: OffsetDateTime,
+    now: OffsetDateTime,
+) -> (Money, u32) {
+    if duration_days == 0 {
+        return (Money::zero(Currency::Usdt), 0);
+    }
+    let elapsed_days = (now - started_at).whole_days().clamp(0, duration_days as i64) as u32;
+    let depreciation = Money::usdt(
+        paid.amount() * Decimal::from(elapsed_days) / Decimal::from(duration_days),
+    );
+    let consumed = if earned.amount() > depreciation.amount() {
+        earned
+    } else {
+        depreciation
+    };
+
+    (paid.saturating_sub(consumed), elapsed_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_credit() {
+        let now = TimeZone::Beijing.get_time();
+        let paid = Money::usdt(Decimal::from(300));
+        let zero = Money::zero(Currency::Usdt);
+
+        // 刚支付：全额抵扣
+        let (credit, elapsed) = upgrade_credit(paid, zero, 30, now, now);
+        assert_eq!((credit, elapsed), (paid, 0));
+
+        // 使用 10 天，收益低于折旧：按剩余天数折算
+        let paid_at = now - Duration::days(10);
+        let (credit, elapsed) = upgrade_credit(paid, Money::usdt(Decimal::from(50)), 30, paid_at, now);
+        assert_eq!((credit, elapsed), (Money::usdt(Decimal::from(200)), 10));
+
+        // 收益高于折旧：扣除累计收益
+        let (credit, _) = upgrade_credit(paid, Money::usdt(Decimal::from(120)), 30, paid_at, now);
+        assert_eq!(credit, Money::usdt(Decimal::from(180)));
+
+        // 限时优惠价购买：按实付金额折算，不按原价
+        let (credit, _) = upgrade_credit(Money::usdt(Decimal::from(150)), zero, 30, paid_at, now);
+        assert_eq!(credit, Money::usdt(Decimal::from(100)));
+
+        // 赠送算力无实付金额：无抵扣
+        let (credit, _) = upgrade_credit(zero, zero, 30, paid_at, now);
+        assert_eq!(credit, zero);
+
+        // 延迟生效：支付 10 天后才开始计算，按生效后 4 天折旧
+        let started_at = now - Duration::days(4);
+        let (credit, elapsed) = upgrade_credit(paid, zero, 30, started_at, now);
+        assert_eq!((credit, elapsed), (Money::usdt(Decimal::from(260)), 4));
+
+        // 尚未生效：全额抵扣
+        let started_at = now + Duration::days(2);
+        let (credit, elapsed) = upgrade_credit(paid, zero, 30, started_at, now);
+        assert_eq!((credit, elapsed), (paid, 0));
+
+        // 超过有效期：无剩余价值
+        let paid_at = now - Duration::days(45);
+        let (credit, elapsed) = upgrade_credit(paid, zero, 30, paid_at, now);
+        assert_eq!((credit, elapsed), (zero, 30));
+    }
+
+    #[test]
+    fn test_split_payment() {
+        let price = Money::usdt(Decimal::from(100));
+        assert_eq!(
+            split_payment(Money::usdt(Decimal::from(30)), price),
+            (Money::usdt(Decimal::from(30)), Money::usdt(Decimal::from(70)))
+        );
+        assert_eq!(
+            split_payment(Money::usdt(Decimal::from(150)), price),
+            (price, Money::zero(Currency::Usdt))
+        );
+        assert_eq!(
+            split_payment(Money::zero(Currency::Usdt), price),
+            (Money::zero(Currency::Usdt), price)
+        );
+    }
+}
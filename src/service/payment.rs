@@ -0,0 +1,194 @@
+use crate::chain::{ChainTransfer, ChainWatchers};
+use crate::model::{Order, ORDER_STATUS_PENDING};
+use crate::repository::OrderRepo;
+use crate::schema::order::PaymentConfirmationResponse;
+use crate::service::blockchain::BlockchainService;
+use crate::service::order::OrderService;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// 每轮后台确认处理的最大订单数
+const CONFIRM_BATCH_SIZE: u32 = 100;
+
+/// 链上支付确认服务
+pub struct PaymentService {
+    db: sqlx::MySqlPool,
+    watchers: Arc<ChainWatchers>,
+    blockchains: BlockchainService,
+    order_service: OrderService,
+}
+
+impl PaymentService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            watchers: state.chain_watchers.clone(),
+            blockchains: BlockchainService::new(state),
+            order_service: OrderService::new(state),
+        }
+    }
+
+    /// 用户主动查询订单支付状态
+    pub async fn confirm_user_order(
+        &self,
+        user_id: u64,
+        order_id: &str,
+    ) -> Result<PaymentConfirmationResponse> {
+        let order = OrderRepo::get_order_by_id_and_user_id(&self.db, order_id, user_id).await?;
+        if order.status != ORDER_STATUS_PENDING {
+            return Err(AppError::Business(
+                "Only pending orders can be paid".to_string(),
+            ));
+        }
+
+        let confirmation = self.confirm_order(&order).await?;
+        if confirmation.transaction_hash.is_none() {
+            return Err(AppError::OrderNotPaid(
+                "No matching on-chain payment found".to_string(),
+            ));
+        }
+
+        Ok(confirmation)
+    }
+
+    /// 后台批量确认待支付订单，返回本轮结算成功的订单数
+    pub async fn confirm_pending_orders(&self) -> Result<usize> {
+        let orders = OrderRepo::get_pending_payment_orders(&self.db, CONFIRM_BATCH_SIZE).await?;
+        let mut paid = 0;
+        for order in orders {
+            match self.confirm_order(&order).await {
+                Ok(confirmation) if confirmation.paid => paid += 1,
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to confirm payment for order {}: {}",
+                        order.order_id,
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(paid)
+    }
+
+    /// 匹配订单的链上转账并检查确认数，达到要求后结算订单
+    pub async fn confirm_order(&self, order: &Order) -> Result<PaymentConfirmationResponse> {
+        let watcher = self.watchers.get(&order.blockchain_type)?;
+        // 已从链配置中移除的链沿用监听器的确认数，保证存量订单仍能确认
+        let required = match self.blockchains.get_blockchain(&order.blockchain_type).await {
+            Ok(blockchain) => blockchain.confirmations,
+            Err(AppError::Validation(_)) => watcher.required_confirmations(),
+            Err(err) => return Err(err),
+        };
+        let mut response = PaymentConfirmationResponse {
+            order_number: order.order_id.clone(),
+            paid: false,
+            transaction_hash: None,
+            confirmations: 0,
+            required_confirmations: required,
+        };
+
+        let tx_hash = match &order.transaction_hash {
+            Some(tx_hash) => tx_hash.clone(),
+            None => {
+                let transfers = watcher
+                    .incoming_transfers(&order.blockchain_address, order.created_at)
+                    .await?;
+                match self.find_unclaimed_transfer(order, transfers).await? {
+                    Some(transfer) => transfer.tx_hash,
+                    None => return Ok(response),
+                }
+            }
+        };
+
+        let confirmations = watcher.confirmations(&tx_hash).await?;
+        let confirmations = u32::try_from(confirmations).unwrap_or(u32::MAX);
+        response.transaction_hash = Some(tx_hash.clone());
+        response.confirmations = confirmations;
+        if (confirmations as u64) < required {
+            OrderRepo::update_payment_confirmations(&self.db, &order.order_id, &tx_hash, confirmations)
+                .await?;
+            return Ok(response);
+        }
+
+        self.order_service
+            .settle_paid_order(order, &tx_hash, confirmations)
+            .await?;
+        tracing::info!(
+            "Order {} paid on {} with tx {} ({} confirmations)",
+            order.order_id,
+            order.blockchain_type,
+            tx_hash,
+            confirmations
+        );
+        response.paid = true;
+        Ok(response)
+    }
+
+    /// 在候选转账中找到金额匹配且未被其他订单认领的一笔
+    async fn find_unclaimed_transfer(
+        &self,
+        order: &Order,
+        transfers: Vec<ChainTransfer>,
+    ) -> Result<Option<ChainTransfer>> {
+        for transfer in match_transfers(transfers, order.coin_pay, order.created_at) {
+            if !OrderRepo::exists_transaction_hash(&self.db, &transfer.tx_hash, &order.order_id)
+                .await?
+            {
+                return Ok(Some(transfer));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// 过滤出金额与待付金额一致、且不早于下单时间的转账，按时间先后排序
+pub fn match_transfers(
+    transfers: Vec<ChainTransfer>,
+    amount: Decimal,
+    since: OffsetDateTime,
+) -> Vec<ChainTransfer> {
+    let mut matched: Vec<ChainTransfer> = transfers
+        .into_iter()
+        .filter(|t| t.amount == amount)
+        .filter(|t| t.timestamp.is_none_or(|ts| ts >= since))
+        .collect();
+    matched.sort_by_key(|t| t.timestamp);
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use time::Duration;
+
+    fn transfer(hash: &str, amount: &str, timestamp: OffsetDateTime) -> ChainTransfer {
+        ChainTransfer {
+            tx_hash: hash.to_string(),
+            from_address: "TSender".to_string(),
+            to_address: "TAddr".to_string(),
+            amount: Decimal::from_str(amount).unwrap(),
+            timestamp: Some(timestamp),
+        }
+    }
+
+    #[test]
+    fn test_match_transfers_by_amount_and_time() {
+        let created = OffsetDateTime::now_utc();
+        let transfers = vec![
+            transfer("late", "500.00", created + Duration::minutes(10)),
+            transfer("before", "500", created - Duration::minutes(1)),
+            transfer("wrong", "499.99", created + Duration::minutes(1)),
+            transfer("early", "500.000000", created + Duration::minutes(2)),
+        ];
+
+        let matched = match_transfers(transfers, Decimal::from_str("500.00000000").unwrap(), created);
+        let hashes: Vec<&str> = matched.iter().map(|t| t.tx_hash.as_str()).collect();
+        assert_eq!(hashes, vec!["early", "late"]);
+    }
+}
@@ -0,0 +1,369 @@
+use crate::chain::error::ChainError;
+use crate::chain::payout::{PayoutProvider, PayoutProviders, PayoutRequest, PayoutStatus};
+use crate::model::withdrawal::{
+    payout_backoff, Withdrawal, WithdrawalStatus, PAYOUT_CONFIRM_TIMEOUT_MINUTES,
+    PAYOUT_MAX_ATTEMPTS,
+};
+use crate::repository::{TransactionsRepo, WithdrawalRepo};
+use crate::service::blockchain::BlockchainService;
+use crate::service::withdrawal::WithdrawalService;
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use std::sync::Arc;
+use time::{Duration, OffsetDateTime};
+
+/// 每轮后台出账处理的最大提现数（每条链）
+const PAYOUT_BATCH_SIZE: u32 = 50;
+
+/// 本轮出账处理结果
+#[derive(Debug, Default)]
+pub struct PayoutSummary {
+    /// 成功广播的提现数
+    pub submitted: usize,
+    /// 达到确认数完成出账的提现数
+    pub completed: usize,
+    /// 签名服务拒绝或链上执行失败、已退回的提现数
+    pub failed: usize,
+    /// 无法确认是否已转出、转入人工复核的提现数
+    pub review: usize,
+}
+
+/// 出账提交失败后的处理方式
+#[derive(Debug, PartialEq, Eq)]
+pub enum PayoutFailure {
+    /// 按退避时间重试，签名服务按提现单号去重，不会重复转账
+    Retry,
+    /// 签名服务明确拒绝，交易未广播，解冻退回
+    Refund,
+    /// 超过重试上限仍无法确认是否已转出（超时、5xx），转入人工复核
+    Review,
+}
+
+/// 第 attempts 次出账提交失败后的处理方式：只有签名服务明确拒绝时退回
+pub fn payout_failure(err: &ChainError, attempts: u32) -> PayoutFailure {
+    match err {
+        ChainError::Rejected(_) => PayoutFailure::Refund,
+        _ if attempts < PAYOUT_MAX_ATTEMPTS => PayoutFailure::Retry,
+        _ => PayoutFailure::Review,
+    }
+}
+
+/// 已广播的出账是否超过确认期限仍未上链
+pub fn payout_overdue(submitted_at: Option<OffsetDateTime>, now: OffsetDateTime) -> bool {
+    submitted_at
+        .is_some_and(|at| now - at > Duration::minutes(PAYOUT_CONFIRM_TIMEOUT_MINUTES))
+}
+
+/// 提现出账服务：广播审核通过的提现，轮询确认数，失败按退避重试；签名服务明确拒绝或链上执行失败时退回，
+/// 超过重试上限或广播后长时间未上链等无法确认结果的提现转入人工复核
+pub struct PayoutService {
+    db: sqlx::MySqlPool,
+    providers: Arc<PayoutProviders>,
+    blockchains: BlockchainService,
+}
+
+impl PayoutService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            providers: state.payout_providers.clone(),
+            blockchains: BlockchainService::new(state),
+        }
+    }
+
+    /// 后台处理一轮出账：先确认已广播的提现，再提交到期的提现
+    pub async fn process_payouts(&self) -> Result<PayoutSummary> {
+        let mut summary = PayoutSummary::default();
+        for chain in self.providers.chains() {
+            let provider = self.providers.get(&chain)?;
+            // 已从链配置中移除的链沿用广播器的确认数，保证存量出账仍能完成
+            let required = match self.blockchains.get_blockchain(&chain).await {
+                Ok(blockchain) => blockchain.confirmations,
+                Err(AppError::Validation(_)) => provider.required_confirmations(),
+                Err(err) => return Err(err),
+            };
+
+            let unconfirmed =
+                WithdrawalRepo::get_payout_unconfirmed(&self.db, &chain, PAYOUT_BATCH_SIZE).await?;
+            for withdrawal in unconfirmed {
+                match self.confirm_payout(provider.as_ref(), required, &withdrawal).await {
+                    Ok(Some(WithdrawalStatus::Completed)) => summary.completed += 1,
+                    Ok(Some(WithdrawalStatus::Review)) => summary.review += 1,
+                    Ok(Some(_)) => summary.failed += 1,
+                    Ok(None) => {}
+                    Err(err) => {
+                        tracing::error!(
+                            "Failed to confirm payout for withdrawal {}: {}",
+                            withdrawal.withdrawal_id,
+                            err
+                        );
+                    }
+                }
+            }
+
+            let now = TimeZone::Beijing.get_time();
+            let due =
+                WithdrawalRepo::get_payout_due(&self.db, &chain, now, PAYOUT_BATCH_SIZE).await?;
+            for withdrawal in due {
+                match self.submit_payout(provider.as_ref(), &withdrawal).await {
+                    Ok(Some(WithdrawalStatus::Processing)) => summary.submitted += 1,
+                    Ok(Some(WithdrawalStatus::Review)) => summary.review += 1,
+                    Ok(Some(_)) => summary.failed += 1,
+                    Ok(None) => {}
+                    Err(err) => {
+                        tracing::error!(
+                            "Failed to submit payout for withdrawal {}: {}",
+                            withdrawal.withdrawal_id,
+                            err
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// 广播一笔提现。返回 Processing 表示已广播，Failed 表示签名服务拒绝已退回，
+    /// Review 表示超过重试上限转入人工复核，None 表示本次未广播（被其他实例占用或等待重试）
+    async fn submit_payout(
+        &self,
+        provider: &dyn PayoutProvider,
+        withdrawal: &Withdrawal,
+    ) -> Result<Option<WithdrawalStatus>> {
+        let attempts = withdrawal.payout_attempts + 1;
+        let now = TimeZone::Beijing.get_time();
+        // 先占用本次尝试并推迟下次提交时间，广播失败时即按退避时间重试
+        if !WithdrawalRepo::claim_payout(
+            &self.db,
+            &withdrawal.withdrawal_id,
+            withdrawal.payout_attempts,
+            now + payout_backoff(attempts),
+        )
+        .await?
+        {
+            return Ok(None);
+        }
+
+        let request = PayoutRequest {
+            request_id: withdrawal.withdrawal_id.clone(),
+            to_address: withdrawal.destination_address.clone(),
+            amount: withdrawal.receive_amount(),
+        };
+        let err = match provider.submit(&request).await {
+            Ok(tx_hash) => {
+                let mut tx = self.db.begin().await?;
+                WithdrawalRepo::tx_set_payout_hash(
+                    &mut tx,
+                    &withdrawal.withdrawal_id,
+                    &tx_hash,
+                    TimeZone::Beijing.get_time(),
+                )
+                .await?;
+                TransactionsRepo::tx_update_hash_by_transaction_id(
+                    &mut tx,
+                    &withdrawal.withdrawal_id,
+                    &tx_hash,
+                )
+                .await?;
+                tx.commit().await?;
+                tracing::info!(
+                    "Withdrawal {} submitted on {} with tx {}",
+                    withdrawal.withdrawal_id,
+                    withdrawal.blockchain_type,
+                    tx_hash
+                );
+                return Ok(Some(WithdrawalStatus::Processing));
+            }
+            Err(err) => err,
+        };
+
+        let action = payout_failure(&err, attempts);
+        let err = err.to_string();
+        match action {
+            PayoutFailure::Retry => {
+                tracing::warn!(
+                    "Payout attempt {}/{} for withdrawal {} failed: {}",
+                    attempts,
+                    PAYOUT_MAX_ATTEMPTS,
+                    withdrawal.withdrawal_id,
+                    err
+                );
+                let mut conn = self.db.acquire().await?;
+                WithdrawalRepo::tx_set_payout_error(&mut conn, &withdrawal.withdrawal_id, &err)
+                    .await?;
+                Ok(None)
+            }
+            PayoutFailure::Refund => {
+                tracing::error!(
+                    "Payout for withdrawal {} was rejected: {}",
+                    withdrawal.withdrawal_id,
+                    err
+                );
+                self.fail_payout(withdrawal, &err).await?;
+                Ok(Some(WithdrawalStatus::Failed))
+            }
+            PayoutFailure::Review => {
+                tracing::error!(
+                    "Payout for withdrawal {} still unresolved after {} attempts, needs manual review: {}",
+                    withdrawal.withdrawal_id,
+                    attempts,
+                    err
+                );
+                self.review_payout(withdrawal, &err).await?;
+                Ok(Some(WithdrawalStatus::Review))
+            }
+        }
+    }
+
+    /// 检查已广播提现的链上状态，达到确认数后完成出账，执行失败则退回，
+    /// 超过确认期限仍未上链或查询不到交易时转入人工复核。返回提现的新状态，仍在确认中时返回 None
+    async fn confirm_payout(
+        &self,
+        provider: &dyn PayoutProvider,
+        required_confirmations: u64,
+        withdrawal: &Withdrawal,
+    ) -> Result<Option<WithdrawalStatus>> {
+        let Some(tx_hash) = &withdrawal.transaction_hash else {
+            return Ok(None);
+        };
+        let overdue = payout_overdue(withdrawal.payout_submitted_at, TimeZone::Beijing.get_time());
+        let status = match provider.status(tx_hash).await {
+            Ok(status) => status,
+            // 交易被丢弃后节点可能查询不到，超过期限同样转入人工复核
+            Err(err) if overdue => {
+                tracing::warn!("Failed to query payout tx {}: {}", tx_hash, err);
+                PayoutStatus::Pending { confirmations: 0 }
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let confirmations = match status {
+            PayoutStatus::Pending { confirmations: 0 } if overdue => {
+                tracing::error!(
+                    "Payout tx {} for withdrawal {} not mined within {} minutes, needs manual review",
+                    tx_hash,
+                    withdrawal.withdrawal_id,
+                    PAYOUT_CONFIRM_TIMEOUT_MINUTES
+                );
+                self.review_payout(
+                    withdrawal,
+                    &format!("transaction {} not mined in time", tx_hash),
+                )
+                .await?;
+                return Ok(Some(WithdrawalStatus::Review));
+            }
+            PayoutStatus::Pending { confirmations } => confirmations,
+            PayoutStatus::Failed(reason) => {
+                tracing::error!(
+                    "Payout tx {} for withdrawal {} failed on chain: {}",
+                    tx_hash,
+                    withdrawal.withdrawal_id,
+                    reason
+                );
+                self.fail_payout(withdrawal, &format!("transaction {} failed: {}", tx_hash, reason))
+                    .await?;
+                return Ok(Some(WithdrawalStatus::Failed));
+            }
+        };
+
+        let confirmations = u32::try_from(confirmations).unwrap_or(u32::MAX);
+        let mut tx = self.db.begin().await?;
+        WithdrawalRepo::tx_update_confirmations(&mut tx, &withdrawal.withdrawal_id, confirmations)
+            .await?;
+        if (confirmations as u64) < required_confirmations {
+            tx.commit().await?;
+            return Ok(None);
+        }
+
+        let completed = WithdrawalService::tx_transition(
+            &mut tx,
+            withdrawal,
+            WithdrawalStatus::Processing,
+            WithdrawalStatus::Completed,
+        )
+        .await?;
+        tx.commit().await?;
+        if !completed {
+            return Ok(None);
+        }
+        tracing::info!(
+            "Withdrawal {} completed on {} with tx {} ({} confirmations)",
+            withdrawal.withdrawal_id,
+            withdrawal.blockchain_type,
+            tx_hash,
+            confirmations
+        );
+
+        Ok(Some(WithdrawalStatus::Completed))
+    }
+
+    /// 将处理中的提现转入人工复核，冻结资金保留
+    async fn review_payout(&self, withdrawal: &Withdrawal, reason: &str) -> Result<()> {
+        let mut tx = self.db.begin().await?;
+        let reviewed = WithdrawalService::tx_transition(
+            &mut tx,
+            withdrawal,
+            WithdrawalStatus::Processing,
+            WithdrawalStatus::Review,
+        )
+        .await?;
+        if !reviewed {
+            tx.rollback().await?;
+            return Err(AppError::Business(format!(
+                "Withdrawal {} is no longer processing",
+                withdrawal.withdrawal_id
+            )));
+        }
+        WithdrawalRepo::tx_set_payout_error(&mut tx, &withdrawal.withdrawal_id, reason).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// 将处理中的提现标记为失败并解冻退回
+    async fn fail_payout(&self, withdrawal: &Withdrawal, reason: &str) -> Result<()> {
+        let mut tx = self.db.begin().await?;
+        let failed = WithdrawalService::tx_transition(
+            &mut tx,
+            withdrawal,
+            WithdrawalStatus::Processing,
+            WithdrawalStatus::Failed,
+        )
+        .await?;
+        if !failed {
+            tx.rollback().await?;
+            return Err(AppError::Business(format!(
+                "Withdrawal {} is no longer processing",
+                withdrawal.withdrawal_id
+            )));
+        }
+        WithdrawalRepo::tx_set_payout_error(&mut tx, &withdrawal.withdrawal_id, reason).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payout_failure() {
+        let timeout = ChainError::RequestError("operation timed out".to_string());
+        let rejected = ChainError::Rejected("400 insufficient balance".to_string());
+        assert_eq!(payout_failure(&timeout, 1), PayoutFailure::Retry);
+        assert_eq!(payout_failure(&timeout, PAYOUT_MAX_ATTEMPTS), PayoutFailure::Review);
+        assert_eq!(payout_failure(&rejected, 1), PayoutFailure::Refund);
+        assert_eq!(payout_failure(&rejected, PAYOUT_MAX_ATTEMPTS), PayoutFailure::Refund);
+    }
+
+    #[test]
+    fn test_payout_overdue() {
+        let now = OffsetDateTime::now_utc();
+        let timeout = Duration::minutes(PAYOUT_CONFIRM_TIMEOUT_MINUTES);
+        assert!(!payout_overdue(None, now));
+        assert!(!payout_overdue(Some(now - timeout + Duration::minutes(1)), now));
+        assert!(payout_overdue(Some(now - timeout - Duration::minutes(1)), now));
+    }
+}
@@ -1,6 +1,7 @@
+use crate::utils::time_zone::TimeZone;
 use crate::{
     error::Result, model::power::PowerPackage, repository::power_repo::PowerRepo,
-    schema::power::PowerPackageResponse, state::AppState,
+    schema::power::PowerPackageResponse, state::AppState, AppError,
 };
 
 pub struct PowerService {
@@ -19,6 +20,22 @@ impl PowerService {
         PowerRepo::get_power_record_by_id(&self.db, power_id).await
     }
 
+    /// 开启算力加速：刷新加速时间并追加当日（北京时间）加速记录，收益结算按加速记录判断当日是否生效
+    pub async fn start_power(&self, user_id: u64, user_power_id: u64) -> Result<()> {
+        let now = TimeZone::Beijing.get_time();
+        let mut tx = self.db.begin().await?;
+        if !PowerRepo::start_user_power_record(&mut tx, user_id, user_power_id, now).await? {
+            tx.rollback().await?;
+            return Err(AppError::NotFound(
+                "Computing power package not found".to_string(),
+            ));
+        }
+        PowerRepo::tx_record_acceleration(&mut tx, user_id, user_power_id, now.date()).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     pub async fn get_packages(&self) -> Result<Vec<PowerPackageResponse>> {
         // 暂时返回空实现
         Ok(vec![])
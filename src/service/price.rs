@@ -0,0 +1,66 @@
+use crate::model::exchange::DG_USDT_SYMBOL;
+use crate::model::price::PriceSyncSummary;
+use crate::price::PriceSource;
+use crate::repository::PriceRepo;
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use time::{Date, Duration};
+
+/// 行情服务：从行情数据源同步 DG/USDT 实时价格与日K线，供兑换报价与每日收益结算使用
+pub struct PriceService {
+    db: sqlx::MySqlPool,
+    source: Option<Arc<dyn PriceSource>>,
+    sync_days: i64,
+}
+
+impl PriceService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            source: state.price_source.clone(),
+            sync_days: state.config.price.sync_days,
+        }
+    }
+
+    /// 同步实时价格与最近 sync_days 天的日K线，未配置数据源时不做任何处理
+    pub async fn sync_prices(&self) -> Result<PriceSyncSummary> {
+        let Some(source) = &self.source else {
+            return Ok(PriceSyncSummary::default());
+        };
+        let ticker = source.ticker(DG_USDT_SYMBOL).await?;
+        PriceRepo::upsert_real_time_price(&self.db, DG_USDT_SYMBOL, &ticker).await?;
+
+        let today = TimeZone::Beijing.get_time().date();
+        let from = today - Duration::days(self.sync_days.max(1) - 1);
+        let candles = self.sync_daily_candles(from, today).await?;
+
+        Ok(PriceSyncSummary {
+            ticker_updated: true,
+            candles,
+        })
+    }
+
+    /// 同步 [from, to] 范围内的日K线，返回写入条数
+    pub async fn sync_daily_candles(&self, from: Date, to: Date) -> Result<usize> {
+        let Some(source) = &self.source else {
+            return Ok(0);
+        };
+        let candles = source.daily_candles(DG_USDT_SYMBOL, from, to).await?;
+        PriceRepo::upsert_daily_candles(&self.db, DG_USDT_SYMBOL, &candles).await?;
+
+        Ok(candles.len())
+    }
+
+    /// 获取 DG 某个交易日（北京时间）的收盘价（USDT），没有行情时拒绝结算
+    pub async fn get_daily_close(&self, date: Date) -> Result<Decimal> {
+        match PriceRepo::get_daily_close(&self.db, DG_USDT_SYMBOL, date).await? {
+            Some(close) if close > Decimal::ZERO => Ok(close),
+            _ => Err(AppError::Business(format!(
+                "DG close price of {} is unavailable",
+                date
+            ))),
+        }
+    }
+}
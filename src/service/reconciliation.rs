@@ -0,0 +1,145 @@
+use crate::model::ledger::{ledger_drift, LedgerBalance};
+use crate::model::reconciliation::{
+    ReconciliationConfig, ReconciliationReport, ReconciliationSummary,
+};
+use crate::model::system_config::SystemConfigType;
+use crate::model::wallet::{UserAsset, Wallet};
+use crate::repository::{
+    AssetRepo, ReconciliationRepo, SystemConfigRepo, TransactionsRepo, UserRepo,
+};
+use crate::{error::Result, state::AppState, AppError};
+use sqlx::Connection;
+use std::collections::HashMap;
+use time::Date;
+
+/// 每批对账的用户数
+const RECONCILIATION_BATCH_SIZE: u32 = 500;
+
+/// 余额对账服务：由账本分录推导每个用户的可用/冻结余额并与 user_assets 核对，
+/// 差异写入对账报告，超过阈值时按配置冻结用户资金
+pub struct ReconciliationService {
+    db: sqlx::MySqlPool,
+}
+
+impl ReconciliationService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+        }
+    }
+
+    /// 获取对账配置，未配置时只报告不冻结
+    pub async fn get_config(&self) -> Result<ReconciliationConfig> {
+        let config = SystemConfigRepo::get_config_by_key(
+            &self.db,
+            &SystemConfigType::Reconciliation.to_string(),
+        )
+        .await;
+        match config {
+            Ok(config) => {
+                let config: ReconciliationConfig = serde_json::from_str(&config.config_value)?;
+                config.validate().map_err(|e| {
+                    AppError::Internal(format!("Invalid reconciliation config: {}", e))
+                })?;
+                Ok(config)
+            }
+            Err(AppError::NotFound(_)) => Ok(ReconciliationConfig::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 按用户 ID 分批核对所有已开户用户，返回本次对账结果
+    pub async fn reconcile(&self, run_date: Date) -> Result<ReconciliationSummary> {
+        let config = self.get_config().await?;
+        let mut summary = ReconciliationSummary::default();
+        let mut after_user_id = 0;
+        loop {
+            let user_ids =
+                AssetRepo::get_user_ids_after(&self.db, after_user_id, RECONCILIATION_BATCH_SIZE)
+                    .await?;
+            let (Some(&first), Some(&last)) = (user_ids.first(), user_ids.last()) else {
+                break;
+            };
+
+            let (batch_assets, batch_balances) = self.read_batch(first, last).await?;
+            let mut assets: HashMap<u64, Vec<UserAsset>> = HashMap::new();
+            for asset in batch_assets {
+                assets.entry(asset.user_id).or_default().push(asset);
+            }
+            let mut balances: HashMap<u64, Vec<LedgerBalance>> = HashMap::new();
+            for balance in batch_balances {
+                balances.entry(balance.user_id).or_default().push(balance);
+            }
+
+            for user_id in user_ids {
+                let wallet = Wallet::new(assets.remove(&user_id).unwrap_or_default());
+                let drifts = ledger_drift(&wallet, &balances.remove(&user_id).unwrap_or_default());
+                summary.users += 1;
+                if drifts.is_empty() {
+                    continue;
+                }
+
+                let freeze = config.should_freeze(&drifts);
+                if freeze && UserRepo::set_funds_frozen(&self.db, user_id, true).await? {
+                    summary.frozen += 1;
+                    tracing::warn!("Froze funds of user {} after reconciliation drift", user_id);
+                }
+                tracing::warn!(
+                    "Reconciliation found {} mismatched accounts for user {}: {:?}",
+                    drifts.len(),
+                    user_id,
+                    drifts
+                );
+                summary.mismatches += drifts.len();
+                ReconciliationRepo::save_reports(&self.db, run_date, user_id, &drifts, freeze)
+                    .await?;
+            }
+            after_user_id = last;
+        }
+
+        Ok(summary)
+    }
+
+    /// 在同一个一致性快照（REPEATABLE READ）中读取一批用户的资产与账本余额，
+    /// 避免两次读取之间提交的记账被误判为差异
+    async fn read_batch(
+        &self,
+        first: u64,
+        last: u64,
+    ) -> Result<(Vec<UserAsset>, Vec<LedgerBalance>)> {
+        let mut conn = self.db.acquire().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(&mut *conn)
+            .await?;
+        let mut tx = conn.begin_with("START TRANSACTION WITH CONSISTENT SNAPSHOT").await?;
+        let assets = AssetRepo::tx_get_assets_in_range(&mut tx, first, last).await?;
+        let balances = TransactionsRepo::tx_get_ledger_balances_in_range(&mut tx, first, last).await?;
+        tx.commit().await?;
+
+        Ok((assets, balances))
+    }
+
+    /// 分页获取对账差异报告
+    pub async fn get_reports(
+        &self,
+        run_date: Option<Date>,
+        user_id: Option<u64>,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<ReconciliationReport>, u64)> {
+        ReconciliationRepo::get_reports(&self.db, run_date, user_id, page, limit).await
+    }
+
+    /// 管理员核查后解除用户资金冻结
+    pub async fn unfreeze_user(&self, user_id: u64) -> Result<()> {
+        if !UserRepo::set_funds_frozen(&self.db, user_id, false).await? {
+            return Err(AppError::Business(format!(
+                "Funds of user {} are not frozen",
+                user_id
+            )));
+        }
+        tracing::info!("Unfroze funds of user {}", user_id);
+
+        Ok(())
+    }
+}
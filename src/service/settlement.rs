@@ -0,0 +1,394 @@
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
+use crate::model::settlement::{
+    mining_earning, SettlementReport, SettlementRun, SettlementRunStatus,
+};
+use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
+use crate::model::UserPower;
+use crate::repository::power_repo::PowerRepo;
+use crate::repository::{SettlementRepo, TransactionsRepo};
+use crate::service::price::PriceService;
+use crate::utils::money::{Currency, Money};
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use time::{Date, Duration};
+
+/// 每批结算的用户数
+const SETTLEMENT_BATCH_SIZE: u32 = 200;
+
+/// 结算中的批次超过该时间（分钟）无进展时视为进程已中断，允许接管
+const SETTLEMENT_STALE_MINUTES: i64 = 10;
+
+/// 单次补结算的最大天数
+const MAX_BACKFILL_DAYS: i64 = 31;
+
+/// 每日挖矿收益结算服务：按 DG 日收盘价折算算力收益，写入收益记录、累加算力累计收益，
+/// 并由奖励科目记入用户 DG 可用余额。每个交易日一个结算批次，按用户 ID 分批入账，
+/// 中断后从断点继续，同一交易日不会重复结算
+pub struct SettlementService {
+    db: sqlx::MySqlPool,
+    price: PriceService,
+}
+
+impl SettlementService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            price: PriceService::new(state),
+        }
+    }
+
+    /// 结算刚结束的北京时间交易日
+    pub async fn settle_daily_earnings(&self) -> Result<SettlementRun> {
+        let yesterday = TimeZone::Beijing.get_time().date() - Duration::days(1);
+        self.settle(yesterday).await
+    }
+
+    /// 结算指定交易日：已完成的直接返回，失败或中断的从断点继续，没有收盘价时拒绝结算
+    pub async fn settle(&self, business_date: Date) -> Result<SettlementRun> {
+        let now = TimeZone::Beijing.get_time();
+        if business_date >= now.date() {
+            return Err(AppError::Validation(format!(
+                "Cannot settle {} before the day ends",
+                business_date
+            )));
+        }
+
+        let run = match SettlementRepo::get_run(&self.db, business_date).await? {
+            Some(run) if run.status == SettlementRunStatus::Completed.to_string() => {
+                return Ok(run);
+            }
+            Some(run) => {
+                // 接管失败的批次或中断的批次，正在进行中的批次不重复执行
+                let stale_before = now - Duration::minutes(SETTLEMENT_STALE_MINUTES);
+                if !SettlementRepo::claim_run(&self.db, business_date, stale_before).await? {
+                    return Err(AppError::Conflict(format!(
+                        "Settlement of {} is already running",
+                        business_date
+                    )));
+                }
+                run
+            }
+            None => {
+                self.ensure_source_data(business_date).await?;
+                let close_price = self.get_close_price(business_date).await?;
+                if !SettlementRepo::create_run(&self.db, business_date, close_price).await? {
+                    return Err(AppError::Conflict(format!(
+                        "Settlement of {} is already running",
+                        business_date
+                    )));
+                }
+                self.get_run(business_date).await?
+            }
+        };
+
+        if let Err(err) = self.process(&run).await {
+            if matches!(err, AppError::Conflict(_)) {
+                // 批次已被其他进程接管，由接管方负责结束
+                return Err(err);
+            }
+            tracing::error!("Settlement of {} failed: {}", business_date, err);
+            SettlementRepo::finish_run(
+                &self.db,
+                business_date,
+                SettlementRunStatus::Failed,
+                Some(&err.to_string()),
+            )
+            .await?;
+            return Err(err);
+        }
+        SettlementRepo::finish_run(&self.db, business_date, SettlementRunStatus::Completed, None)
+            .await?;
+
+        self.get_run(business_date).await
+    }
+
+    async fn get_run(&self, business_date: Date) -> Result<SettlementRun> {
+        SettlementRepo::get_run(&self.db, business_date)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Settlement of {} not found", business_date)))
+    }
+
+    /// 结算资格来自算力加速记录，交易日没有任何加速记录时拒绝结算，避免把无数据的日期标记为已完成
+    async fn ensure_source_data(&self, business_date: Date) -> Result<()> {
+        if !PowerRepo::has_accelerations(&self.db, business_date).await? {
+            return Err(AppError::Validation(format!(
+                "No acceleration records for {}, cannot settle",
+                business_date
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 获取交易日收盘价，结算前先尝试从行情数据源补齐
+    async fn get_close_price(&self, business_date: Date) -> Result<Decimal> {
+        if let Err(err) = self.price.sync_daily_candles(business_date, business_date).await {
+            tracing::error!("Failed to sync DG close price of {}: {}", business_date, err);
+        }
+        self.price.get_daily_close(business_date).await
+    }
+
+    /// 补结算 [from, to] 范围内的交易日，逐日返回结果，单日失败不影响其他日期。
+    /// 试运行时只按历史收盘价计算预计入账数据，不写入任何记录
+    pub async fn backfill(
+        &self,
+        from: Date,
+        to: Date,
+        dry_run: bool,
+    ) -> Result<Vec<SettlementReport>> {
+        if from > to {
+            return Err(AppError::Validation(
+                "Start date must not be after end date".to_string(),
+            ));
+        }
+        if (to - from).whole_days() >= MAX_BACKFILL_DAYS {
+            return Err(AppError::Validation(format!(
+                "Backfill range must not exceed {} days",
+                MAX_BACKFILL_DAYS
+            )));
+        }
+        if to >= TimeZone::Beijing.get_time().date() {
+            return Err(AppError::Validation(format!(
+                "Cannot settle {} before the day ends",
+                to
+            )));
+        }
+        // 先批量补齐历史收盘价，缺失的日期逐日结算时再尝试一次
+        if let Err(err) = self.price.sync_daily_candles(from, to).await {
+            tracing::error!("Failed to sync DG close prices of {} - {}: {}", from, to, err);
+        }
+
+        let mut reports = Vec::new();
+        let mut date = from;
+        while date <= to {
+            let report = if dry_run {
+                self.preview(date).await
+            } else {
+                self.settle(date).await.map(SettlementReport::from)
+            };
+            reports.push(report.unwrap_or_else(|err| {
+                tracing::error!("Backfill settlement of {} failed: {}", date, err);
+                SettlementReport::failed(date, err.to_string())
+            }));
+            date += Duration::days(1);
+        }
+
+        Ok(reports)
+    }
+
+    /// 试运行：计算交易日尚未入账部分的预计入账数据
+    async fn preview(&self, business_date: Date) -> Result<SettlementReport> {
+        let run = SettlementRepo::get_run(&self.db, business_date).await?;
+        if let Some(run) = run.as_ref() {
+            if run.status == SettlementRunStatus::Completed.to_string() {
+                return Ok(SettlementReport {
+                    message: Some("Already settled".to_string()),
+                    ..SettlementReport::from(run.clone())
+                });
+            }
+        }
+        let (close_price, mut after_user_id) = match run.as_ref() {
+            Some(run) => (run.close_price, run.checkpoint_user_id),
+            None => {
+                self.ensure_source_data(business_date).await?;
+                (self.price.get_daily_close(business_date).await?, 0)
+            }
+        };
+
+        let mut report = SettlementReport {
+            business_date,
+            status: run.map(|run| run.status),
+            close_price: Some(close_price),
+            users: 0,
+            records: 0,
+            credited: Decimal::ZERO,
+            message: Some("Dry run, nothing was credited".to_string()),
+        };
+        while let Some((last, powers)) = self.next_batch(business_date, after_user_id).await? {
+            for user_powers in powers.chunk_by(|a, b| a.user_id == b.user_id) {
+                report.users += 1;
+                for power in user_powers {
+                    let earning =
+                        mining_earning(power.amount, power.daily_yield_percentage, close_price);
+                    report.records += 1;
+                    report.credited += earning.amount();
+                }
+            }
+            after_user_id = last;
+        }
+
+        Ok(report)
+    }
+
+    /// 获取断点之后下一批用户在交易日内生效的算力，返回本批最大用户 ID 与按用户排序的算力
+    async fn next_batch(
+        &self,
+        business_date: Date,
+        after_user_id: u64,
+    ) -> Result<Option<(u64, Vec<UserPower>)>> {
+        let user_ids = PowerRepo::get_started_user_ids(
+            &self.db,
+            business_date,
+            after_user_id,
+            SETTLEMENT_BATCH_SIZE,
+        )
+        .await?;
+        let (Some(&first), Some(&last)) = (user_ids.first(), user_ids.last()) else {
+            return Ok(None);
+        };
+        let powers =
+            PowerRepo::get_started_powers_in_range(&self.db, business_date, first, last).await?;
+
+        Ok(Some((last, powers)))
+    }
+
+    /// 从断点之后的用户开始分批入账
+    async fn process(&self, run: &SettlementRun) -> Result<()> {
+        let mut after_user_id = run.checkpoint_user_id;
+        while let Some((last, powers)) = self.next_batch(run.business_date, after_user_id).await? {
+            for user_powers in powers.chunk_by(|a, b| a.user_id == b.user_id) {
+                self.settle_user(run, user_powers[0].user_id, user_powers).await?;
+            }
+            after_user_id = last;
+        }
+
+        Ok(())
+    }
+
+    /// 在同一事务中写入用户各算力的收益记录、累加算力累计收益、入账并推进断点
+    async fn settle_user(
+        &self,
+        run: &SettlementRun,
+        user_id: u64,
+        powers: &[UserPower],
+    ) -> Result<()> {
+        let mut tx = self.db.begin().await?;
+        let mut records = 0;
+        let mut total = Money::zero(Currency::Dg);
+        for power in powers {
+            let earning =
+                mining_earning(power.amount, power.daily_yield_percentage, run.close_price);
+            if !PowerRepo::tx_insert_power_earning(
+                &mut tx,
+                power,
+                &run.close_price,
+                &earning.amount(),
+                run.business_date,
+            )
+            .await?
+            {
+                continue;
+            }
+            PowerRepo::tx_add_earnings(&mut tx, power.id, &earning.amount()).await?;
+            records += 1;
+            total = total + earning;
+        }
+
+        if total.is_positive() {
+            let curr_time = TimeZone::Beijing.get_time();
+            let entry = JournalEntry::new(LedgerAccount::Available(user_id)).transfer(
+                LedgerAccount::System(SystemAccount::Rewards),
+                LedgerAccount::Available(user_id),
+                total,
+            );
+            let mut builder = TransactionsBuilder::default();
+            builder
+                .types(OrderType::MiningEarning.to_string())
+                .exchange_rate(run.close_price)
+                .status(OrderStatus::Completed.to_string())
+                .description(format!("mining earning of {}", run.business_date))
+                .created_at(curr_time)
+                .updated_at(curr_time)
+                .completed_at(Some(curr_time));
+            TransactionsRepo::tx_record(&mut tx, &entry, builder).await?;
+        }
+        if !SettlementRepo::tx_checkpoint(
+            &mut tx,
+            run.business_date,
+            user_id,
+            records,
+            total.amount(),
+        )
+        .await?
+        {
+            tx.rollback().await?;
+            return Err(AppError::Conflict(format!(
+                "Settlement of {} was taken over by another worker",
+                run.business_date
+            )));
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::USER_POWER_RECORD_STATUS_ACTIVE;
+    use sqlx::MySqlPool;
+
+    /// 需要已执行迁移的数据库：cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL"]
+    async fn test_backfill_eligibility_survives_newer_acceleration() {
+        let pool = MySqlPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let now = TimeZone::Beijing.get_time();
+        let user_id = 9_000_000_000 + now.unix_timestamp() as u64;
+        let past = now.date() - Duration::days(3);
+
+        let mut tx = pool.begin().await.unwrap();
+        let power_id = PowerRepo::create_user_power_record(
+            &mut tx,
+            user_id,
+            1,
+            &format!("TEST{}", user_id),
+            0,
+            &Decimal::from(100),
+            USER_POWER_RECORD_STATUS_ACTIVE,
+            1,
+            &Decimal::ONE,
+        )
+        .await
+        .unwrap();
+        PowerRepo::tx_record_acceleration(&mut tx, user_id, power_id, past)
+            .await
+            .unwrap();
+        // 之后再次加速会覆盖 start_time，但不影响历史交易日的结算资格
+        assert!(PowerRepo::start_user_power_record(&mut tx, user_id, power_id, now)
+            .await
+            .unwrap());
+        PowerRepo::tx_record_acceleration(&mut tx, user_id, power_id, now.date())
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let powers = PowerRepo::get_started_powers_in_range(&pool, past, user_id, user_id)
+            .await
+            .unwrap();
+        let not_accelerated =
+            PowerRepo::get_started_powers_in_range(&pool, past - Duration::days(1), user_id, user_id)
+                .await
+                .unwrap();
+        let has_past = PowerRepo::has_accelerations(&pool, past).await.unwrap();
+
+        sqlx::query("DELETE FROM user_power_accelerations WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM user_power WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(powers.iter().map(|power| power.id).collect::<Vec<_>>(), vec![power_id]);
+        assert!(not_accelerated.is_empty());
+        assert!(has_past);
+    }
+}
@@ -1,11 +1,8 @@
-use std::collections::HashMap;
-
 use crate::{
-    error::Result,
+    error::{AppError, Result},
     model::system_config::{SystemConfig, SystemConfigType},
     repository::system_config_repo::SystemConfigRepo,
     state::AppState,
-    AppError,
 };
 
 /// 系统配置服务
@@ -27,6 +24,7 @@ impl SystemConfigService {
         config_value: &str,
         description: Option<&str>,
     ) -> Result<u64> {
+        Self::validate_config(config_key, config_value)?;
         // 验证配置键是否已存在
         if SystemConfigRepo::exists_config_key(
             &self.db, config_key, None, // 排除当前编辑的配置
@@ -61,6 +59,7 @@ impl SystemConfigService {
         config_value: &str,
         description: Option<&str>,
     ) -> Result<()> {
+        Self::validate_config(key, config_value)?;
         SystemConfigRepo::update_config(&self.db, key, config_value, description).await?;
 
         // (|| crate::error::AppError::Internal("Failed to update configuration".to_string()))?;
@@ -68,22 +67,18 @@ impl SystemConfigService {
         Ok(())
     }
 
+    /// 已知配置类型按结构校验取值，避免写入读取时会被拒绝的配置
+    fn validate_config(key: &str, value: &str) -> Result<()> {
+        match key.parse::<SystemConfigType>() {
+            Ok(config_type) => config_type.validate_value(value).map_err(AppError::Validation),
+            Err(_) => Ok(()),
+        }
+    }
+
     /// 删除系统配置
     pub async fn delete_config(&self, key: &str) -> Result<String> {
         SystemConfigRepo::delete_config(&self.db, key).await?;
 
         Ok("Configuration deleted successfully".to_string())
     }
-
-    pub async fn get_chain_addr(&self, types: &str) -> Result<String> {
-        let config = self
-            .get_config_by_key(SystemConfigType::Blockchain.to_string().as_str())
-            .await?;
-        let mut chain: HashMap<String, String> = serde_json::from_str(&config.config_value)?;
-        let Some(addr) = chain.remove(types) else {
-            return Err(AppError::Validation(format!("Unsupported payment type: {}", types)));
-        };
-
-        Ok(addr)
-    }
 }
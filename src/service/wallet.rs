@@ -0,0 +1,29 @@
+use crate::model::ledger::{ledger_drift, LedgerDrift};
+use crate::model::wallet::Wallet;
+use crate::repository::{AssetRepo, TransactionsRepo};
+use crate::{error::Result, state::AppState};
+
+/// 钱包服务：按币种查询用户可用、冻结余额与累计收益
+pub struct WalletService {
+    db: sqlx::MySqlPool,
+}
+
+impl WalletService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+        }
+    }
+
+    /// 获取用户钱包
+    pub async fn get_wallet(&self, user_id: u64) -> Result<Wallet> {
+        AssetRepo::get_wallet(&self.db, user_id).await
+    }
+
+    /// 核对用户钱包与复式记账账本，返回不一致的科目，为空表示一致
+    pub async fn check_ledger(&self, user_id: u64) -> Result<Vec<LedgerDrift>> {
+        let wallet = AssetRepo::get_wallet(&self.db, user_id).await?;
+        let balances = TransactionsRepo::get_ledger_balances(&self.db, user_id).await?;
+        Ok(ledger_drift(&wallet, &balances))
+    }
+}
@@ -0,0 +1,388 @@
+use crate::model::blockchain::Blockchain;
+use crate::model::ledger::{JournalEntry, LedgerAccount, SystemAccount};
+use crate::model::transactions::{OrderStatus, OrderType, TransactionsBuilder};
+use crate::model::operation_log::{OperationLog, OperationStatus};
+use crate::model::withdrawal::{NewWithdrawal, Withdrawal, WithdrawalReview, WithdrawalStatus};
+use crate::repository::{OperationLogRepo, TransactionsRepo, WithdrawalRepo};
+use crate::service::blockchain::BlockchainService;
+use crate::utils::gen::generate_no;
+use crate::utils::money::{Currency, Money};
+use crate::utils::time_zone::TimeZone;
+use crate::{error::Result, state::AppState, AppError};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::MySqlConnection;
+
+/// 管理员审核决定
+#[derive(Debug, Clone)]
+pub enum ReviewDecision {
+    /// 通过，进入处理中等待出账
+    Approve,
+    /// 拒绝并说明原因，冻结资金退回
+    Reject(String),
+}
+
+impl ReviewDecision {
+    fn action(&self) -> &'static str {
+        match self {
+            ReviewDecision::Approve => "withdrawal_approve",
+            ReviewDecision::Reject(_) => "withdrawal_reject",
+        }
+    }
+}
+
+/// 提现服务：申请时从可用资产转入冻结余额，审核/出账后按状态机结算或解冻
+pub struct WithdrawalService {
+    db: sqlx::MySqlPool,
+    blockchains: BlockchainService,
+}
+
+impl WithdrawalService {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            db: (*state.db).clone(),
+            blockchains: BlockchainService::new(state),
+        }
+    }
+
+    /// 提交提现申请：校验金额与余额，将钱包可用余额转入冻结，生成待审核提现单
+    pub async fn create_withdrawal(
+        &self,
+        user_id: u64,
+        currency: &str,
+        chain: &str,
+        address: &str,
+        amount: Decimal,
+    ) -> Result<Withdrawal> {
+        if currency != Currency::Usdt.to_string() {
+            return Err(AppError::Validation(format!(
+                "Unsupported withdrawal currency: {}",
+                currency
+            )));
+        }
+        let blockchain = self.blockchains.get_enabled_blockchain(chain).await?;
+        if let Err(message) = blockchain.check_address(address) {
+            return Err(AppError::Validation(format!("{} on {}", message, blockchain.code)));
+        }
+        check_withdrawal_amount(&blockchain, amount)?;
+
+        let withdrawal_id = generate_no("W");
+        let mut tx = self.db.begin().await?;
+        WithdrawalRepo::tx_create(
+            &mut tx,
+            &NewWithdrawal {
+                user_id,
+                withdrawal_id: &withdrawal_id,
+                amount,
+                fee: blockchain.fee,
+                currency,
+                blockchain_type: chain,
+                destination_address: address,
+            },
+        )
+        .await?;
+        let current_time = TimeZone::Beijing.get_time();
+        let entry = JournalEntry::new(LedgerAccount::Available(user_id)).transfer(
+            LedgerAccount::Available(user_id),
+            LedgerAccount::Frozen(user_id),
+            Money::usdt(amount),
+        );
+        let mut builder = TransactionsBuilder::default();
+        builder
+            .transaction_id(withdrawal_id.clone())
+            .types(OrderType::Withdraw.to_string())
+            .fee(blockchain.fee)
+            .status(OrderStatus::Pending.to_string())
+            .blockchain_type(chain.to_string())
+            .to_address(address.to_string())
+            .description(format!("withdrawal {}", withdrawal_id))
+            .created_at(current_time)
+            .updated_at(current_time);
+        // 可用余额不足或资金被冻结时记账失败，提现单随事务回滚
+        TransactionsRepo::tx_record(&mut tx, &entry, builder).await?;
+        tx.commit().await?;
+
+        WithdrawalRepo::get_by_withdrawal_id(&self.db, &withdrawal_id)
+            .await?
+            .ok_or_else(|| AppError::Internal("Withdrawal creation failed".to_string()))
+    }
+
+    /// 用户取消待审核的提现，冻结资金退回可用资产
+    pub async fn cancel_withdrawal(&self, user_id: u64, withdrawal_id: &str) -> Result<Withdrawal> {
+        let withdrawal =
+            WithdrawalRepo::get_by_withdrawal_id_and_user_id(&self.db, withdrawal_id, user_id)
+                .await?;
+        if withdrawal.status() != Some(WithdrawalStatus::Pending) {
+            return Err(AppError::Business(
+                "Only pending withdrawals can be cancelled".to_string(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+        let closed = Self::tx_transition(
+            &mut tx,
+            &withdrawal,
+            WithdrawalStatus::Pending,
+            WithdrawalStatus::Cancelled,
+        )
+        .await;
+        match closed {
+            Ok(true) => tx.commit().await?,
+            Ok(false) => {
+                tx.rollback().await?;
+                return Err(AppError::Business(
+                    "Only pending withdrawals can be cancelled".to_string(),
+                ));
+            }
+            Err(err) => {
+                tx.rollback().await?;
+                return Err(err);
+            }
+        }
+
+        WithdrawalRepo::get_by_withdrawal_id_and_user_id(&self.db, withdrawal_id, user_id).await
+    }
+
+    /// 获取用户提现详情
+    pub async fn get_withdrawal(&self, user_id: u64, withdrawal_id: &str) -> Result<Withdrawal> {
+        WithdrawalRepo::get_by_withdrawal_id_and_user_id(&self.db, withdrawal_id, user_id).await
+    }
+
+    /// 分页获取用户提现记录
+    pub async fn get_user_withdrawals(
+        &self,
+        user_id: u64,
+        status: Option<WithdrawalStatus>,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Withdrawal>, u64)> {
+        WithdrawalRepo::get_user_withdrawals(&self.db, user_id, status, page, limit).await
+    }
+
+    /// 分页获取待审核（pending）或待人工复核（review）的提现
+    pub async fn get_pending_reviews(
+        &self,
+        status: WithdrawalStatus,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<WithdrawalReview>, u64)> {
+        WithdrawalRepo::get_pending_reviews(&self.db, status, page, limit).await
+    }
+
+    /// 审核提现：待审核的提现通过进入处理中，待人工复核的提现通过表示已核实链上到账、完成出账；
+    /// 拒绝则标记失败并解冻退回。审核结果与操作日志在同一事务中写入，失败的审核尝试同样记录日志
+    pub async fn review_withdrawal(
+        &self,
+        reviewer_id: u64,
+        withdrawal_id: &str,
+        decision: &ReviewDecision,
+        ip_address: &str,
+    ) -> Result<Withdrawal> {
+        let reason = match decision {
+            ReviewDecision::Approve => None,
+            ReviewDecision::Reject(reason) => Some(reason.as_str()),
+        };
+        let mut log = OperationLog {
+            user_id: Some(reviewer_id),
+            action: decision.action().to_string(),
+            resource_type: "withdrawal".to_string(),
+            resource_id: Some(withdrawal_id.to_string()),
+            ip_address: Some(ip_address.to_string()),
+            request_data: Some(json!({ "reason": reason })),
+            status: OperationStatus::Success,
+            error_message: None,
+        };
+
+        let result = self
+            .tx_review(reviewer_id, withdrawal_id, decision, reason, &mut log)
+            .await;
+        if let Err(err) = &result {
+            log.status = OperationStatus::Failed;
+            log.error_message = Some(err.to_string());
+            let mut conn = self.db.acquire().await?;
+            if let Err(log_err) = OperationLogRepo::tx_create(&mut conn, &log).await {
+                tracing::error!(
+                    "Failed to write review log for withdrawal {}: {}",
+                    withdrawal_id,
+                    log_err
+                );
+            }
+        }
+
+        result
+    }
+
+    async fn tx_review(
+        &self,
+        reviewer_id: u64,
+        withdrawal_id: &str,
+        decision: &ReviewDecision,
+        reason: Option<&str>,
+        log: &mut OperationLog,
+    ) -> Result<Withdrawal> {
+        let Some(withdrawal) = WithdrawalRepo::get_by_withdrawal_id(&self.db, withdrawal_id).await?
+        else {
+            return Err(AppError::NotFound("Withdrawal not found".to_string()));
+        };
+        let from = match withdrawal.status() {
+            Some(WithdrawalStatus::Review) => WithdrawalStatus::Review,
+            _ => WithdrawalStatus::Pending,
+        };
+        let to = match (from, decision) {
+            (_, ReviewDecision::Reject(_)) => WithdrawalStatus::Failed,
+            (WithdrawalStatus::Review, ReviewDecision::Approve) => WithdrawalStatus::Completed,
+            (_, ReviewDecision::Approve) => WithdrawalStatus::Processing,
+        };
+        log.request_data = Some(json!({ "status": to.to_string(), "reason": reason }));
+
+        let mut tx = self.db.begin().await?;
+        let reviewed =
+            match Self::tx_transition(&mut tx, &withdrawal, from, to).await {
+                Ok(reviewed) => reviewed,
+                Err(err) => {
+                    tx.rollback().await?;
+                    return Err(err);
+                }
+            };
+        if !reviewed {
+            tx.rollback().await?;
+            return Err(AppError::Business(format!(
+                "Withdrawal {} is not pending review",
+                withdrawal_id
+            )));
+        }
+        WithdrawalRepo::tx_set_review(&mut tx, withdrawal_id, reviewer_id, reason).await?;
+        OperationLogRepo::tx_create(&mut tx, log).await?;
+        tx.commit().await?;
+
+        WithdrawalRepo::get_by_withdrawal_id(&self.db, withdrawal_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Withdrawal not found".to_string()))
+    }
+
+    /// 批量通过审核，逐笔独立提交，返回每笔的处理结果
+    pub async fn batch_approve(
+        &self,
+        reviewer_id: u64,
+        withdrawal_ids: &[String],
+        ip_address: &str,
+    ) -> Vec<(String, Result<Withdrawal>)> {
+        let mut results = Vec::with_capacity(withdrawal_ids.len());
+        for withdrawal_id in withdrawal_ids {
+            let result = self
+                .review_withdrawal(reviewer_id, withdrawal_id, &ReviewDecision::Approve, ip_address)
+                .await;
+            results.push((withdrawal_id.clone(), result));
+        }
+
+        results
+    }
+
+    /// 在事务中流转提现状态并同步资金与交易记录：
+    /// cancelled/failed 解冻退回钱包可用余额，completed 扣减冻结余额完成出账。
+    /// 提现已被其他流程处理（当前状态不是 from）时返回 false
+    pub async fn tx_transition(
+        tx: &mut MySqlConnection,
+        withdrawal: &Withdrawal,
+        from: WithdrawalStatus,
+        to: WithdrawalStatus,
+    ) -> Result<bool> {
+        let now = TimeZone::Beijing.get_time();
+        if !WithdrawalRepo::tx_update_status(tx, &withdrawal.withdrawal_id, from, to, now).await? {
+            return Ok(false);
+        }
+
+        let tran_status = match to {
+            WithdrawalStatus::Pending | WithdrawalStatus::Processing | WithdrawalStatus::Review => {
+                OrderStatus::Processing
+            }
+            WithdrawalStatus::Completed => OrderStatus::Completed,
+            WithdrawalStatus::Failed => OrderStatus::Failed,
+            WithdrawalStatus::Cancelled => OrderStatus::Cancelled,
+        };
+        if to.is_final() {
+            let currency: Currency = withdrawal.currency.parse().map_err(|_| {
+                AppError::Internal(format!(
+                    "Unsupported currency {} on withdrawal {}",
+                    withdrawal.currency, withdrawal.withdrawal_id
+                ))
+            })?;
+            let frozen = LedgerAccount::Frozen(withdrawal.user_id);
+            let entry = if to == WithdrawalStatus::Completed {
+                // 实际到账部分转出至用户链上钱包，手续费计入平台收入
+                JournalEntry::new(frozen)
+                    .transfer(
+                        frozen,
+                        LedgerAccount::External(withdrawal.user_id),
+                        Money::new(withdrawal.receive_amount(), currency),
+                    )
+                    .transfer(
+                        frozen,
+                        LedgerAccount::System(SystemAccount::Fees),
+                        Money::new(withdrawal.fee, currency),
+                    )
+            } else {
+                JournalEntry::new(frozen).transfer(
+                    frozen,
+                    LedgerAccount::Available(withdrawal.user_id),
+                    Money::new(withdrawal.amount, currency),
+                )
+            };
+            TransactionsRepo::tx_post(tx, &withdrawal.withdrawal_id, &entry).await?;
+        }
+        TransactionsRepo::tx_update_status_by_transaction_id(
+            tx,
+            &withdrawal.withdrawal_id,
+            tran_status,
+            (to == WithdrawalStatus::Completed).then_some(now),
+        )
+        .await?;
+
+        Ok(true)
+    }
+}
+
+/// 校验提现金额：需在链的限额范围内且大于手续费
+pub fn check_withdrawal_amount(chain: &Blockchain, amount: Decimal) -> Result<()> {
+    if amount < chain.min_amount || amount > chain.max_amount {
+        return Err(AppError::Validation(format!(
+            "Withdrawal amount must be between {} and {} on {}",
+            chain.min_amount, chain.max_amount, chain.code
+        )));
+    }
+    if amount <= chain.fee {
+        return Err(AppError::Validation(format!(
+            "Withdrawal amount must be greater than the {} fee of {}",
+            chain.code, chain.fee
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::blockchain::AddressFormat;
+
+    #[test]
+    fn test_check_withdrawal_amount() {
+        let chain = Blockchain {
+            code: "TRC20".to_string(),
+            name: "TRC20 (USDT)".to_string(),
+            enabled: true,
+            fee: Decimal::from(15),
+            min_amount: Decimal::from(10),
+            max_amount: Decimal::from(1000),
+            confirmations: 19,
+            address_format: AddressFormat::Tron,
+            icon: String::new(),
+            confirmation_time: String::new(),
+        };
+        assert!(check_withdrawal_amount(&chain, Decimal::from(100)).is_ok());
+        assert!(check_withdrawal_amount(&chain, Decimal::from(5)).is_err());
+        assert!(check_withdrawal_amount(&chain, Decimal::from(1001)).is_err());
+        // 金额不足以覆盖手续费
+        assert!(check_withdrawal_amount(&chain, Decimal::from(12)).is_err());
+    }
+}
@@ -1,4 +1,8 @@
+use crate::chain::address::AddressProviders;
+use crate::chain::payout::PayoutProviders;
+use crate::chain::ChainWatchers;
 use crate::config::Config;
+use crate::price::PriceSource;
 use crate::websocket::hub::WsHub;
 use sqlx::MySqlPool;
 use std::sync::Arc;
@@ -10,6 +14,10 @@ pub struct AppState {
     pub db: Arc<MySqlPool>,
     pub ws_hub: Arc<RwLock<WsHub>>,
     pub cron_scheduler: Arc<crate::cron::scheduler::CronSchedulerManager>,
+    pub chain_watchers: Arc<ChainWatchers>,
+    pub address_providers: Arc<AddressProviders>,
+    pub payout_providers: Arc<PayoutProviders>,
+    pub price_source: Option<Arc<dyn PriceSource>>,
 }
 
 impl AppState {
@@ -32,11 +40,24 @@ impl AppState {
 
         // 创建定时任务调度器
         let cron_scheduler = Arc::new(crate::cron::scheduler::CronSchedulerManager::new());
+
+        // 创建链上支付监听器
+        let chain_watchers = Arc::new(ChainWatchers::from_config(&config.chain)?);
+        // 创建收款地址派生器
+        let address_providers = Arc::new(AddressProviders::from_config(&config.chain)?);
+        // 创建提现出账广播器
+        let payout_providers = Arc::new(PayoutProviders::from_config(&config.chain)?);
+        // 创建行情数据源
+        let price_source = crate::price::from_config(&config.price)?;
         let state = Self {
             config: Arc::new(config),
             db,
             ws_hub,
             cron_scheduler,
+            chain_watchers,
+            address_providers,
+            payout_providers,
+            price_source,
         };
         state.health_check().await?;
 
@@ -0,0 +1,157 @@
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use validator::ValidationError;
+
+/// Base58 字母表（不含 0、O、I、l）
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// TRON 主网地址版本字节
+const TRON_ADDRESS_PREFIX: u8 = 0x41;
+
+/// 校验 TRON 地址：T 开头的 Base58Check 编码，解码后为 0x41 + 20 字节地址 + 4 字节校验和
+pub fn check_tron_address(address: &str) -> Result<(), &'static str> {
+    if address.len() != 34 || !address.starts_with('T') {
+        return Err("Invalid TRON address: must be 34 characters starting with T");
+    }
+    let Some(bytes) = base58_decode(address) else {
+        return Err("Invalid TRON address: contains non-Base58 characters");
+    };
+    if bytes.len() != 25 || bytes[0] != TRON_ADDRESS_PREFIX {
+        return Err("Invalid TRON address: wrong length or version byte");
+    }
+    let checksum = Sha256::digest(Sha256::digest(&bytes[..21]));
+    if checksum[..4] != bytes[21..] {
+        return Err("Invalid TRON address: checksum mismatch");
+    }
+
+    Ok(())
+}
+
+/// 校验以太坊（EVM）地址：0x 开头的 40 位十六进制，大小写混合时需符合 EIP-55 校验
+pub fn check_evm_address(address: &str) -> Result<(), &'static str> {
+    let Some(hex) = address.strip_prefix("0x") else {
+        return Err("Invalid Ethereum address: must start with 0x");
+    };
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Invalid Ethereum address: must be 0x followed by 40 hex characters");
+    }
+    // 全小写或全大写的地址不携带校验信息
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && to_checksum_address(hex) != hex {
+        return Err("Invalid Ethereum address: EIP-55 checksum mismatch");
+    }
+
+    Ok(())
+}
+
+pub fn is_tron_address(address: &str) -> bool {
+    check_tron_address(address).is_ok()
+}
+
+pub fn is_evm_address(address: &str) -> bool {
+    check_evm_address(address).is_ok()
+}
+
+/// validator 自定义规则：按前缀校验 TRON 或以太坊地址，
+/// 用法 `#[validate(custom(function = "crate::utils::address::validate_blockchain_address"))]`
+pub fn validate_blockchain_address(address: &str) -> Result<(), ValidationError> {
+    let result = if address.starts_with('T') {
+        check_tron_address(address)
+    } else if address.starts_with("0x") {
+        check_evm_address(address)
+    } else {
+        Err("Invalid address: expected a TRON (T...) or Ethereum (0x...) address")
+    };
+
+    result.map_err(|message| ValidationError::new("blockchain_address").with_message(message.into()))
+}
+
+/// 按 EIP-55 生成 40 位十六进制地址（不含 0x）的校验大小写形式
+fn to_checksum_address(hex: &str) -> String {
+    let lower = hex.to_ascii_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+    lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Base58 解码，包含非法字符时返回 None
+fn base58_decode(input: &str) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in input.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    // 前导的 '1' 对应前导零字节
+    let zeros = input.bytes().take_while(|&c| c == b'1').count();
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes);
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tron_address() {
+        assert!(is_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+        assert_eq!(
+            check_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u"),
+            Err("Invalid TRON address: checksum mismatch")
+        );
+        assert_eq!(
+            check_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj60"),
+            Err("Invalid TRON address: contains non-Base58 characters")
+        );
+        assert!(!is_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6"));
+        assert!(!is_tron_address("0xdAC17F958D2ee523a2206206994597C13D831ec7"));
+    }
+
+    #[test]
+    fn test_evm_address() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+            "0xdac17f958d2ee523a2206206994597c13d831ec7",
+        ] {
+            assert!(is_evm_address(address), "{}", address);
+        }
+        assert_eq!(
+            check_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err("Invalid Ethereum address: EIP-55 checksum mismatch")
+        );
+        assert!(!is_evm_address("dAC17F958D2ee523a2206206994597C13D831ec7"));
+        assert!(!is_evm_address("0xdAC17F958D2ee523a2206206994597C13D831ecg"));
+    }
+
+    #[test]
+    fn test_validate_blockchain_address() {
+        assert!(validate_blockchain_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t").is_ok());
+        assert!(validate_blockchain_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_ok());
+        let err = validate_blockchain_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq")
+            .unwrap_err();
+        assert_eq!(err.code, "blockchain_address");
+        assert!(err.message.is_some());
+    }
+}
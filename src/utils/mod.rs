@@ -1,7 +1,9 @@
+pub mod address;
 pub mod convert;
 pub mod file_upload;
 pub mod gen;
 pub mod jwt;
+pub mod money;
 pub mod password;
 pub mod qrcode;
 pub mod time_zone;
@@ -0,0 +1,186 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use strum::{Display, EnumIter, EnumString};
+
+/// 金额存储精度，与数据库 decimal(20,8) 一致
+pub const MONEY_SCALE: u32 = 8;
+
+/// 金额舍入规则：银行家舍入（四舍六入五取偶），避免批量计算时的系统性偏差
+pub const MONEY_ROUNDING: RoundingStrategy = RoundingStrategy::MidpointNearestEven;
+
+/// 币种
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Currency {
+    Usdt,
+    Dg,
+    /// 平台积分，仅站内使用，不可提现
+    Points,
+}
+
+/// 带币种的金额，所有运算结果统一按 MONEY_SCALE 与 MONEY_ROUNDING 舍入
+///
+/// 不同币种的金额相加减属于编程错误，会直接 panic；币种之间的转换必须通过 `exchange` 显式进行。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    amount: Decimal,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self {
+            amount: round(amount),
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(Decimal::ZERO, currency)
+    }
+
+    pub fn usdt(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Usdt)
+    }
+
+    pub fn dg(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Dg)
+    }
+
+    /// 用于写库的金额
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > Decimal::ZERO
+    }
+
+    /// 按百分比计算，如邀请分成、日收益率
+    pub fn percent(&self, rate: Decimal) -> Self {
+        Self::new(self.amount * rate / Decimal::ONE_HUNDRED, self.currency)
+    }
+
+    /// 按汇率换算为另一币种：1 单位当前币种 = rate 单位目标币种
+    pub fn exchange(&self, rate: Decimal, to: Currency) -> Self {
+        Self::new(self.amount * rate, to)
+    }
+
+    /// 按单价换算为另一币种：1 单位目标币种 = price 单位当前币种，price 为 0 时返回 None
+    pub fn convert_at_price(&self, price: Decimal, to: Currency) -> Option<Self> {
+        self.amount
+            .checked_div(price)
+            .map(|amount| Self::new(amount, to))
+    }
+
+    /// 取两者较小值
+    pub fn min(self, other: Self) -> Self {
+        assert_same_currency(&self, &other);
+        if self.amount <= other.amount {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// 相减，结果小于 0 时取 0
+    pub fn saturating_sub(self, other: Self) -> Self {
+        assert_same_currency(&self, &other);
+        Self::new((self.amount - other.amount).max(Decimal::ZERO), self.currency)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_same_currency(&self, &rhs);
+        Money::new(self.amount + rhs.amount, self.currency)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_same_currency(&self, &rhs);
+        Money::new(self.amount - rhs.amount, self.currency)
+    }
+}
+
+impl Mul<u32> for Money {
+    type Output = Money;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Money::new(self.amount * Decimal::from(rhs), self.currency)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+fn round(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(MONEY_SCALE, MONEY_ROUNDING)
+}
+
+fn assert_same_currency(a: &Money, b: &Money) {
+    assert_eq!(
+        a.currency, b.currency,
+        "currency mismatch: {} vs {}",
+        a.currency, b.currency
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_money_keeps_eight_decimal_places() {
+        let price = Money::usdt(dec("99.12345678"));
+        assert_eq!((price * 3).amount(), dec("297.37037034"));
+        assert_eq!(Money::usdt(dec("0.000000005")).amount(), dec("0.00000000"));
+        assert_eq!(Money::usdt(dec("0.000000015")).amount(), dec("0.00000002"));
+    }
+
+    #[test]
+    fn test_money_percent_and_conversion() {
+        let paid = Money::usdt(dec("333.33333333"));
+        assert_eq!(paid.percent(Decimal::from(10)).amount(), dec("33.33333333"));
+        assert_eq!(
+            paid.convert_at_price(Decimal::from(3), Currency::Dg),
+            Some(Money::dg(dec("111.11111111")))
+        );
+        assert_eq!(paid.convert_at_price(Decimal::ZERO, Currency::Dg), None);
+        assert_eq!(
+            Money::usdt(Decimal::from(5)).saturating_sub(Money::usdt(Decimal::from(8))),
+            Money::zero(Currency::Usdt)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "currency mismatch")]
+    fn test_money_rejects_mixed_currencies() {
+        let _ = Money::usdt(Decimal::ONE) + Money::dg(Decimal::ONE);
+    }
+}
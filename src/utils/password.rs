@@ -95,6 +95,7 @@ mod tests {
             password_require_uppercase: true,
             rate_limit_requests: 100,
             rate_limit_window: 60,
+            idempotency_ttl: 86400,
         }
     }
 
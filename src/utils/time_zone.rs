@@ -13,9 +13,18 @@ const BEIJING_OFFSET: UtcOffset = {
 pub enum TimeZone {
     #[strum(to_string = "Asia/Shanghai")]
     Beijing,
+    #[strum(to_string = "UTC")]
+    Utc,
 }
 
 impl TimeZone {
+    pub fn offset(&self) -> UtcOffset {
+        match self {
+            TimeZone::Beijing => BEIJING_OFFSET,
+            TimeZone::Utc => UtcOffset::UTC,
+        }
+    }
+
     pub fn get_time(&self) -> OffsetDateTime {
         match self.to_string().as_str() {
             "Asia/Shanghai" =>  OffsetDateTime::now_utc().to_offset(BEIJING_OFFSET),